@@ -0,0 +1,58 @@
+use aoc::Solution;
+
+fn rotate_vector_of_vectors<T>(vec: &Vec<Vec<T>>) -> Vec<Vec<T>>
+where
+    T: Copy,
+{
+    assert!(!vec.is_empty());
+    assert!(!vec[0].is_empty());
+    (0..vec[0].len())
+        .map(|i| (0..vec.len()).map(|j| vec[j][i]).collect())
+        .collect()
+}
+
+pub fn list_distance(lines: &Vec<Vec<i64>>) -> i64 {
+    let mut lists: Vec<Vec<i64>> = rotate_vector_of_vectors(lines);
+    lists.iter_mut().map(|x| x.sort()).last();
+    let pairs = rotate_vector_of_vectors(&lists);
+
+    pairs.iter().map(|x| (x[1] - x[0]).abs()).sum()
+}
+
+pub fn list_similarity(lines: &Vec<Vec<i64>>) -> i64 {
+    let lists: Vec<Vec<i64>> = rotate_vector_of_vectors(lines);
+    lists[0]
+        .iter()
+        .map(|&i| i * lists[1].iter().filter(|&&j| j == i).count() as i64)
+        .sum()
+}
+
+pub fn parse_input(data: &str) -> aoc::Result<Vec<Vec<i64>>> {
+    let lines = aoc::lines(data)
+        .map(|line| {
+            let nums = line.parse_whitespace_list::<i64>()?;
+            if nums.len() != 2 {
+                return Err(line.error(line.text, "expected two numbers"));
+            }
+            Ok(nums)
+        })
+        .collect::<aoc::Result<Vec<_>>>()?;
+    if lines.is_empty() {
+        return Err(aoc::AocError::parse(1, 1, "empty input"));
+    }
+    Ok(lines)
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    fn part1(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        Ok(list_distance(&parse_input(input)?).to_string())
+    }
+
+    fn part2(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        Ok(list_similarity(&parse_input(input)?).to_string())
+    }
+}
+
+aoc::example_tests!(Day01);
@@ -0,0 +1,51 @@
+use aoc::Solution;
+
+pub fn is_safe(report: &Vec<i64>) -> bool {
+    let diffs = report
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .collect::<Vec<_>>();
+    let all_positive = diffs.iter().all(|&x| x > 0);
+    let all_negative = diffs.iter().all(|&x| x < 0);
+    let max_magnitude = diffs.iter().map(|x| x.abs()).max().unwrap_or(0);
+    let safe = (all_positive || all_negative) && max_magnitude <= 3;
+
+    safe
+}
+
+pub fn is_safe_with_dampener(report: &Vec<i64>) -> bool {
+    (0..report.len())
+        .map(|i| [&report[0..i], &report[i + 1..report.len()]].concat())
+        .any(|dampened| is_safe(&dampened))
+}
+
+pub fn safe_reports(reports: &Vec<Vec<i64>>) -> usize {
+    reports.iter().filter(|report| is_safe(report)).count()
+}
+
+pub fn safe_reports_with_dampener(reports: &Vec<Vec<i64>>) -> usize {
+    reports
+        .iter()
+        .filter(|report| is_safe_with_dampener(report))
+        .count()
+}
+
+pub fn parse_input(data: &str) -> aoc::Result<Vec<Vec<i64>>> {
+    aoc::lines(data)
+        .map(|line| line.parse_whitespace_list::<i64>())
+        .collect()
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    fn part1(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        Ok(safe_reports(&parse_input(input)?).to_string())
+    }
+
+    fn part2(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        Ok(safe_reports_with_dampener(&parse_input(input)?).to_string())
+    }
+}
+
+aoc::example_tests!(Day02);
@@ -0,0 +1,165 @@
+use aoc::{AocError, Solution};
+
+#[derive(Debug, Clone)]
+pub struct Direction {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Position {
+    x: i32,
+    y: i32,
+}
+
+impl Position {
+    pub fn from(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn mv(&self, dir: &Direction, count: i32) -> Self {
+        let x = self.x + dir.x * count;
+        let y = self.y + dir.y * count;
+        Self { x, y }
+    }
+}
+
+#[derive(Debug)]
+pub struct Grid<'a> {
+    grid: Vec<&'a str>,
+    x_size: i32,
+    y_size: i32,
+}
+
+impl<'a> Grid<'a> {
+    pub fn from(buf: &'a str) -> aoc::Result<Self> {
+        let grid = buf.lines().collect::<Vec<_>>();
+        let Some(first) = grid.first() else {
+            return Err(AocError::parse(1, 1, "empty grid"));
+        };
+        for line in aoc::lines(buf) {
+            if line.text.len() != first.len() {
+                return Err(line.error(
+                    line.end(),
+                    format!("expected row of length {}", first.len()),
+                ));
+            }
+        }
+        let too_large = |_| AocError::parse(1, 1, "grid is too large");
+        let x_size = first.len().try_into().map_err(too_large)?;
+        let y_size = grid.len().try_into().map_err(too_large)?;
+        Ok(Self {
+            grid,
+            x_size,
+            y_size,
+        })
+    }
+
+    pub fn at(&self, pos: Position) -> Option<u8> {
+        if pos.x >= 0 && pos.x < self.x_size && pos.y >= 0 && pos.y < self.y_size {
+            Some(self.grid[pos.y as usize].as_bytes()[pos.x as usize])
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&'a self) -> impl Iterator<Item = (Position, u8)> + 'a {
+        self.grid.iter().enumerate().flat_map(|(y, &line)| {
+            line.bytes()
+                .enumerate()
+                .map(move |(x, c)| (Position::from(x as i32, y as i32), c))
+        })
+    }
+
+    pub fn x_mas_match(&self, pos: &Position) -> bool {
+        const DIRECTIONS: &'static [&[Direction]] = &[
+            &[
+                Direction { x: 1, y: -1 }, // up right
+                Direction { x: -1, y: 1 }, // down left
+            ],
+            &[
+                Direction { x: -1, y: -1 }, // up left
+                Direction { x: 1, y: 1 },   // down right
+            ],
+        ];
+
+        DIRECTIONS.iter().all(|&dirs| {
+            dirs.iter()
+                .any(|dir| self.str_match_dir("MAS", pos, dir, 1))
+        })
+    }
+
+    pub fn xmas_count(&self, pos: &Position) -> usize {
+        const DIRECTIONS: &'static [Direction] = &[
+            Direction { x: 1, y: 0 },   // right
+            Direction { x: -1, y: 0 },  // left
+            Direction { x: 0, y: -1 },  // up
+            Direction { x: 0, y: 1 },   // down
+            Direction { x: 1, y: -1 },  // up right
+            Direction { x: -1, y: -1 }, // up left
+            Direction { x: 1, y: 1 },   // down right
+            Direction { x: -1, y: 1 },  // down left
+        ];
+
+        DIRECTIONS
+            .iter()
+            .filter(|&dir| self.str_match_dir("XMAS", pos, dir, 0))
+            .count()
+    }
+
+    pub fn str_match_dir(&self, s: &str, pos: &Position, dir: &Direction, offset: i32) -> bool {
+        s.bytes()
+            .enumerate()
+            .all(|(i, c)| self.at(pos.mv(dir, i as i32 - offset)).unwrap_or(0) == c)
+    }
+}
+
+pub fn count_of_xmas(grid: &Grid) -> usize {
+    grid.iter()
+        .filter(|&(_, c)| c == b'X')
+        .map(|(pos, _)| grid.xmas_count(&pos))
+        .sum()
+}
+
+pub fn count_of_x_mas(grid: &Grid) -> usize {
+    grid.iter()
+        .filter(|&(_, c)| c == b'A')
+        .filter(|(pos, _)| grid.x_mas_match(&pos))
+        .count()
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    fn part1(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        Ok(count_of_xmas(&Grid::from(input)?).to_string())
+    }
+
+    fn part2(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        Ok(count_of_x_mas(&Grid::from(input)?).to_string())
+    }
+}
+
+aoc::example_tests!(Day04);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::read_to_string;
+
+    #[test]
+    fn answer_part1() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let grid = Grid::from(&data).unwrap();
+        let count_of_xmas = count_of_xmas(&grid);
+        assert_eq!(count_of_xmas, 2390);
+    }
+
+    #[test]
+    fn answer_part2() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let grid = Grid::from(&data).unwrap();
+        let count_of_x_mas = count_of_x_mas(&grid);
+        assert_eq!(count_of_x_mas, 1809);
+    }
+}
@@ -0,0 +1,122 @@
+use aoc::Solution;
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+};
+
+pub type Orders = HashMap<u32, HashSet<u32>>;
+
+pub fn parse_input(data: &str) -> aoc::Result<(Orders, Vec<Vec<u32>>)> {
+    let mut lines = aoc::lines(data);
+
+    let mut orders = Orders::new();
+    for line in lines.by_ref().take_while(|line| !line.text.is_empty()) {
+        let (from, to) = line.split_once("|")?;
+        let from = line.parse::<u32>(from)?;
+        let to = line.parse::<u32>(to)?;
+        orders.entry(from).or_default().insert(to);
+    }
+
+    let pages = lines
+        .map(|line| {
+            let pages = line.parse_list::<u32>(",")?;
+            if pages.len() % 2 == 0 {
+                return Err(line.error(line.text, "expected an odd number of pages"));
+            }
+            Ok(pages)
+        })
+        .collect::<aoc::Result<Vec<_>>>()?;
+
+    Ok((orders, pages))
+}
+
+pub fn page_is_valid(orders: &HashMap<u32, HashSet<u32>>, page: u32, before: &[u32]) -> bool {
+    let Some(page_must_be_before) = orders.get(&page) else {
+        return true;
+    };
+
+    !before.iter().any(|&a| page_must_be_before.contains(&a))
+}
+
+pub fn valid_pages(orders: &HashMap<u32, HashSet<u32>>, pages: &[u32]) -> bool {
+    pages
+        .iter()
+        .enumerate()
+        .all(|(i, &page)| page_is_valid(orders, page, &pages[..i]))
+}
+
+pub fn reorder_pages(orders: &HashMap<u32, HashSet<u32>>, pages: &[u32]) -> Vec<u32> {
+    let mut pages = pages.to_vec();
+    pages.sort_by(|a, b| match orders.get(a) {
+        Some(a_must_be_before) => {
+            if a_must_be_before.contains(b) {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        }
+        None => Ordering::Greater,
+    });
+    pages
+}
+
+pub fn sum_of_middle_digits_of_valid_updates(
+    orders: &HashMap<u32, HashSet<u32>>,
+    pages: &[Vec<u32>],
+) -> u32 {
+    pages
+        .iter()
+        .filter(|&pages| valid_pages(orders, pages))
+        .map(|v| v[v.len() / 2])
+        .sum()
+}
+
+pub fn sum_of_middle_digits_of_fixed_updates(
+    orders: &HashMap<u32, HashSet<u32>>,
+    pages: &[Vec<u32>],
+) -> u32 {
+    pages
+        .iter()
+        .filter(|&pages| !valid_pages(orders, pages))
+        .map(|pages| reorder_pages(orders, pages))
+        .map(|v| v[v.len() / 2])
+        .sum()
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    fn part1(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        let (orders, pages) = parse_input(input)?;
+        Ok(sum_of_middle_digits_of_valid_updates(&orders, &pages).to_string())
+    }
+
+    fn part2(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        let (orders, pages) = parse_input(input)?;
+        Ok(sum_of_middle_digits_of_fixed_updates(&orders, &pages).to_string())
+    }
+}
+
+aoc::example_tests!(Day05);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::read_to_string;
+
+    #[test]
+    fn answer_part1() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let (orders, pages) = parse_input(&data).unwrap();
+        let sum = sum_of_middle_digits_of_valid_updates(&orders, &pages);
+        assert_eq!(sum, 5275);
+    }
+
+    #[test]
+    fn answer_part2() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let (orders, pages) = parse_input(&data).unwrap();
+        let sum = sum_of_middle_digits_of_fixed_updates(&orders, &pages);
+        assert_eq!(sum, 6191);
+    }
+}
@@ -0,0 +1,240 @@
+use aoc::{AocError, Solution};
+use std::collections::HashSet;
+
+#[derive(Clone, Debug)]
+pub struct Grid<T>
+where
+    T: Default,
+{
+    x_size: usize,
+    y_size: usize,
+    grid: Vec<T>,
+}
+
+impl<T> Grid<T>
+where
+    T: Default + Clone + Copy,
+{
+    pub fn new(x_size: usize, y_size: usize) -> Self {
+        Self {
+            x_size,
+            y_size,
+            grid: vec![T::default(); x_size * y_size],
+        }
+    }
+
+    pub fn from_file(data: &str) -> aoc::Result<Grid<u8>> {
+        let lines = data.lines().collect::<Vec<_>>();
+        let Some(first) = lines.first() else {
+            return Err(AocError::parse(1, 1, "empty grid"));
+        };
+        let x_size = first.len();
+        let y_size = lines.len();
+        for line in aoc::lines(data) {
+            for (x, c) in line.text.bytes().enumerate() {
+                if !matches!(c, b'.' | b'#' | b'^') {
+                    return Err(AocError::parse(
+                        line.num,
+                        x + 1,
+                        format!("unexpected character {:?}", c as char),
+                    ));
+                }
+            }
+            if line.text.len() != x_size {
+                return Err(line.error(line.end(), format!("expected row of length {}", x_size)));
+            }
+        }
+        let grid = lines.iter().flat_map(|line| line.bytes()).collect();
+        Ok(Grid::<u8> {
+            x_size,
+            y_size,
+            grid,
+        })
+    }
+
+    pub fn from_iter<I>(x_size: usize, y_size: usize, iter: I) -> Self
+    where
+        I: Iterator<Item = T>,
+    {
+        let grid = iter.collect();
+        Self {
+            x_size,
+            y_size,
+            grid,
+        }
+    }
+
+    pub fn valid_pos(&self, pos: &Position) -> bool {
+        pos.x >= 0 && (pos.x as usize) < self.x_size && pos.y >= 0 && (pos.y as usize) < self.y_size
+    }
+
+    pub fn at(&self, pos: &Position) -> Option<T> {
+        if self.valid_pos(pos) {
+            Some(self.grid[pos.y as usize * self.x_size + pos.x as usize])
+        } else {
+            None
+        }
+    }
+
+    pub fn at_mut(&mut self, pos: &Position) -> Option<&mut T> {
+        if self.valid_pos(pos) {
+            self.grid
+                .get_mut(pos.y as usize * self.x_size + pos.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.grid.iter()
+    }
+
+    pub fn iter_positions(&self) -> impl Iterator<Item = Position> + '_ {
+        (0..self.y_size).flat_map(|y| {
+            (0..self.x_size)
+                .map(move |x| Position::new(x.try_into().unwrap(), y.try_into().unwrap()))
+        })
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Hash, Debug)]
+pub struct Position {
+    x: i32,
+    y: i32,
+}
+
+impl Position {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+    pub fn step(&self, dir: &Direction) -> Self {
+        Self {
+            x: self.x + dir.x,
+            y: self.y + dir.y,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct Direction {
+    x: i32,
+    y: i32,
+}
+
+impl Direction {
+    pub fn rotate_90_cw(self) -> Self {
+        match self {
+            UP => RIGHT,
+            RIGHT => DOWN,
+            DOWN => LEFT,
+            LEFT => UP,
+            _ => panic!(),
+        }
+    }
+}
+
+pub const UP: Direction = Direction { x: 0, y: -1 };
+pub const DOWN: Direction = Direction { x: 0, y: 1 };
+pub const LEFT: Direction = Direction { x: -1, y: 0 };
+pub const RIGHT: Direction = Direction { x: 1, y: 0 };
+
+pub fn parse_input(data: &str) -> aoc::Result<(Grid<bool>, Position)> {
+    let text_grid = Grid::<u8>::from_file(data)?;
+    let obstruction_grid = Grid::from_iter(
+        text_grid.x_size,
+        text_grid.y_size,
+        text_grid.iter().map(|&c| c == b'#'),
+    );
+    let start_position = text_grid
+        .iter_positions()
+        .find(|&pos| text_grid.at(&pos) == Some(b'^'))
+        .ok_or_else(|| AocError::invalid_state("no guard '^' in map"))?;
+    Ok((obstruction_grid, start_position))
+}
+
+pub fn walk_to_exit(grid: &Grid<bool>, start: &Position) -> Option<usize> {
+    let mut visited_grid = Grid::<bool>::new(grid.x_size, grid.y_size);
+    let mut dir = UP;
+    let mut pos = start.clone();
+    let mut visited_obstacles = HashSet::<(Position, Direction)>::new();
+    loop {
+        let v = visited_grid.at_mut(&pos).expect("pos is valid");
+        *v = true;
+
+        let mut next_pos = pos.step(&dir);
+        if !grid.valid_pos(&next_pos) {
+            break;
+        }
+        if grid.at(&next_pos).expect("next_pos is valid") {
+            // obstruction
+            let key: (Position, Direction) = (pos.clone(), dir.clone());
+            if visited_obstacles.contains(&key) {
+                return None;
+            }
+            visited_obstacles.insert(key);
+
+            dir = dir.rotate_90_cw();
+            next_pos = pos.step(&dir);
+        }
+        if !grid.valid_pos(&next_pos) {
+            break;
+        }
+
+        pos = next_pos;
+    }
+
+    Some(visited_grid.iter().filter(|&&v| v).count())
+}
+
+pub fn grid_with_obstruction(grid: &Grid<bool>, pos: &Position) -> Grid<bool> {
+    let mut new_grid = grid.clone();
+    *new_grid.at_mut(pos).expect("pos is valid") = true;
+    new_grid
+}
+
+pub fn count_possible_looping_obstructions(grid: &Grid<bool>, start: &Position) -> usize {
+    grid.iter_positions()
+        .filter(|pos| pos != start)
+        .filter(|pos| walk_to_exit(&grid_with_obstruction(grid, pos), start).is_none())
+        .count()
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    fn part1(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        let (grid, start) = parse_input(input)?;
+        let visited = walk_to_exit(&grid, &start)
+            .ok_or_else(|| AocError::no_solution("guard walks in a loop"))?;
+        Ok(visited.to_string())
+    }
+
+    fn part2(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        let (grid, start) = parse_input(input)?;
+        Ok(count_possible_looping_obstructions(&grid, &start).to_string())
+    }
+}
+
+aoc::example_tests!(Day06);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::read_to_string;
+
+    #[test]
+    fn answer_part1() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let (grid, start) = parse_input(&data).unwrap();
+        let visited = walk_to_exit(&grid, &start).expect("not a loop");
+        assert_eq!(visited, 4988);
+    }
+
+    #[test]
+    fn answer_part2() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let (grid, start) = parse_input(&data).unwrap();
+        let visited = count_possible_looping_obstructions(&grid, &start);
+        assert_eq!(visited, 1697);
+    }
+}
@@ -0,0 +1,249 @@
+use aoc::Solution;
+
+#[derive(Debug)]
+pub struct Equation {
+    answer: u64,
+    nums: Vec<u64>,
+}
+
+impl Equation {
+    pub fn from(line: &aoc::Line) -> aoc::Result<Self> {
+        let (answer_str, nums_str) = line.split_once(":")?;
+        let answer = line.parse(answer_str)?;
+        let nums = nums_str
+            .split_ascii_whitespace()
+            .map(|n| line.parse(n))
+            .collect::<aoc::Result<Vec<_>>>()?;
+        if nums.is_empty() {
+            return Err(line.error(nums_str, "expected at least one number"));
+        }
+        Ok(Self { answer, nums })
+    }
+
+    pub fn apply_operators(&self, operators: &[Operator]) -> u64 {
+        assert_eq!(operators.len(), self.nums.len() - 1);
+        self.nums[1..]
+            .iter()
+            .enumerate()
+            .fold(self.nums[0], |accum, (i, &n)| operators[i].apply(accum, n))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Operator {
+    f: fn(u64, u64) -> u64,
+    // Given the result and the right operand, returns the left operand if there is one.
+    inverse: fn(u64, u64) -> Option<u64>,
+}
+
+impl Operator {
+    pub fn apply(&self, a: u64, b: u64) -> u64 {
+        (self.f)(a, b)
+    }
+
+    pub fn unapply(&self, result: u64, b: u64) -> Option<u64> {
+        (self.inverse)(result, b)
+    }
+}
+
+pub const ADD: Operator = Operator {
+    f: |a, b| a + b,
+    inverse: |result, b| result.checked_sub(b),
+};
+pub const MUL: Operator = Operator {
+    f: |a, b| a * b,
+    inverse: |result, b| (b != 0 && result % b == 0).then(|| result / b),
+};
+pub const CONCAT: Operator = Operator {
+    f: |a, b| (10 as u64).pow(count_digits(b)) * a + b,
+    inverse: |result, b| {
+        let shift = 10_u64.pow(count_digits(b));
+        (result % shift == b).then(|| result / shift)
+    },
+};
+
+fn count_digits(n: u64) -> u32 {
+    (n as f64).log10() as u32 + 1
+}
+
+pub const FIRST_OPERATORS: &[Operator] = &[ADD, MUL];
+pub const SECOND_OPERATORS: &[Operator] = &[ADD, MUL, CONCAT];
+
+pub struct OperatorIterator<'a> {
+    operator_list: &'a [Operator],
+    len: u32,
+    i: u64,
+}
+
+impl<'a> OperatorIterator<'a> {
+    pub fn new(operator_list: &'a [Operator], len: usize) -> Self {
+        Self {
+            operator_list,
+            len: len as u32,
+            i: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for OperatorIterator<'a> {
+    type Item = Vec<Operator>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let num_operators = self.operator_list.len() as u64;
+        if self.i < num_operators.pow(self.len) {
+            let mut operators = vec![ADD; self.len as usize];
+            for (operator_number, operator) in operators.iter_mut().enumerate() {
+                *operator = self.operator_list[((self.i
+                    / num_operators.pow(operator_number as u32))
+                    % num_operators) as usize]
+            }
+            self.i += 1;
+            Some(operators)
+        } else {
+            None
+        }
+    }
+}
+
+pub fn solvable_equation(operators: &[Operator], equation: &Equation) -> bool {
+    OperatorIterator::new(operators, equation.nums.len() - 1)
+        .any(|operators| equation.apply_operators(&operators) == equation.answer)
+}
+
+/// Works backwards from the answer, undoing the last operator at each step.  This prunes every
+/// combination of operators whose last step can't produce the answer.
+pub fn solvable_equation_reverse(operators: &[Operator], equation: &Equation) -> bool {
+    fn solvable(operators: &[Operator], answer: u64, nums: &[u64]) -> bool {
+        match nums.split_last() {
+            None => false,
+            Some((&first, [])) => answer == first,
+            Some((&last, rest)) => operators.iter().any(|operator| {
+                operator
+                    .unapply(answer, last)
+                    .is_some_and(|answer| solvable(operators, answer, rest))
+            }),
+        }
+    }
+    solvable(operators, equation.answer, &equation.nums)
+}
+
+pub fn solvable_equations_sum(equations: &[Equation]) -> u64 {
+    equations
+        .iter()
+        .filter(|&equation| solvable_equation_reverse(FIRST_OPERATORS, equation))
+        .map(|equation| equation.answer)
+        .sum()
+}
+
+pub fn solvable_equations_with_concat_sum(equations: &[Equation]) -> u64 {
+    equations
+        .iter()
+        .filter(|&equation| solvable_equation_reverse(SECOND_OPERATORS, equation))
+        .map(|equation| equation.answer)
+        .sum()
+}
+
+pub fn parse_input(data: &str) -> aoc::Result<Vec<Equation>> {
+    aoc::lines(data).map(|line| Equation::from(&line)).collect()
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    fn part1(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        Ok(solvable_equations_sum(&parse_input(input)?).to_string())
+    }
+
+    fn part2(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        Ok(solvable_equations_with_concat_sum(&parse_input(input)?).to_string())
+    }
+}
+
+aoc::example_tests!(Day07);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::testing::{self, Rng};
+    use std::fs::read_to_string;
+
+    /// Half of the generated equations are solvable by construction.
+    fn random_equation(rng: &mut Rng) -> (u64, Vec<u64>) {
+        let nums = (0..rng.usize(1, 6))
+            .map(|_| rng.range(1, 20) as u64)
+            .collect::<Vec<_>>();
+        let answer = if rng.bool() {
+            nums[1..].iter().fold(nums[0], |accum, &n| {
+                rng.choose(SECOND_OPERATORS).apply(accum, n)
+            })
+        } else {
+            rng.range(1, 1000) as u64
+        };
+        (answer, nums)
+    }
+
+    fn solve_with(
+        solver: fn(&[Operator], &Equation) -> bool,
+        operators: &[Operator],
+        (answer, nums): &(u64, Vec<u64>),
+    ) -> Option<bool> {
+        // Shrinking may empty the list or produce zeros, which the real input never has.
+        if nums.is_empty() || nums.contains(&0) {
+            return None;
+        }
+        let equation = Equation {
+            answer: *answer,
+            nums: nums.clone(),
+        };
+        Some(solver(operators, &equation))
+    }
+
+    fn differential_solvable(operators: &[Operator]) {
+        testing::differential(
+            "solvable_equation",
+            2000,
+            random_equation,
+            |input| solve_with(solvable_equation, operators, input),
+            |input| solve_with(solvable_equation_reverse, operators, input),
+        );
+    }
+
+    #[test]
+    fn differential_solvable_equation() {
+        differential_solvable(FIRST_OPERATORS);
+        differential_solvable(SECOND_OPERATORS);
+    }
+
+    #[test]
+    fn answer_part1() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let equations = parse_input(&data).unwrap();
+        let solvable_equations_sum = solvable_equations_sum(&equations);
+        assert_eq!(solvable_equations_sum, 8401132154762);
+    }
+
+    #[test]
+    fn answer_part2() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let equations = parse_input(&data).unwrap();
+        let solvable_equations_with_concat_sum = solvable_equations_with_concat_sum(&equations);
+        assert_eq!(solvable_equations_with_concat_sum, 95297119227552);
+    }
+
+    #[test]
+    fn test_concat() {
+        assert_eq!(CONCAT.apply(1, 2), 12);
+        assert_eq!(CONCAT.apply(15, 6), 156);
+        assert_eq!(CONCAT.apply(48, 6), 486);
+        assert_eq!(CONCAT.apply(17, 8), 178);
+        assert_eq!(CONCAT.apply(1000, 1000), 10001000);
+        assert_eq!(CONCAT.apply(12, 345), 12345);
+    }
+
+    #[test]
+    fn test_count_digits() {
+        assert_eq!(count_digits(1), 1);
+        assert_eq!(count_digits(5), 1);
+        assert_eq!(count_digits(10), 2);
+        assert_eq!(count_digits(11), 2);
+    }
+}
@@ -0,0 +1,290 @@
+use aoc::{AocError, Solution};
+use std::{
+    collections::HashMap,
+    ops::{Add, Sub},
+};
+
+#[derive(Clone, Debug)]
+pub struct Grid<T>
+where
+    T: Default,
+{
+    x_size: usize,
+    y_size: usize,
+    grid: Vec<T>,
+}
+
+impl<T> Grid<T>
+where
+    T: Default + Clone + Copy,
+{
+    pub fn new(x_size: usize, y_size: usize) -> Self {
+        Self {
+            x_size,
+            y_size,
+            grid: vec![T::default(); x_size * y_size],
+        }
+    }
+
+    pub fn from_file(data: &str) -> aoc::Result<Grid<u8>> {
+        let lines = data.lines().collect::<Vec<_>>();
+        let Some(first) = lines.first() else {
+            return Err(AocError::parse(1, 1, "empty grid"));
+        };
+        let x_size = first.len();
+        let y_size = lines.len();
+        for line in aoc::lines(data) {
+            for (x, c) in line.text.bytes().enumerate() {
+                if !(c == b'.' || c.is_ascii_alphanumeric()) {
+                    return Err(AocError::parse(
+                        line.num,
+                        x + 1,
+                        format!("unexpected character {:?}", c as char),
+                    ));
+                }
+            }
+            if line.text.len() != x_size {
+                return Err(line.error(line.end(), format!("expected row of length {}", x_size)));
+            }
+        }
+        let grid = lines.iter().flat_map(|line| line.bytes()).collect();
+        Ok(Grid::<u8> {
+            x_size,
+            y_size,
+            grid,
+        })
+    }
+
+    pub fn valid_pos(&self, pos: &Position) -> bool {
+        pos.x >= 0 && (pos.x as usize) < self.x_size && pos.y >= 0 && (pos.y as usize) < self.y_size
+    }
+
+    pub fn at_mut(&mut self, pos: &Position) -> Option<&mut T> {
+        if self.valid_pos(pos) {
+            self.grid
+                .get_mut(pos.y as usize * self.x_size + pos.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, T)> + '_ {
+        (0..self.y_size).flat_map(move |y| {
+            (0..self.x_size).map(move |x| {
+                (
+                    Position::new(x.try_into().unwrap(), y.try_into().unwrap()),
+                    self.grid[x + y * self.x_size],
+                )
+            })
+        })
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Hash, Debug)]
+pub struct Position {
+    x: i32,
+    y: i32,
+}
+
+impl Position {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Add for Position {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+impl Sub for Position {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+
+impl<'a, 'b> Add<&'b Position> for &'a Position {
+    type Output = Position;
+
+    fn add(self, other: &'b Position) -> Position {
+        Position {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+impl<'a, 'b> Sub<&'b Position> for &'a Position {
+    type Output = Position;
+
+    fn sub(self, other: &'b Position) -> Position {
+        Position {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+
+impl<'a> Sub<&'a Position> for Position {
+    type Output = Self;
+
+    fn sub(self, other: &'a Position) -> Self {
+        Self {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+struct Delta {
+    x: i32,
+    y: i32,
+}
+
+pub fn parse_input(data: &str) -> aoc::Result<Grid<u8>> {
+    Grid::<u8>::from_file(data)
+}
+
+pub fn find_antenna_groups(grid: &Grid<u8>) -> Vec<Vec<Position>> {
+    let mut antenna_groups = HashMap::<u8, Vec<Position>>::new();
+    grid.iter()
+        .filter(|&(_, v)| v != b'.')
+        .for_each(|(pos, v)| antenna_groups.entry(v).or_default().push(pos));
+    antenna_groups.values().map(|v| v.clone()).collect()
+}
+
+pub fn iter_pairs<T>(v: &[T]) -> impl Iterator<Item = (&T, &T)> {
+    v.iter()
+        .enumerate()
+        .flat_map(|(i, e1)| v[i + 1..].iter().map(move |e2| (e1, e2)))
+}
+
+pub fn antinodes(x_size: usize, y_size: usize, antenna_groups: &[Vec<Position>]) -> Grid<bool> {
+    let mut antinodes = Grid::<bool>::new(x_size, y_size);
+    for antennas in antenna_groups {
+        for (a1, a2) in iter_pairs(antennas) {
+            let an1 = a1 + a1 - a2;
+            let an2 = a2 + a2 - a1;
+            if let Some(v) = antinodes.at_mut(&an1) {
+                *v = true;
+            }
+            if let Some(v) = antinodes.at_mut(&an2) {
+                *v = true;
+            }
+        }
+    }
+    antinodes
+}
+
+pub fn gcd(a: i32, b: i32) -> i32 {
+    (1..=std::cmp::min(a.abs(), b.abs()))
+        .rev()
+        .filter(|v| a % v == 0 && b % v == 0)
+        .next()
+        .unwrap_or_else(|| panic!("has gcd {} {}", a, b))
+}
+
+pub fn resonant_antinodes(
+    x_size: usize,
+    y_size: usize,
+    antenna_groups: &[Vec<Position>],
+) -> Grid<bool> {
+    let mut antinodes = Grid::<bool>::new(x_size, y_size);
+    for antennas in antenna_groups {
+        for (a1, a2) in iter_pairs(antennas) {
+            let mut delta = a1 - a2;
+            let gcd = gcd(delta.x, delta.y);
+            delta = Position {
+                x: delta.x / gcd,
+                y: delta.y / gcd,
+            };
+            let mut pos = *a1;
+            while antinodes.valid_pos(&pos) {
+                if let Some(v) = antinodes.at_mut(&pos) {
+                    *v = true;
+                }
+                pos = pos + delta;
+            }
+            delta = Position {
+                x: -delta.x,
+                y: -delta.y,
+            };
+            pos = *a2;
+            while antinodes.valid_pos(&pos) {
+                if let Some(v) = antinodes.at_mut(&pos) {
+                    *v = true;
+                }
+                pos = pos + delta;
+            }
+        }
+    }
+    antinodes
+}
+
+pub fn count_antinodes(grid: &Grid<u8>) -> usize {
+    let antenna_groups = find_antenna_groups(grid);
+    let antinodes_grid = antinodes(grid.x_size, grid.y_size, &antenna_groups);
+    antinodes_grid.iter().filter(|&(_, v)| v).count()
+}
+
+pub fn count_resonant_antinodes(grid: &Grid<u8>) -> usize {
+    let antenna_groups = find_antenna_groups(grid);
+    let antinodes_grid = resonant_antinodes(grid.x_size, grid.y_size, &antenna_groups);
+    antinodes_grid.iter().filter(|&(_, v)| v).count()
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    fn part1(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        Ok(count_antinodes(&parse_input(input)?).to_string())
+    }
+
+    fn part2(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        Ok(count_resonant_antinodes(&parse_input(input)?).to_string())
+    }
+}
+
+aoc::example_tests!(Day08);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::read_to_string;
+
+    #[test]
+    fn answer_part1() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let grid = parse_input(&data).unwrap();
+        let count_antinodes = count_antinodes(&grid);
+        assert_eq!(count_antinodes, 222);
+    }
+
+    #[test]
+    fn answer_part2() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let grid = parse_input(&data).unwrap();
+        let count_resonant_antinodes = count_resonant_antinodes(&grid);
+        assert_eq!(count_resonant_antinodes, 884);
+    }
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(2, 4), 2);
+        assert_eq!(gcd(4, 8), 4);
+        assert_eq!(gcd(14, 21), 7);
+        assert_eq!(gcd(-2, -3), 1);
+    }
+}
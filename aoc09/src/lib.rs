@@ -0,0 +1,308 @@
+use aoc::{AocError, Solution};
+
+#[derive(Clone, Copy, Debug)]
+pub struct File {
+    id: u64,
+    size: u8,
+}
+
+impl File {
+    pub fn block_map(&self) -> String {
+        let c = if self.id < 10 {
+            self.id.to_string()
+        } else {
+            "#".to_string()
+        };
+        c.repeat(self.size.into())
+    }
+}
+
+#[derive(Debug)]
+pub struct Disk {
+    files: Vec<File>,
+    free: Vec<u8>,
+}
+
+impl Disk {
+    pub fn from<I>(mut i: I) -> aoc::Result<Self>
+    where
+        I: Iterator<Item = u8>,
+    {
+        let mut files = Vec::<File>::new();
+        let mut free = Vec::<u8>::new();
+        let mut id = 0;
+
+        let missing_file = || AocError::invalid_state("disk map must end with a file");
+
+        files.push(File {
+            size: i.next().ok_or_else(missing_file)?,
+            id,
+        });
+        id += 1;
+        while let Some(free_size) = i.next() {
+            free.push(free_size);
+            files.push(File {
+                size: i.next().ok_or_else(missing_file)?,
+                id,
+            });
+            id += 1;
+        }
+        Ok(Self { files, free })
+    }
+
+    pub fn block_map(&self) -> String {
+        (0..self.files.len() - 1)
+            .map(|i| self.files[i].block_map() + &".".repeat(self.free[i] as usize))
+            .reduce(|a, b| a + &b)
+            .unwrap()
+            + &self.files.last().unwrap().block_map()
+    }
+
+    pub fn checksum(&self) -> u64 {
+        let mut block: u64 = 0;
+        let mut checksum: u64 = 0;
+        assert_eq!(self.files.len(), self.free.len() + 1);
+        for (i, file) in self.files.iter().enumerate() {
+            checksum += (block..block + file.size as u64)
+                .map(|b| b * file.id as u64)
+                .sum::<u64>();
+            block += file.size as u64;
+            if i < self.free.len() {
+                block += self.free[i] as u64;
+            }
+        }
+        checksum
+    }
+
+    pub fn defragment(&mut self) {
+        let mut free_index = 0;
+        let mut file_index = 1;
+        while free_index < self.free.len() {
+            let free = &mut self.free[free_index];
+            if *free == 0 {
+                free_index += 1;
+                file_index += 1;
+                continue;
+            }
+
+            let last_file = self.files.last_mut().unwrap();
+            let file_to_insert = File {
+                id: last_file.id,
+                size: std::cmp::min(*free, last_file.size),
+            };
+            *free -= file_to_insert.size;
+            if last_file.size > file_to_insert.size {
+                last_file.size -= file_to_insert.size;
+            } else {
+                self.files.pop();
+                self.free.pop();
+            }
+            self.files.insert(file_index, file_to_insert);
+            file_index += 1;
+        }
+        self.free = vec![0; self.files.len() - 1];
+    }
+
+    pub fn find_file(&self, id: u64) -> (usize, &File) {
+        self.files
+            .iter()
+            .enumerate()
+            .find(|(_, file)| file.id == id)
+            .expect("has file with id")
+    }
+
+    pub fn defragment_whole(&mut self) {
+        let max_id = self
+            .files
+            .iter()
+            .map(|file| file.id)
+            .max()
+            .expect("has max");
+        for id in (1..=max_id).rev() {
+            assert_eq!(self.files.len(), self.free.len() + 1);
+            let (file_index, file) = self.find_file(id);
+            let file = file.clone();
+            let Some(first_free) = self.free[..file_index]
+                .iter()
+                .enumerate()
+                .find(|&(_, free_size)| free_size >= &file.size)
+                .map(|(index, _)| index)
+            else {
+                continue;
+            };
+            let new_file_index = first_free + 1;
+
+            // Reduce the size of the free block, possibly to zero.
+            self.free[first_free] -= file.size;
+            // Insert a new zero length free entry before the new file.
+            // After this first_free is no longer accurate.
+            self.free.insert(first_free, 0);
+
+            if file_index == self.files.len() - 1 {
+                self.free.pop();
+            } else {
+                // Combine the free entries before and after the old file.
+                self.free[file_index] += self.free[file_index + 1] + file.size;
+                self.free.remove(file_index + 1);
+            }
+            // Remove the old file.  After this file_index is no longer accurate.
+            self.files.remove(file_index);
+            // Insert the new file.  After this new_file_index is no longer accurate.
+            self.files.insert(new_file_index, file);
+        }
+    }
+}
+
+pub fn parse_input(data: &str) -> aoc::Result<Disk> {
+    let data = data.trim_end();
+    if let Some(i) = data.bytes().position(|b| !b.is_ascii_digit()) {
+        let (line, col) = aoc::line_col(data, i);
+        return Err(AocError::parse(line, col, "expected a digit"));
+    }
+    Disk::from(data.bytes().map(|b| b - b'0'))
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    fn part1(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        let mut disk = parse_input(input)?;
+        disk.defragment();
+        Ok(disk.checksum().to_string())
+    }
+
+    fn part2(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        let mut disk = parse_input(input)?;
+        disk.defragment_whole();
+        Ok(disk.checksum().to_string())
+    }
+}
+
+aoc::example_tests!(Day09);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::testing::{self, Rng};
+    use std::fs::read_to_string;
+
+    fn random_disk_map(rng: &mut Rng) -> Vec<u8> {
+        let files = rng.usize(1, 20);
+        (0..files * 2 - 1)
+            .map(|i| if i % 2 == 0 { rng.range(1, 9) } else { rng.range(0, 9) } as u8)
+            .collect()
+    }
+
+    /// Shrinking may produce maps with empty files or without a trailing file, which the real
+    /// input never has.
+    fn valid_disk_map(disk_map: &[u8]) -> bool {
+        disk_map.len() % 2 == 1 && disk_map.iter().step_by(2).all(|&size| size > 0)
+    }
+
+    fn blocks(disk_map: &[u8]) -> Vec<Option<u64>> {
+        disk_map
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &size)| {
+                let id = (i % 2 == 0).then_some(i as u64 / 2);
+                std::iter::repeat_n(id, size as usize)
+            })
+            .collect()
+    }
+
+    fn blocks_checksum(blocks: &[Option<u64>]) -> u64 {
+        blocks
+            .iter()
+            .enumerate()
+            .map(|(i, id)| i as u64 * id.unwrap_or(0))
+            .sum()
+    }
+
+    /// Moves one block at a time from the end of the disk to the first free block.
+    fn reference_defragment(disk_map: &[u8]) -> u64 {
+        let mut blocks = blocks(disk_map);
+        loop {
+            let free = blocks.iter().position(|b| b.is_none());
+            let last = blocks.iter().rposition(|b| b.is_some());
+            match (free, last) {
+                (Some(free), Some(last)) if free < last => blocks.swap(free, last),
+                _ => break,
+            }
+        }
+        blocks_checksum(&blocks)
+    }
+
+    /// Moves each file, highest id first, to the leftmost run of free blocks it fits in.
+    fn reference_defragment_whole(disk_map: &[u8]) -> u64 {
+        let mut blocks = blocks(disk_map);
+        let max_id = disk_map.len() as u64 / 2;
+        for id in (0..=max_id).rev() {
+            let Some(start) = blocks.iter().position(|&b| b == Some(id)) else {
+                continue;
+            };
+            let len = blocks.iter().filter(|&&b| b == Some(id)).count();
+            let Some(to) = (0..start)
+                .find(|&i| i + len <= start && blocks[i..i + len].iter().all(|b| b.is_none()))
+            else {
+                continue;
+            };
+            for i in 0..len {
+                blocks.swap(to + i, start + i);
+            }
+        }
+        blocks_checksum(&blocks)
+    }
+
+    fn checksum_after(disk_map: &[u8], defragment: fn(&mut Disk)) -> u64 {
+        let mut disk = Disk::from(disk_map.iter().copied()).unwrap();
+        defragment(&mut disk);
+        disk.checksum()
+    }
+
+    #[test]
+    fn test_reference() {
+        let map = b"2333133121414131402".map(|b| b - b'0');
+        assert_eq!(reference_defragment(&map), 1928);
+        assert_eq!(reference_defragment_whole(&map), 2858);
+    }
+
+    #[test]
+    fn differential_defragment() {
+        testing::differential(
+            "defragment",
+            500,
+            random_disk_map,
+            |map| valid_disk_map(map).then(|| reference_defragment(map)),
+            |map| valid_disk_map(map).then(|| checksum_after(map, Disk::defragment)),
+        );
+    }
+
+    #[test]
+    fn differential_defragment_whole() {
+        testing::differential(
+            "defragment_whole",
+            500,
+            random_disk_map,
+            |map| valid_disk_map(map).then(|| reference_defragment_whole(map)),
+            |map| valid_disk_map(map).then(|| checksum_after(map, Disk::defragment_whole)),
+        );
+    }
+
+    #[test]
+    fn answer_part1() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let mut disk = parse_input(&data).unwrap();
+        disk.defragment();
+        let checksum = disk.checksum();
+        assert_eq!(checksum, 6432869891895);
+    }
+
+    #[test]
+    fn answer_part2() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let mut disk = parse_input(&data).unwrap();
+        disk.defragment_whole();
+        let checksum = disk.checksum();
+        assert_eq!(checksum, 6467290479134);
+    }
+}
@@ -0,0 +1,108 @@
+use aoc::Solution;
+use mygrid::{Grid, Position};
+use std::collections::HashSet;
+
+pub fn parse_input(data: &str) -> aoc::Result<Grid<u8>> {
+    Grid::parse(data, |b| b.is_ascii_digit().then(|| b - b'0'))
+}
+
+pub fn reachable_peaks(grid: &Grid<u8>, pos: &mygrid::Position) -> Option<HashSet<Position>> {
+    let Some(&n) = grid.at(pos) else { return None };
+
+    if n == 9 {
+        return Some(HashSet::<Position>::from([pos.clone()]));
+    }
+
+    mygrid::CARDINAL_DIRECTIONS
+        .iter()
+        .map(|dir| pos.step(dir))
+        .filter(|pos| grid.at(&pos).is_some_and(|&v| v == n + 1))
+        .map(|pos| reachable_peaks(grid, &pos))
+        .reduce(|a, b| {
+            if let Some(a) = a {
+                if let Some(b) = b {
+                    Some(a.union(&b).copied().collect())
+                } else {
+                    Some(a)
+                }
+            } else if let Some(b) = b {
+                Some(b)
+            } else {
+                None
+            }
+        })
+        .unwrap_or_default()
+}
+
+pub fn trailhead_score(grid: &Grid<u8>, pos: &mygrid::Position) -> usize {
+    reachable_peaks(grid, pos).unwrap_or_default().len()
+}
+
+pub fn sum_trailhead_scores(grid: &Grid<u8>) -> usize {
+    grid.iter_positions()
+        .filter(|pos| grid.at(pos).expect("valid") == &0)
+        .map(|pos| trailhead_score(grid, &pos))
+        .sum()
+}
+
+pub fn paths_to_peaks(grid: &Grid<u8>, pos: &mygrid::Position) -> usize {
+    let Some(&n) = grid.at(pos) else { return 0 };
+
+    if n == 9 {
+        return 1;
+    }
+
+    mygrid::CARDINAL_DIRECTIONS
+        .iter()
+        .map(|dir| pos.step(dir))
+        .filter(|pos| grid.at(&pos).is_some_and(|&v| v == n + 1))
+        .map(|pos| paths_to_peaks(grid, &pos))
+        .sum()
+}
+
+pub fn trailhead_rating(grid: &Grid<u8>, pos: &mygrid::Position) -> usize {
+    paths_to_peaks(grid, pos)
+}
+
+pub fn sum_trailhead_ratings(grid: &Grid<u8>) -> usize {
+    grid.iter_positions()
+        .filter(|pos| grid.at(pos).expect("valid") == &0)
+        .map(|pos| trailhead_rating(grid, &pos))
+        .sum()
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    fn part1(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        Ok(sum_trailhead_scores(&parse_input(input)?).to_string())
+    }
+
+    fn part2(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        Ok(sum_trailhead_ratings(&parse_input(input)?).to_string())
+    }
+}
+
+aoc::example_tests!(Day10);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::read_to_string;
+
+    #[test]
+    fn answer_part1() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let grid = parse_input(&data).unwrap();
+        let sum_trailhead_scores = sum_trailhead_scores(&grid);
+        assert_eq!(sum_trailhead_scores, 624);
+    }
+
+    #[test]
+    fn answer_part2() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let grid = parse_input(&data).unwrap();
+        let sum_trailhead_ratings = sum_trailhead_ratings(&grid);
+        assert_eq!(sum_trailhead_ratings, 1483);
+    }
+}
@@ -0,0 +1,308 @@
+//! Blinking a row of stones by counting how many stones show each number.
+//!
+//! The order of the stones never matters to the rules, so the row is kept as a count per
+//! value and each blink applies the rules once per distinct value.  Rules are checked in
+//! order and the first one that matches decides what a stone turns into; a stone no rule
+//! matches stays as it is.  They are written one per line or separated by `;`, as
+//! `<condition> -> <action>`:
+//!
+//! - conditions: a number, `even` or `odd` for the count of digits, or `*` for any stone
+//! - actions: numbers separated by `,` to replace the stone, `split` to cut its digits in
+//!   half, `*n` to multiply or `+n` to add
+
+use aoc::AocError;
+use std::{collections::HashMap, fmt, str::FromStr};
+
+pub fn num_digits(n: u64) -> u32 {
+    n.checked_ilog10().unwrap_or(0) + 1
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Condition {
+    Equals(u64),
+    EvenDigits,
+    OddDigits,
+    Any,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    Replace(Vec<u64>),
+    Split,
+    Multiply(u64),
+    Add(u64),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rule {
+    pub condition: Condition,
+    pub action: Action,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rules(pub Vec<Rule>);
+
+impl Condition {
+    fn matches(self, stone: u64) -> bool {
+        match self {
+            Condition::Equals(n) => stone == n,
+            Condition::EvenDigits => num_digits(stone).is_multiple_of(2),
+            Condition::OddDigits => !num_digits(stone).is_multiple_of(2),
+            Condition::Any => true,
+        }
+    }
+}
+
+impl Rules {
+    /// 0 becomes 1, an even number of digits splits in two, and anything else is multiplied
+    /// by 2024.
+    pub fn standard() -> Self {
+        "0 -> 1; even -> split; * -> *2024".parse().unwrap()
+    }
+
+    /// What `stone` turns into, passed to `out` one stone at a time.
+    pub fn apply(&self, stone: u64, mut out: impl FnMut(u64)) -> aoc::Result<()> {
+        let Some(rule) = self.0.iter().find(|rule| rule.condition.matches(stone)) else {
+            out(stone);
+            return Ok(());
+        };
+        let overflow = || AocError::invalid_state(format!("stone {} overflows", stone));
+        match &rule.action {
+            Action::Replace(stones) => stones.iter().for_each(|&n| out(n)),
+            Action::Split => {
+                let half = 10_u64.pow(num_digits(stone) / 2);
+                out(stone / half);
+                out(stone % half);
+            }
+            Action::Multiply(n) => out(stone.checked_mul(*n).ok_or_else(overflow)?),
+            Action::Add(n) => out(stone.checked_add(*n).ok_or_else(overflow)?),
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Rules {
+    type Err = AocError;
+
+    fn from_str(text: &str) -> aoc::Result<Self> {
+        let number = |line: &aoc::Line, text: &str| line.parse::<u64>(text.trim());
+        let mut rules = vec![];
+        for line in aoc::lines(text) {
+            for part in line.text.split(';').filter(|part| !part.trim().is_empty()) {
+                let Some((condition, action)) = part.split_once("->") else {
+                    return Err(line.error(part, "expected <condition> -> <action>"));
+                };
+                let condition = match condition.trim() {
+                    "even" => Condition::EvenDigits,
+                    "odd" => Condition::OddDigits,
+                    "*" => Condition::Any,
+                    _ => Condition::Equals(number(&line, condition)?),
+                };
+                let action = action.trim();
+                let action = if action == "split" {
+                    Action::Split
+                } else if let Some(n) = action.strip_prefix('*') {
+                    Action::Multiply(number(&line, n)?)
+                } else if let Some(n) = action.strip_prefix('+') {
+                    Action::Add(number(&line, n)?)
+                } else {
+                    Action::Replace(
+                        action
+                            .split(',')
+                            .map(|n| number(&line, n))
+                            .collect::<aoc::Result<_>>()?,
+                    )
+                };
+                rules.push(Rule { condition, action });
+            }
+        }
+        Ok(Self(rules))
+    }
+}
+
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, rule) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            match rule.condition {
+                Condition::Equals(n) => write!(f, "{}", n)?,
+                Condition::EvenDigits => write!(f, "even")?,
+                Condition::OddDigits => write!(f, "odd")?,
+                Condition::Any => write!(f, "*")?,
+            }
+            write!(f, " -> ")?;
+            match &rule.action {
+                Action::Replace(stones) => {
+                    let stones = stones.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+                    write!(f, "{}", stones.join(","))?
+                }
+                Action::Split => write!(f, "split")?,
+                Action::Multiply(n) => write!(f, "*{}", n)?,
+                Action::Add(n) => write!(f, "+{}", n)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What the row looks like after a blink.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub blink: usize,
+    /// How many different numbers are on the stones.
+    pub distinct: usize,
+    pub total: u64,
+    /// The total over the total before the blink, or 1 for an empty row.
+    pub growth: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct Stones {
+    counts: HashMap<u64, u64>,
+    blinks: usize,
+}
+
+impl Stones {
+    pub fn new(stones: &[u64]) -> Self {
+        let mut counts = HashMap::new();
+        for &stone in stones {
+            *counts.entry(stone).or_default() += 1;
+        }
+        Self { counts, blinks: 0 }
+    }
+
+    /// How many stones show each number.
+    pub fn counts(&self) -> &HashMap<u64, u64> {
+        &self.counts
+    }
+
+    /// The number of stones, which always fits as a blink that would leave too many fails
+    /// without changing the row.
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    /// Blinks once, or leaves the row as it was if a stone or the total overflows.
+    pub fn blink(&mut self, rules: &Rules) -> aoc::Result<Stats> {
+        let before = self.total();
+        let too_many = || {
+            AocError::invalid_state(format!(
+                "more than {} stones after {} blinks",
+                u64::MAX,
+                self.blinks + 1
+            ))
+        };
+        let mut counts = HashMap::with_capacity(self.counts.len());
+        let mut overflow = false;
+        for (&stone, &count) in &self.counts {
+            rules.apply(stone, |next| {
+                let total = counts.entry(next).or_insert(0u64);
+                match total.checked_add(count) {
+                    Some(sum) => *total = sum,
+                    None => overflow = true,
+                }
+            })?;
+        }
+        let total = counts
+            .values()
+            .try_fold(0u64, |sum, &count| sum.checked_add(count))
+            .filter(|_| !overflow)
+            .ok_or_else(too_many)?;
+        self.counts = counts;
+        self.blinks += 1;
+        Ok(Stats {
+            blink: self.blinks,
+            distinct: self.counts.len(),
+            total,
+            growth: if before == 0 {
+                1.0
+            } else {
+                total as f64 / before as f64
+            },
+        })
+    }
+
+    /// Blinks `times` times, returning what each blink left.
+    pub fn blink_times(&mut self, rules: &Rules, times: usize) -> aoc::Result<Vec<Stats>> {
+        (0..times).map(|_| self.blink(rules)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Blinks the row itself, keeping every stone in order.
+    fn blink_row(rules: &Rules, stones: &[u64], times: usize) -> Vec<u64> {
+        let mut stones = stones.to_vec();
+        for _ in 0..times {
+            let mut next = vec![];
+            for &stone in &stones {
+                rules.apply(stone, |n| next.push(n)).unwrap();
+            }
+            stones = next;
+        }
+        stones
+    }
+
+    #[test]
+    fn test_rules() {
+        let rules = Rules::standard();
+        assert_eq!(rules.to_string(), "0 -> 1; even -> split; * -> *2024");
+        assert_eq!(blink_row(&rules, &[125, 17], 1), [253000, 1, 7]);
+        assert_eq!(blink_row(&rules, &[1000], 1), [10, 0]);
+
+        let rules = "odd -> +1\n8 -> 1,2,3; 1234 -> split"
+            .parse::<Rules>()
+            .unwrap();
+        assert_eq!(rules.to_string(), "odd -> +1; 8 -> 1,2,3; 1234 -> split");
+        assert_eq!(blink_row(&rules, &[7, 1234, 44], 1), [8, 12, 34, 44]);
+
+        assert!("0 => 1".parse::<Rules>().is_err());
+        assert!("x -> 1".parse::<Rules>().is_err());
+        assert!("* -> *y".parse::<Rules>().is_err());
+        let mut out = vec![];
+        assert!(Rules::standard()
+            .apply(u64::MAX / 10, |n| out.push(n))
+            .is_err());
+    }
+
+    #[test]
+    fn test_stones() {
+        let mut stones = Stones::new(&[125, 17]);
+        let stats = stones.blink_times(&Rules::standard(), 6).unwrap();
+        assert_eq!(
+            stats.iter().map(|stats| stats.total).collect::<Vec<_>>(),
+            [3, 4, 5, 9, 13, 22]
+        );
+        assert_eq!(stats[5].distinct, 15);
+        assert_eq!(stats[1].growth, 4.0 / 3.0);
+        assert_eq!(stones.counts()[&2], 4);
+
+        // A variant where the row doubles on every blink.
+        let rules = "* -> 7,7".parse().unwrap();
+        let stats = Stones::new(&[1]).blink_times(&rules, 70);
+        assert!(stats.is_err());
+        let mut stones = Stones::new(&[1]);
+        stones.blink_times(&rules, 63).unwrap();
+        assert!(stones.blink(&rules).is_err());
+        assert_eq!(stones.total(), 1 << 63);
+        assert_eq!(stones.counts()[&7], 1 << 63);
+        assert!(stones.blink(&rules).is_err());
+        let stats = Stones::new(&[1]).blink_times(&rules, 63).unwrap();
+        assert_eq!(stats[62].total, 1 << 63);
+        assert!(stats.iter().all(|stats| stats.growth == 2.0));
+
+        let rules = "odd -> *3; even -> split".parse().unwrap();
+        let mut stones = Stones::new(&[5, 17, 2024]);
+        let stats = stones.blink_times(&rules, 12).unwrap();
+        let row = blink_row(&rules, &[5, 17, 2024], 12);
+        assert_eq!(stats[11].total, row.len() as u64);
+        assert_eq!(stones.total(), row.len() as u64);
+
+        let empty = Stones::new(&[]).blink(&rules).unwrap();
+        assert_eq!((empty.total, empty.growth), (0, 1.0));
+    }
+}
@@ -0,0 +1,120 @@
+pub mod engine;
+
+use aoc::Solution;
+pub use engine::num_digits;
+use engine::{Rules, Stones};
+
+pub fn num_stones_after_blinks(stones: &[u64], times: usize, rules: &Rules) -> aoc::Result<u64> {
+    let mut stones = Stones::new(stones);
+    stones.blink_times(rules, times)?;
+    Ok(stones.total())
+}
+
+pub fn parse_input(data: &str) -> aoc::Result<Vec<u64>> {
+    let mut stones = vec![];
+    for line in aoc::lines(data) {
+        stones.extend(line.parse_whitespace_list::<u64>()?);
+    }
+    Ok(stones)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    fn part1(&self, input: &str, params: &aoc::Params) -> aoc::Result<String> {
+        let rules = params.get("rules", Rules::standard())?;
+        let blinks = params.get("blinks", 25)?;
+        Ok(num_stones_after_blinks(&parse_input(input)?, blinks, &rules)?.to_string())
+    }
+
+    fn part2(&self, input: &str, params: &aoc::Params) -> aoc::Result<String> {
+        let rules = params.get("rules", Rules::standard())?;
+        let blinks = params.get("blinks", 75)?;
+        Ok(num_stones_after_blinks(&parse_input(input)?, blinks, &rules)?.to_string())
+    }
+}
+
+aoc::example_tests!(Day11);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::testing::{self, Rng};
+    use std::fs::read_to_string;
+
+    /// Blinks the whole row of stones, following the rules as written.
+    fn reference_num_stones_after_blinks(stones: &[u64], times: usize) -> u64 {
+        let mut stones = stones.to_vec();
+        for _ in 0..times {
+            stones = stones
+                .iter()
+                .flat_map(|&stone| {
+                    let digits = stone.to_string();
+                    if stone == 0 {
+                        vec![1]
+                    } else if digits.len() % 2 == 0 {
+                        let (left, right) = digits.split_at(digits.len() / 2);
+                        vec![left.parse().unwrap(), right.parse().unwrap()]
+                    } else {
+                        vec![stone * 2024]
+                    }
+                })
+                .collect();
+        }
+        stones.len() as u64
+    }
+
+    /// Half of the stones are next to a power of ten, where the number of digits changes.
+    fn random_stones(rng: &mut Rng) -> (Vec<u64>, usize) {
+        let stones = (0..rng.usize(1, 5))
+            .map(|_| {
+                if rng.bool() {
+                    10_u64.pow(rng.range(1, 6) as u32) - rng.range(0, 1) as u64
+                } else {
+                    rng.range(0, 1_000_000) as u64
+                }
+            })
+            .collect();
+        (stones, rng.usize(0, 20))
+    }
+
+    #[test]
+    fn test_num_digits() {
+        assert_eq!(num_digits(0), 1);
+        for d in 1..20 {
+            assert_eq!(num_digits(10_u64.pow(d - 1)), d);
+            assert_eq!(num_digits(10_u64.pow(d) - 1), d);
+        }
+        assert_eq!(num_digits(9_999_999), 7);
+        assert_eq!(num_digits(u64::MAX), 20);
+    }
+
+    #[test]
+    fn differential_num_stones_after_blinks() {
+        testing::differential(
+            "num_stones_after_blinks",
+            200,
+            random_stones,
+            |(stones, times)| reference_num_stones_after_blinks(stones, *times),
+            |(stones, times)| num_stones_after_blinks(stones, *times, &Rules::standard()).unwrap(),
+        );
+    }
+
+    #[test]
+    fn answer_part1() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let stones = parse_input(&data).unwrap();
+        let num_stones_after_25_blinks =
+            num_stones_after_blinks(&stones, 25, &Rules::standard()).unwrap();
+        assert_eq!(num_stones_after_25_blinks, 194557);
+    }
+
+    #[test]
+    fn answer_part2() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let stones = parse_input(&data).unwrap();
+        let num_stones_after_75_blinks =
+            num_stones_after_blinks(&stones, 75, &Rules::standard()).unwrap();
+        assert_eq!(num_stones_after_75_blinks, 231532558973909);
+    }
+}
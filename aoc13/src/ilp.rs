@@ -0,0 +1,519 @@
+//! Cheapest presses for any number of buttons moving in any number of dimensions.
+//!
+//! Integer column operations bring the buttons' steps to echelon form, which gives one press
+//! vector reaching the target and a basis for the ways of changing it without moving the claw.
+//! With no such way the answer is fixed, and with one it is the cheaper end of the range that
+//! keeps every count in bounds, as in [`crate::linear`].  With more, the search fixes the count
+//! of one button at a time and tries again.  The buttons left over can't cover the rest of the
+//! target for less than their lowest cost per unit along any axis, which leaves a range of
+//! counts worth trying that narrows as better presses turn up.
+
+use crate::linear::{div_ceil, steps_in_range};
+use aoc::AocError;
+
+// Press vectors `point + w * basis` for any integer weights `w`.
+struct Lattice {
+    point: Vec<i128>,
+    basis: Vec<Vec<i128>>,
+}
+
+fn overflow() -> AocError {
+    AocError::invalid_state("press counts do not fit in an i128")
+}
+
+// `acc + a * b`, failing on overflow.
+fn add_mul(acc: i128, a: i128, b: i128) -> aoc::Result<i128> {
+    a.checked_mul(b)
+        .and_then(|p| acc.checked_add(p))
+        .ok_or_else(overflow)
+}
+
+// `acc - a * b`, failing on overflow.
+fn sub_mul(acc: i128, a: i128, b: i128) -> aoc::Result<i128> {
+    a.checked_mul(b)
+        .and_then(|p| acc.checked_sub(p))
+        .ok_or_else(overflow)
+}
+
+// The integer solutions of `rows * x = rhs` over `n` unknowns.
+fn solutions(rows: &[Vec<i128>], rhs: &[i128], n: usize) -> aoc::Result<Option<Lattice>> {
+    // Each column of the rows, paired with the press vector it stands for.
+    let mut cols = (0..n)
+        .map(|j| {
+            let mut unit = vec![0; n];
+            unit[j] = 1;
+            (rows.iter().map(|row| row[j]).collect::<Vec<_>>(), unit)
+        })
+        .collect::<Vec<_>>();
+    let mut pivots = vec![];
+    for i in 0..rows.len() {
+        let rank = pivots.len();
+        // Euclid's algorithm across the columns clears row `i` after the pivot.
+        while let Some(p) = (rank..n)
+            .filter(|&j| cols[j].0[i] != 0)
+            .min_by_key(|&j| cols[j].0[i].abs())
+        {
+            cols.swap(rank, p);
+            let (pivot, rest) = cols[rank..].split_first_mut().unwrap();
+            for (col, vec) in rest.iter_mut() {
+                let q = col[i] / pivot.0[i];
+                for (a, &b) in col
+                    .iter_mut()
+                    .zip(&pivot.0)
+                    .chain(vec.iter_mut().zip(&pivot.1))
+                {
+                    *a = sub_mul(*a, q, b)?;
+                }
+            }
+            if rest.iter().all(|(col, _)| col[i] == 0) {
+                pivots.push(i);
+                break;
+            }
+        }
+    }
+
+    let mut weights = vec![];
+    for (k, &i) in pivots.iter().enumerate() {
+        let rest = (0..k).try_fold(rhs[i], |rest, j| sub_mul(rest, cols[j].0[i], weights[j]))?;
+        if rest % cols[k].0[i] != 0 {
+            return Ok(None);
+        }
+        weights.push(rest / cols[k].0[i]);
+    }
+    for (i, &target) in rhs.iter().enumerate() {
+        let reached =
+            (0..pivots.len()).try_fold(0, |sum, k| add_mul(sum, cols[k].0[i], weights[k]))?;
+        if reached != target {
+            return Ok(None);
+        }
+    }
+
+    let mut point = vec![0; n];
+    for (k, &w) in weights.iter().enumerate() {
+        for (x, &u) in point.iter_mut().zip(&cols[k].1) {
+            *x = add_mul(*x, w, u)?;
+        }
+    }
+    let basis = cols.split_off(pivots.len()).into_iter().map(|(_, vec)| vec);
+    Ok(Some(Lattice {
+        point,
+        basis: basis.collect(),
+    }))
+}
+
+struct Search<'a> {
+    /// How far each button moves the claw along each axis.
+    steps: Vec<Vec<i128>>,
+    costs: &'a [i128],
+    target: Vec<i128>,
+    /// The most presses each button can take.
+    upper: Vec<i128>,
+    /// The counts the search has fixed so far.
+    fixed: Vec<Option<i128>>,
+    best: Option<(i128, Vec<i128>)>,
+}
+
+impl Search<'_> {
+    fn cost(&self, presses: &[i128]) -> aoc::Result<i128> {
+        presses
+            .iter()
+            .zip(self.costs)
+            .try_fold(0, |sum, (&x, &c)| add_mul(sum, x, c))
+    }
+
+    // The counts of button `j` that could still beat the best presses so far, or `None` if
+    // none can.  Any count has to leave the rest of every axis to the unfixed buttons.
+    fn counts(&self, j: usize) -> Option<(i128, i128)> {
+        let (mut lo, mut hi) = (0, self.upper[j]);
+        let mut rest = self.target.clone();
+        let mut spent = Some(0i128);
+        for (i, x) in self.fixed.iter().enumerate() {
+            let Some(x) = *x else { continue };
+            rest.iter_mut()
+                .zip(&self.steps[i])
+                .for_each(|(r, s)| *r -= x * s);
+            spent = spent.and_then(|spent| spent.checked_add(x.checked_mul(self.costs[i])?));
+        }
+        let free = |i: usize| i != j && self.fixed[i].is_none();
+        for (d, &r) in rest.iter().enumerate() {
+            if r < 0 {
+                return None;
+            }
+            let s = self.steps[j][d];
+            // The unfixed button moving furthest along this axis for what it costs.
+            let cheapest = (0..self.steps.len())
+                .filter(|&i| free(i) && self.steps[i][d] > 0)
+                .map(|i| (self.costs[i], self.steps[i][d]))
+                // Costs and steps come from u64s, so the cross products fit in a u128.
+                .min_by(|&(c1, s1), &(c2, s2)| {
+                    (c1 as u128 * s2 as u128).cmp(&(c2 as u128 * s1 as u128))
+                });
+            let Some((c, step)) = cheapest else {
+                // Only button `j` is left to move along this axis.
+                if s == 0 {
+                    if r != 0 {
+                        return None;
+                    }
+                } else if r % s != 0 {
+                    return None;
+                } else {
+                    (lo, hi) = (lo.max(r / s), hi.min(r / s));
+                }
+                continue;
+            };
+            if s > 0 {
+                hi = hi.min(r.div_euclid(s));
+            }
+            // With `count` presses of `j` the rest costs at least `(r - count * s) * c / step`,
+            // and as costs are whole numbers a better total is at most `best - 1`.
+            let Some((best, _)) = &self.best else {
+                continue;
+            };
+            let bound = (|| {
+                let a = self.costs[j]
+                    .checked_mul(step)?
+                    .checked_sub(s.checked_mul(c)?)?;
+                let b = (best - 1)
+                    .checked_sub(spent?)?
+                    .checked_mul(step)?
+                    .checked_sub(r.checked_mul(c)?)?;
+                Some((a, b))
+            })();
+            // Only a bound that fits is used; without it the range is merely wider.
+            match bound {
+                Some((a, b)) if a > 0 => hi = hi.min(b.div_euclid(a)),
+                Some((a, b)) if a < 0 => lo = lo.max(div_ceil(-b, -a)),
+                Some((_, b)) if b < 0 => return None,
+                _ => {}
+            }
+        }
+        (lo <= hi).then_some((lo, hi))
+    }
+
+    fn consider(&mut self, presses: Vec<i128>) -> aoc::Result<()> {
+        if presses
+            .iter()
+            .zip(&self.upper)
+            .any(|(&x, &max)| x < 0 || x > max)
+        {
+            return Ok(());
+        }
+        let cost = self.cost(&presses)?;
+        if self.best.as_ref().is_none_or(|(best, _)| cost < *best) {
+            self.best = Some((cost, presses));
+        }
+        Ok(())
+    }
+
+    fn search(&mut self, rows: &mut Vec<Vec<i128>>, rhs: &mut Vec<i128>) -> aoc::Result<()> {
+        let n = self.costs.len();
+        let Some(Lattice { point, basis }) = solutions(rows, rhs, n)? else {
+            return Ok(());
+        };
+        match basis.as_slice() {
+            [] => self.consider(point),
+            [step] => {
+                let (mut lo, mut hi) = (i128::MIN, i128::MAX);
+                for j in 0..n {
+                    let Some((a, b)) = steps_in_range(point[j], step[j], self.upper[j]) else {
+                        return Ok(());
+                    };
+                    (lo, hi) = (lo.max(a), hi.min(b));
+                }
+                if lo > hi {
+                    return Ok(());
+                }
+                let k = if self.cost(step)? > 0 { lo } else { hi };
+                let presses = point
+                    .iter()
+                    .zip(step)
+                    .map(|(&x, &s)| add_mul(x, k, s))
+                    .collect::<aoc::Result<_>>()?;
+                self.consider(presses)
+            }
+            _ => {
+                // Fixing a button whose count varies leaves one fewer way to vary.
+                let j = (0..n)
+                    .filter(|&j| basis.iter().any(|step| step[j] != 0))
+                    .min_by_key(|&j| self.upper[j])
+                    .unwrap();
+                let mut row = vec![0; n];
+                row[j] = 1;
+                rows.push(row);
+                rhs.push(0);
+                // Trying both ends in turn finds cheap presses early whichever way the cost
+                // falls, and each one found can pull in the other end.
+                let (mut lo, mut hi) = (0, self.upper[j]);
+                let mut from_lo = true;
+                while let Some((a, b)) = self.counts(j) {
+                    (lo, hi) = (lo.max(a), hi.min(b));
+                    if lo > hi {
+                        break;
+                    }
+                    let count = if from_lo { lo } else { hi };
+                    *rhs.last_mut().unwrap() = count;
+                    self.fixed[j] = Some(count);
+                    let found = self.search(rows, rhs);
+                    self.fixed[j] = None;
+                    found?;
+                    if from_lo {
+                        lo += 1;
+                    } else {
+                        hi -= 1;
+                    }
+                    from_lo = !from_lo;
+                }
+                rows.pop();
+                rhs.pop();
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The cheapest press counts reaching `target`, where `steps[i]` is how far button `i` moves
+/// the claw along each axis and `costs[i]` what pressing it costs, with at most `max_presses`
+/// presses of each button if given.  `None` if the target can't be reached, and an error if
+/// the counts or costs along the way overflow an `i128`.
+pub fn min_presses(
+    steps: &[Vec<u64>],
+    costs: &[u64],
+    target: &[u64],
+    max_presses: Option<u64>,
+) -> aoc::Result<Option<Vec<u64>>> {
+    if steps.len() != costs.len() {
+        return Err(AocError::invalid_state(format!(
+            "{} buttons but {} costs",
+            steps.len(),
+            costs.len()
+        )));
+    }
+    if let Some(i) = steps.iter().position(|step| step.len() != target.len()) {
+        return Err(AocError::invalid_state(format!(
+            "button {} moves in {} dimensions but the target has {}",
+            i,
+            steps[i].len(),
+            target.len()
+        )));
+    }
+
+    // Steps are never negative, so no button can be pressed more often than it takes to pass
+    // the target.  A button that moves nothing is never worth pressing.
+    let max = max_presses.unwrap_or(u64::MAX) as i128;
+    let upper = steps
+        .iter()
+        .map(|step| {
+            step.iter()
+                .zip(target)
+                .filter(|&(&s, _)| s > 0)
+                .map(|(&s, &t)| (t / s) as i128)
+                .min()
+                .unwrap_or(0)
+                .min(max)
+        })
+        .collect();
+    let costs = costs.iter().map(|&c| c as i128).collect::<Vec<_>>();
+    let mut rows = (0..target.len())
+        .map(|d| steps.iter().map(|step| step[d] as i128).collect())
+        .collect();
+    let target = target.iter().map(|&t| t as i128).collect::<Vec<_>>();
+    let mut rhs = target.clone();
+
+    let mut search = Search {
+        steps: steps
+            .iter()
+            .map(|step| step.iter().map(|&s| s as i128).collect())
+            .collect(),
+        costs: &costs,
+        target,
+        upper,
+        fixed: vec![None; steps.len()],
+        best: None,
+    };
+    search.search(&mut rows, &mut rhs)?;
+    Ok(search
+        .best
+        .map(|(_, presses)| presses.into_iter().map(|x| x as u64).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{linear, parse_input};
+    use aoc::testing::Rng;
+    use std::fs::read_to_string;
+
+    // The cheapest presses found by trying every count up to `max`.
+    fn brute_force(steps: &[Vec<u64>], costs: &[u64], target: &[u64], max: u64) -> Option<u64> {
+        let mut best = None;
+        let mut presses = vec![0; steps.len()];
+        loop {
+            let reached = (0..target.len()).all(|d| {
+                steps
+                    .iter()
+                    .zip(&presses)
+                    .map(|(s, x)| s[d] * x)
+                    .sum::<u64>()
+                    == target[d]
+            });
+            if reached {
+                let cost = presses.iter().zip(costs).map(|(x, c)| x * c).sum::<u64>();
+                best = Some(best.map_or(cost, |best: u64| best.min(cost)));
+            }
+            let Some(i) = presses.iter().position(|&x| x < max) else {
+                return best;
+            };
+            presses[..i].fill(0);
+            presses[i] += 1;
+        }
+    }
+
+    #[test]
+    fn test_min_presses() {
+        let steps = [vec![94, 34], vec![22, 67]];
+        assert_eq!(
+            min_presses(&steps, &[3, 1], &[8400, 5400], None).unwrap(),
+            Some(vec![80, 40])
+        );
+        assert_eq!(
+            min_presses(&steps, &[3, 1], &[8400, 5400], Some(79)).unwrap(),
+            None
+        );
+
+        // Three buttons on a line, where the cheapest mix uses all of them.
+        let steps = [vec![5], vec![3], vec![1]];
+        assert_eq!(
+            min_presses(&steps, &[4, 2, 2], &[12], None).unwrap(),
+            Some(vec![0, 4, 0])
+        );
+        assert_eq!(
+            min_presses(&steps, &[4, 2, 2], &[12], Some(3)).unwrap(),
+            Some(vec![1, 2, 1])
+        );
+
+        // Three buttons on a line with a far target, which takes as long as trying every
+        // count of one of them without a bound on the rest.
+        let steps = [vec![1, 1], vec![2, 2], vec![3, 3]];
+        let target = 10000000000000;
+        let presses = min_presses(&steps, &[3, 2, 1], &[target, target], None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(presses[0] * 3 + presses[1] * 2 + presses[2], target / 3 + 3);
+        assert_eq!(presses[0] + presses[1] * 2 + presses[2] * 3, target);
+        assert_eq!(
+            min_presses(&steps, &[0, 5, 5], &[target, target], None).unwrap(),
+            Some(vec![target, 0, 0])
+        );
+        assert_eq!(
+            min_presses(&steps, &[5, 5, 0], &[target + 1, target + 1], None).unwrap(),
+            Some(vec![0, 1, target / 3])
+        );
+
+        // Three buttons in three dimensions.
+        let steps = [vec![1, 0, 2], vec![0, 1, 1], vec![1, 1, 0]];
+        assert_eq!(
+            min_presses(
+                &steps,
+                &[1, 1, 1],
+                &[10000000000003, 10000000000004, 7],
+                None
+            )
+            .unwrap(),
+            Some(vec![2, 3, 10000000000001])
+        );
+        assert_eq!(
+            min_presses(&steps, &[1, 1, 1], &[3, 4, 7], None).unwrap(),
+            Some(vec![2, 3, 1])
+        );
+
+        assert!(min_presses(&steps, &[1, 1], &[3, 4, 7], None).is_err());
+        assert!(min_presses(&steps, &[1, 1, 1], &[3, 4], None).is_err());
+        assert_eq!(min_presses(&[], &[], &[0, 0], None).unwrap(), Some(vec![]));
+        assert_eq!(min_presses(&[], &[], &[1, 0], None).unwrap(), None);
+
+        let max = u64::MAX;
+        let steps = [vec![max, 1], vec![1, max]];
+        assert!(min_presses(&steps, &[3, 1], &[max, max], None).is_err());
+        let steps = [vec![max, max], vec![1, 1]];
+        assert_eq!(
+            min_presses(&steps, &[3, 1], &[max, max], None).unwrap(),
+            Some(vec![1, 0])
+        );
+    }
+
+    #[test]
+    fn test_against_two_buttons() {
+        let data = read_to_string("src/main.txt").unwrap();
+        for machine in parse_input(&data).unwrap() {
+            let machine = machine.add_10_trillion().unwrap();
+            let (a, b) = (machine.a, machine.b);
+            let expected = machine.solve(None).map(|(a, b)| vec![a, b]);
+            let found = min_presses(
+                &[vec![a.x, a.y], vec![b.x, b.y]],
+                &[a.cost, b.cost],
+                &[machine.x, machine.y],
+                None,
+            );
+            assert_eq!(found.unwrap(), expected);
+        }
+
+        let mut rng = Rng::from_env();
+        for _ in 0..500 {
+            let a = (rng.usize(0, 6) as u64, rng.usize(0, 6) as u64);
+            let k = rng.usize(0, 3) as u64;
+            let b = (a.0 * k, a.1 * k);
+            let target = (rng.usize(0, 40) as u64, rng.usize(0, 40) as u64);
+            let costs = (rng.usize(0, 4) as u64, rng.usize(0, 4) as u64);
+            let cost = |p: &[u64]| p[0] * costs.0 + p[1] * costs.1;
+            let expected = linear::solve(a, b, target, costs, Some(20));
+            let found = min_presses(
+                &[vec![a.0, a.1], vec![b.0, b.1]],
+                &[costs.0, costs.1],
+                &[target.0, target.1],
+                Some(20),
+            )
+            .unwrap();
+            assert_eq!(
+                found.as_deref().map(cost),
+                expected.map(|(a, b)| cost(&[a, b]))
+            );
+        }
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let mut rng = Rng::from_env();
+        for _ in 0..300 {
+            let buttons = rng.usize(1, 4);
+            let dims = rng.usize(1, 3);
+            let steps = (0..buttons)
+                .map(|_| (0..dims).map(|_| rng.usize(0, 4) as u64).collect())
+                .collect::<Vec<Vec<u64>>>();
+            let costs = (0..buttons)
+                .map(|_| rng.usize(0, 4) as u64)
+                .collect::<Vec<_>>();
+            let target = (0..dims)
+                .map(|_| rng.usize(0, 12) as u64)
+                .collect::<Vec<_>>();
+            let found = min_presses(&steps, &costs, &target, Some(6)).unwrap();
+            if let Some(presses) = &found {
+                for d in 0..dims {
+                    let moved = steps
+                        .iter()
+                        .zip(presses)
+                        .map(|(s, x)| s[d] * x)
+                        .sum::<u64>();
+                    assert_eq!(moved, target[d]);
+                }
+            }
+            assert_eq!(
+                found.map(|presses| presses.iter().zip(&costs).map(|(x, c)| x * c).sum()),
+                brute_force(&steps, &costs, &target, 6),
+                "{:?} {:?} {:?}",
+                steps,
+                costs,
+                target
+            );
+        }
+    }
+}
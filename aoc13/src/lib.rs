@@ -0,0 +1,194 @@
+pub mod ilp;
+pub mod linear;
+
+use aoc::{AocError, Solution};
+use regex::Regex;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Button {
+    cost: u64,
+    x: u64,
+    y: u64,
+}
+
+impl Button {
+    const A_COST: u64 = 3;
+    const B_COST: u64 = 1;
+}
+
+#[derive(Debug)]
+pub struct Machine {
+    a: Button,
+    b: Button,
+    x: u64,
+    y: u64,
+}
+
+impl Machine {
+    pub fn from(data: &str) -> aoc::Result<Vec<Self>> {
+        let a_re = Regex::new(r"^Button A: X\+(?<x>[0-9]+), Y\+(?<y>[0-9]+)$").expect("compiles");
+        let b_re = Regex::new(r"^Button B: X\+(?<x>[0-9]+), Y\+(?<y>[0-9]+)$").expect("compiles");
+        let prize_re = Regex::new(r"^Prize: X=(?<x>[0-9]+), Y=(?<y>[0-9]+)$").expect("compiles");
+
+        let mut lines = aoc::lines(data).filter(|line| !line.text.is_empty());
+        let mut machines = vec![];
+        while let Some(a_line) = lines.next() {
+            let (ax, ay) = Self::parse_xy(&a_re, &a_line, "Button A: X+<n>, Y+<n>")?;
+            let b_line = Self::next_line(&mut lines, data)?;
+            let (bx, by) = Self::parse_xy(&b_re, &b_line, "Button B: X+<n>, Y+<n>")?;
+            let prize_line = Self::next_line(&mut lines, data)?;
+            let (x, y) = Self::parse_xy(&prize_re, &prize_line, "Prize: X=<n>, Y=<n>")?;
+            machines.push(Self {
+                a: Button {
+                    cost: Button::A_COST,
+                    x: ax,
+                    y: ay,
+                },
+                b: Button {
+                    cost: Button::B_COST,
+                    x: bx,
+                    y: by,
+                },
+                x,
+                y,
+            });
+        }
+        Ok(machines)
+    }
+
+    pub fn next_line<'a, I>(lines: &mut I, data: &str) -> aoc::Result<aoc::Line<'a>>
+    where
+        I: Iterator<Item = aoc::Line<'a>>,
+    {
+        lines.next().ok_or_else(|| {
+            let (line, col) = aoc::line_col(data, data.len());
+            AocError::parse(line, col, "unexpected end of input")
+        })
+    }
+
+    pub fn parse_xy(re: &Regex, line: &aoc::Line, expected: &str) -> aoc::Result<(u64, u64)> {
+        let caps = re
+            .captures(line.text)
+            .ok_or_else(|| line.error(line.text, format!("expected {:?}", expected)))?;
+        Ok((line.parse(&caps["x"])?, line.parse(&caps["y"])?))
+    }
+
+    pub fn add_10_trillion(&self) -> aoc::Result<Self> {
+        const INC: u64 = 10000000000000;
+        let (Some(x), Some(y)) = (self.x.checked_add(INC), self.y.checked_add(INC)) else {
+            return Err(AocError::invalid_state(format!(
+                "prize at X={}, Y={} moves past {}",
+                self.x,
+                self.y,
+                u64::MAX
+            )));
+        };
+        Ok(Self {
+            a: self.a,
+            b: self.b,
+            x,
+            y,
+        })
+    }
+
+    /// The cheapest press counts of A and B that win the prize, pressing each button at most
+    /// `max_presses` times if given.
+    pub fn solve(&self, max_presses: Option<u64>) -> Option<(u64, u64)> {
+        linear::solve(
+            (self.a.x, self.a.y),
+            (self.b.x, self.b.y),
+            (self.x, self.y),
+            (self.a.cost, self.b.cost),
+            max_presses,
+        )
+    }
+}
+
+pub fn parse_input(data: &str) -> aoc::Result<Vec<Machine>> {
+    Machine::from(data)
+}
+
+fn too_many_tokens() -> AocError {
+    AocError::invalid_state(format!("more than {} tokens", u64::MAX))
+}
+
+/// The fewest tokens that win the prize, or `None` if it can't be won.
+pub fn min_tokens(machine: &Machine, max_presses: Option<u64>) -> aoc::Result<Option<u64>> {
+    let Some((a, b)) = machine.solve(max_presses) else {
+        return Ok(None);
+    };
+    let tokens = a
+        .checked_mul(machine.a.cost)
+        .zip(b.checked_mul(machine.b.cost))
+        .and_then(|(a, b)| a.checked_add(b))
+        .ok_or_else(too_many_tokens)?;
+    Ok(Some(tokens))
+}
+
+pub fn min_total_tokens(machines: &[Machine], max_presses: Option<u64>) -> aoc::Result<u64> {
+    machines.iter().try_fold(0u64, |total, machine| {
+        let tokens = min_tokens(machine, max_presses)?.unwrap_or_default();
+        total.checked_add(tokens).ok_or_else(too_many_tokens)
+    })
+}
+
+pub fn add_10_trillion(machines: &[Machine]) -> aoc::Result<Vec<Machine>> {
+    machines
+        .iter()
+        .map(|machine| machine.add_10_trillion())
+        .collect()
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    fn part1(&self, input: &str, params: &aoc::Params) -> aoc::Result<String> {
+        let max_presses = params.get("max_presses", 100)?;
+        Ok(min_total_tokens(&parse_input(input)?, Some(max_presses))?.to_string())
+    }
+
+    fn part2(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        Ok(min_total_tokens(&add_10_trillion(&parse_input(input)?)?, None)?.to_string())
+    }
+}
+
+aoc::example_tests!(Day13);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::read_to_string;
+
+    #[test]
+    fn answer_part1() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let machines = parse_input(&data).unwrap();
+        assert_eq!(min_total_tokens(&machines, Some(100)).unwrap(), 29877);
+        assert_eq!(min_total_tokens(&machines, None).unwrap(), 29877);
+    }
+
+    #[test]
+    fn test_huge_values() {
+        let data = "Button A: X+1, Y+0\nButton B: X+0, Y+1\n\
+                    Prize: X=18446744073709551615, Y=1\n";
+        let machines = parse_input(data).unwrap();
+        assert!(add_10_trillion(&machines).is_err());
+        assert!(Day13.part2(data, &aoc::Params::new()).is_err());
+        // Winnable, but pressing A that often costs three times too many tokens.
+        assert!(min_total_tokens(&machines, None).is_err());
+
+        let data = "Button A: X+3, Y+0\nButton B: X+0, Y+1\n\
+                    Prize: X=18446744073709551615, Y=0\n";
+        let machines = parse_input(data).unwrap();
+        assert_eq!(min_total_tokens(&machines, None).unwrap(), u64::MAX);
+        let twice = parse_input(&data.repeat(2)).unwrap();
+        assert!(min_total_tokens(&twice, None).is_err());
+    }
+
+    #[test]
+    fn answer_part2() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let machines = add_10_trillion(&parse_input(&data).unwrap()).unwrap();
+        assert_eq!(min_total_tokens(&machines, None).unwrap(), 99423413811305);
+    }
+}
@@ -0,0 +1,199 @@
+//! Exact integer solutions of two-button machines.
+//!
+//! When the buttons point in different directions there is at most one way to reach the prize,
+//! found with Cramer's rule.  When they are parallel every solution lies on a line, so the
+//! system shrinks to `a * p + b * q = t` along it.  Extended Euclid gives one solution of that,
+//! the rest step by the same amount, and as the cost changes linearly along the step the
+//! cheapest one sits at an end of the range that keeps the counts in bounds.
+
+use std::cmp::Ordering;
+
+/// The greatest common divisor of `a` and `b`, and `x` and `y` with `a * x + b * y` equal to it.
+pub fn ext_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        return (a, 1, 0);
+    }
+    let (g, x, y) = ext_gcd(b, a.rem_euclid(b));
+    (g, y, x - a.div_euclid(b) * y)
+}
+
+pub(crate) fn div_ceil(n: i128, d: i128) -> i128 {
+    -(-n).div_euclid(d)
+}
+
+/// The cheapest press counts `(a, b)` reaching `target`, where pressing A adds `a_step` and
+/// pressing B adds `b_step`, with at most `max_presses` presses of each button.  `None` if there
+/// are none, or if working them out overflows `i128`, which takes values close to `u64::MAX`.
+pub fn solve(
+    a_step: (u64, u64),
+    b_step: (u64, u64),
+    target: (u64, u64),
+    costs: (u64, u64),
+    max_presses: Option<u64>,
+) -> Option<(u64, u64)> {
+    let (ax, ay) = (a_step.0 as i128, a_step.1 as i128);
+    let (bx, by) = (b_step.0 as i128, b_step.1 as i128);
+    let (x, y) = (target.0 as i128, target.1 as i128);
+    let max = max_presses.unwrap_or(u64::MAX) as i128;
+
+    let det = cross(ax, by, ay, bx)?;
+    let (a, b) = if det != 0 {
+        let a = cross(x, by, y, bx)?;
+        let b = cross(ax, y, ay, x)?;
+        if a % det != 0 || b % det != 0 {
+            return None;
+        }
+        (a / det, b / det)
+    } else {
+        // Work along whichever axis the buttons move on; a target off their line fails the
+        // check at the end.
+        let (p, q, t) = if ax != 0 || bx != 0 {
+            (ax, bx, x)
+        } else {
+            (ay, by, y)
+        };
+        parallel(p, q, t, costs, max)?
+    };
+
+    let fits = |n: i128| (0..=max).contains(&n);
+    let reaches = |p: i128, q: i128, t: i128| {
+        a.checked_mul(p)
+            .zip(b.checked_mul(q))
+            .and_then(|(a, b)| a.checked_add(b))
+            == Some(t)
+    };
+    if !fits(a) || !fits(b) || !reaches(ax, bx, x) || !reaches(ay, by, y) {
+        return None;
+    }
+    Some((a as u64, b as u64))
+}
+
+// `a * b - c * d`, or `None` if it overflows.
+fn cross(a: i128, b: i128, c: i128, d: i128) -> Option<i128> {
+    a.checked_mul(b)?.checked_sub(c.checked_mul(d)?)
+}
+
+// The range of k for which `n0 + k * dn` lies in `0..=max`.
+pub(crate) fn steps_in_range(n0: i128, dn: i128, max: i128) -> Option<(i128, i128)> {
+    match dn.cmp(&0) {
+        Ordering::Equal => (0..=max).contains(&n0).then_some((i128::MIN, i128::MAX)),
+        Ordering::Greater => Some((div_ceil(-n0, dn), (max - n0).div_euclid(dn))),
+        Ordering::Less => Some((div_ceil(n0 - max, -dn), n0.div_euclid(-dn))),
+    }
+}
+
+// The cheapest `a * p + b * q = t` with both counts in `0..=max`.
+fn parallel(p: i128, q: i128, t: i128, costs: (u64, u64), max: i128) -> Option<(i128, i128)> {
+    if p == 0 && q == 0 {
+        return (t == 0).then_some((0, 0));
+    }
+    let (g, x0, y0) = ext_gcd(p, q);
+    if t % g != 0 {
+        return None;
+    }
+    // a = a0 + k * da and b = b0 - k * db for any k.
+    let (a0, b0) = (x0.checked_mul(t / g)?, y0.checked_mul(t / g)?);
+    let (da, db) = (q / g, p / g);
+
+    let (a_lo, a_hi) = steps_in_range(a0, da, max)?;
+    let (b_lo, b_hi) = steps_in_range(b0, -db, max)?;
+    let (lo, hi) = (a_lo.max(b_lo), a_hi.min(b_hi));
+    if lo > hi {
+        return None;
+    }
+
+    let slope = cross(costs.0 as i128, da, costs.1 as i128, db)?;
+    let k = if slope > 0 { lo } else { hi };
+    Some((
+        a0.checked_add(k.checked_mul(da)?)?,
+        b0.checked_sub(k.checked_mul(db)?)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ext_gcd() {
+        for (a, b) in [(240, 46), (46, 240), (7, 0), (0, 7), (12, 18), (1, 1)] {
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(a * x + b * y, g);
+            assert!(a % g == 0 && b % g == 0);
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        let solve = |a, b, target, max| solve(a, b, target, (3, 1), max);
+        assert_eq!(
+            solve((94, 34), (22, 67), (8400, 5400), Some(100)),
+            Some((80, 40))
+        );
+        assert_eq!(solve((26, 66), (67, 21), (12748, 12176), None), None);
+        assert_eq!(
+            solve((26, 66), (67, 21), (10000000012748, 10000000012176), None),
+            Some((118679050709, 103199174542))
+        );
+        assert_eq!(solve((94, 34), (22, 67), (8400, 5400), Some(79)), None);
+
+        // Parallel buttons where B is cheaper per step, so A only fills in the remainder.
+        assert_eq!(solve((2, 2), (3, 3), (10, 10), None), Some((2, 2)));
+        assert_eq!(solve((1, 1), (3, 3), (10, 10), None), Some((1, 3)));
+        assert_eq!(solve((1, 1), (3, 3), (10, 10), Some(2)), None);
+        assert_eq!(solve((1, 1), (3, 3), (10, 10), Some(4)), Some((1, 3)));
+        // Here A is cheaper per step, until it may only be pressed four times.
+        assert_eq!(solve((12, 12), (3, 3), (60, 60), None), Some((5, 0)));
+        assert_eq!(solve((12, 12), (3, 3), (60, 60), Some(4)), Some((4, 4)));
+        // Off the line, unreachable by divisibility, and vertical buttons.
+        assert_eq!(solve((1, 1), (3, 3), (10, 11), None), None);
+        assert_eq!(solve((2, 2), (4, 4), (5, 5), None), None);
+        assert_eq!(solve((0, 1), (0, 2), (0, 5), None), Some((1, 2)));
+        assert_eq!(solve((0, 0), (0, 0), (0, 0), None), Some((0, 0)));
+        assert_eq!(solve((0, 0), (0, 0), (1, 0), None), None);
+        // A button that does nothing is never pressed.
+        assert_eq!(solve((0, 0), (1, 2), (3, 6), None), Some((0, 3)));
+
+        // Products of values this large no longer fit in an i128.
+        let max = u64::MAX;
+        assert_eq!(solve((max, 1), (1, max), (max, max), None), None);
+        assert_eq!(solve((max, max), (1, 1), (max, max), None), Some((1, 0)));
+        assert_eq!(solve((max - 1, 1), (1, max), (max, max), None), None);
+        assert_eq!(solve((1, 0), (0, 1), (max, max), None), Some((max, max)));
+    }
+
+    #[test]
+    fn test_against_search() {
+        let brute = |a: (u64, u64), b: (u64, u64), target: (u64, u64), costs: (u64, u64)| {
+            (0..=20)
+                .flat_map(|i| (0..=20).map(move |j| (i, j)))
+                .filter(|&(i, j)| i * a.0 + j * b.0 == target.0 && i * a.1 + j * b.1 == target.1)
+                .min_by_key(|&(i, j)| (i * costs.0 + j * costs.1, i))
+        };
+        let mut rng = aoc::testing::Rng::from_env();
+        for _ in 0..2000 {
+            let a = (rng.usize(0, 6) as u64, rng.usize(0, 6) as u64);
+            let k = rng.usize(0, 3) as u64;
+            let b = if rng.bool() {
+                (a.0 * k, a.1 * k)
+            } else {
+                (rng.usize(0, 6) as u64, rng.usize(0, 6) as u64)
+            };
+            let target = (rng.usize(0, 40) as u64, rng.usize(0, 40) as u64);
+            let costs = (rng.usize(0, 4) as u64, rng.usize(0, 4) as u64);
+            let found = solve(a, b, target, costs, Some(20));
+            let expected = brute(a, b, target, costs);
+            let cost = |p: Option<(u64, u64)>| p.map(|(i, j)| i * costs.0 + j * costs.1);
+            assert_eq!(
+                cost(found),
+                cost(expected),
+                "{:?} {:?} {:?} {:?}: {:?}",
+                a,
+                b,
+                target,
+                costs,
+                found
+            );
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use aoc::Solution;
+use mygrid::{Grid, Position};
+use regex::Regex;
+use std::cmp::Ordering;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Robot {
+    p: Position,
+    v: Position,
+}
+
+impl Robot {
+    pub fn from(line: &aoc::Line) -> aoc::Result<Self> {
+        let re = Regex::new("p=(?<px>-?[0-9]+),(?<py>-?[0-9]+) v=(?<vx>-?[0-9]+),(?<vy>-?[0-9]+)")
+            .expect("compiles");
+        let caps = re
+            .captures(line.text)
+            .ok_or_else(|| line.error(line.text, "expected \"p=<x>,<y> v=<x>,<y>\""))?;
+        Ok(Self {
+            p: Position {
+                x: line.parse(&caps["px"])?,
+                y: line.parse(&caps["py"])?,
+            },
+            v: Position {
+                x: line.parse(&caps["vx"])?,
+                y: line.parse(&caps["vy"])?,
+            },
+        })
+    }
+
+    pub fn traverse(&self, seconds: i32, size: &Position) -> Self {
+        let ret = Self {
+            p: (((self.p + self.v * seconds) % size) + size) % size,
+            v: self.v,
+        };
+        ret
+    }
+}
+
+pub fn traverse_robots<'a>(
+    robots: &'a [Robot],
+    seconds: i32,
+    size: &'a Position,
+) -> impl Iterator<Item = Robot> + 'a {
+    robots
+        .iter()
+        .map(move |robot| robot.traverse(seconds, size))
+}
+
+pub fn product_of_robots_in_quadrants_after_traverse(
+    robots: &[Robot],
+    seconds: i32,
+    size: &Position,
+) -> usize {
+    let middle = size / 2;
+    traverse_robots(robots, seconds, size)
+        .map(
+            |robot| match (robot.p.x.cmp(&middle.x), robot.p.y.cmp(&middle.y)) {
+                (Ordering::Less, Ordering::Less) => [1, 0, 0, 0],
+                (Ordering::Greater, Ordering::Less) => [0, 1, 0, 0],
+                (Ordering::Less, Ordering::Greater) => [0, 0, 1, 0],
+                (Ordering::Greater, Ordering::Greater) => [0, 0, 0, 1],
+                (_, _) => [0, 0, 0, 0],
+            },
+        )
+        .fold([0, 0, 0, 0], |a, b| {
+            [a[0] + b[0], a[1] + b[1], a[2] + b[2], a[3] + b[3]]
+        })
+        .iter()
+        .product()
+}
+
+pub fn pretty_print_robots(robots: &[Robot], size: &Position) -> String {
+    let mut grid = Grid::<char>::new(size.x as usize, size.y as usize);
+
+    grid.fill('.');
+    robots
+        .iter()
+        .for_each(|robot| *grid.at_mut(&robot.p).unwrap() = '*');
+
+    grid.to_string()
+}
+
+pub fn no_duplicate_robots(robots: &[Robot], size: &Position) -> bool {
+    let mut grid: Grid<bool> = Grid::<bool>::new(size.x as usize, size.y as usize);
+    robots
+        .iter()
+        .for_each(|robot| *grid.at_mut(&robot.p).unwrap() = true);
+    return grid.iter().filter(|&v| v == &true).count() == robots.len();
+}
+
+pub fn parse_input(data: &str, size: &Position) -> aoc::Result<Vec<Robot>> {
+    aoc::lines(data)
+        .map(|line| {
+            let robot = Robot::from(&line)?;
+            if !(0..size.x).contains(&robot.p.x) || !(0..size.y).contains(&robot.p.y) {
+                return Err(line.error(line.text, "robot is outside the room"));
+            }
+            Ok(robot)
+        })
+        .collect()
+}
+
+pub fn first_second_without_overlap(robots: &[Robot], size: &Position) -> aoc::Result<i32> {
+    // vertical alignment: 9, 168, repeat = 9 + 159* x
+    // horizontal alignment: 65, 110, 211, repeat 45
+    // Every robot is back where it started after width * height seconds.
+    (1..=size.x * size.y)
+        .find(|&seconds| {
+            let robots = traverse_robots(robots, seconds, size).collect::<Vec<_>>();
+            no_duplicate_robots(&robots, size)
+        })
+        .ok_or_else(|| aoc::AocError::no_solution("robots always overlap"))
+}
+
+pub fn room_size(params: &aoc::Params) -> aoc::Result<Position> {
+    Ok(Position::new(
+        params.get("width", 101)?,
+        params.get("height", 103)?,
+    ))
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    fn part1(&self, input: &str, params: &aoc::Params) -> aoc::Result<String> {
+        let size = room_size(params)?;
+        let robots = parse_input(input, &size)?;
+        Ok(product_of_robots_in_quadrants_after_traverse(&robots, 100, &size).to_string())
+    }
+
+    fn part2(&self, input: &str, params: &aoc::Params) -> aoc::Result<String> {
+        let size = room_size(params)?;
+        let robots = parse_input(input, &size)?;
+        Ok(first_second_without_overlap(&robots, &size)?.to_string())
+    }
+}
+
+aoc::example_tests!(Day14);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::read_to_string;
+
+    #[test]
+    fn test_part1_1() {
+        let robot = Robot::from(&aoc::Line::new("p=2,4 v=2,-3", 1)).unwrap();
+        let size = Position::new(11, 7);
+        assert_eq!(robot.traverse(1, &size).p, Position::new(4, 1));
+        assert_eq!(robot.traverse(2, &size).p, Position::new(6, 5));
+    }
+
+    #[test]
+    fn answer_part1() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let size = Position::new(101, 103);
+        let robots = parse_input(&data, &size).unwrap();
+        assert_eq!(
+            product_of_robots_in_quadrants_after_traverse(&robots, 100, &size),
+            236628054
+        );
+    }
+
+    #[test]
+    fn test_no_duplicate_robots() {
+        let robot1 = Robot::from(&aoc::Line::new("p=2,4 v=2,-3", 1)).unwrap();
+        let robot2 = Robot::from(&aoc::Line::new("p=2,5 v=2,-5", 1)).unwrap();
+        let robot3 = Robot::from(&aoc::Line::new("p=2,4 v=2,-4", 1)).unwrap();
+        let size = Position::new(11, 7);
+        assert_eq!(no_duplicate_robots(&[robot1], &size), true);
+        assert_eq!(no_duplicate_robots(&[robot1, robot2], &size), true);
+        assert_eq!(no_duplicate_robots(&[robot1, robot3], &size), false);
+        assert_eq!(no_duplicate_robots(&[robot1, robot2, robot3], &size), false);
+    }
+}
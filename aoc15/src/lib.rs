@@ -0,0 +1,225 @@
+use aoc::{AocError, Solution};
+use mygrid::{Direction, Grid, Position};
+use std::collections::HashSet;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cell {
+    Empty,
+    Box,
+    BoxLeft,
+    BoxRight,
+    Wall,
+}
+
+impl ToString for Cell {
+    fn to_string(&self) -> String {
+        match self {
+            &Cell::Empty => ".".to_string(),
+            &Cell::Box => "O".to_string(),
+            &Cell::BoxLeft => "[".to_string(),
+            &Cell::BoxRight => "]".to_string(),
+            &Cell::Wall => "#".to_string(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct State {
+    grid: Grid<Cell>,
+    robot: Position,
+}
+
+impl State {
+    pub fn from(data: &str) -> aoc::Result<Self> {
+        let grid = Grid::parse(data, |c| match c {
+            b'#' => Some(Cell::Wall),
+            b'O' => Some(Cell::Box),
+            b'@' => Some(Cell::Empty),
+            b'.' => Some(Cell::Empty),
+            _ => None,
+        })?;
+
+        let robot = Grid::<u8>::parse(data, Some)?
+            .find(&b'@')
+            .ok_or_else(|| AocError::invalid_state("no robot '@' in warehouse"))?;
+
+        let walled = grid.iter_positions().all(|pos| {
+            let edge = pos.x == 0
+                || pos.y == 0
+                || pos.x as usize == grid.x_size - 1
+                || pos.y as usize == grid.y_size - 1;
+            !edge || grid[&pos] == Cell::Wall
+        });
+        if !walled {
+            return Err(AocError::invalid_state(
+                "warehouse is not surrounded by walls",
+            ));
+        }
+
+        Ok(Self { grid, robot })
+    }
+
+    pub fn double_from(from: &Self) -> Self {
+        let doubler = from
+            .grid
+            .iter()
+            .map(|cell| match cell {
+                &Cell::Box => vec![Cell::BoxLeft, Cell::BoxRight],
+                &Cell::Empty => vec![Cell::Empty, Cell::Empty],
+                &Cell::Wall => vec![Cell::Wall, Cell::Wall],
+                _ => panic!(),
+            })
+            .flatten();
+        let grid = Grid::<Cell>::from_iter(from.grid.x_size * 2, from.grid.y_size, doubler);
+        let robot = Position::new(from.robot.x * 2, from.robot.y);
+
+        Self { grid, robot }
+    }
+
+    pub fn recurse_move_boxes(&self, pos: &Position, dir: &Direction) -> (Vec<Position>, bool) {
+        match self.grid[&pos] {
+            Cell::Wall => (vec![], true),
+            Cell::Empty => (vec![], false),
+            box_cell => {
+                let next_box = pos.step(dir);
+                let (mut boxes, mut hits_wall) = self.recurse_move_boxes(&next_box, dir);
+                if (box_cell == Cell::BoxLeft || box_cell == Cell::BoxRight)
+                    && (dir == &mygrid::UP || dir == &mygrid::DOWN)
+                {
+                    let pair_box = pos.step(if box_cell == Cell::BoxLeft {
+                        &mygrid::RIGHT
+                    } else {
+                        &mygrid::LEFT
+                    });
+                    let pair_next_box = pair_box.step(dir);
+                    let (pair_boxes, other_hits_wall) =
+                        self.recurse_move_boxes(&pair_next_box, dir);
+                    boxes.extend(pair_boxes);
+                    boxes.insert(0, pair_box.clone());
+                    hits_wall |= other_hits_wall;
+                }
+                boxes.insert(0, pos.clone());
+                (boxes, hits_wall)
+            }
+        }
+    }
+
+    pub fn move_robot(&mut self, dir: &Direction) {
+        assert_eq!(self.grid[&self.robot], Cell::Empty);
+        let next_robot_pos = self.robot.step(dir);
+        let (boxes, hits_wall) = self.recurse_move_boxes(&next_robot_pos, dir);
+        if hits_wall {
+            return;
+        }
+
+        let mut already_moved_boxes = HashSet::<Position>::new();
+        for box_pos in boxes.iter().rev() {
+            if already_moved_boxes.contains(box_pos) {
+                continue;
+            }
+            let moved_box_pos = box_pos.step(dir);
+            self.grid[&moved_box_pos] = self.grid[&box_pos];
+            self.grid[&box_pos] = Cell::Empty;
+            already_moved_boxes.insert(box_pos.clone());
+        }
+
+        assert_eq!(self.grid[&next_robot_pos], Cell::Empty);
+        self.robot = next_robot_pos;
+    }
+
+    pub fn move_robot_multiple(&mut self, dirs: &[Direction]) {
+        for dir in dirs {
+            self.move_robot(dir);
+        }
+    }
+
+    pub fn iter_boxes<'a>(&'a self) -> impl Iterator<Item = Position> + 'a {
+        self.grid
+            .iter_positions()
+            .filter(|pos| self.grid[pos] == Cell::Box || self.grid[pos] == Cell::BoxLeft)
+    }
+
+    pub fn pretty_print_grid(&self) -> String {
+        self.grid.to_string()
+    }
+
+    /// The grid with the robot drawn as '@', as in the puzzle's pictures.
+    pub fn pretty_print_with_robot(&self) -> String {
+        let mut grid = self.grid.to_string();
+        let robot = self.robot.y as usize * (self.grid.x_size + 1) + self.robot.x as usize;
+        grid.replace_range(robot..robot + 1, "@");
+        grid
+    }
+}
+
+pub fn parse_input(data: &str) -> aoc::Result<(State, Vec<Direction>)> {
+    let mut lines = aoc::lines(data);
+    let grid_lines = lines
+        .by_ref()
+        .take_while(|line| !line.text.is_empty())
+        .map(|line| line.text)
+        .collect::<Vec<_>>();
+
+    let state = State::from(&grid_lines.join("\n"))?;
+
+    let mut movements = vec![];
+    for line in lines {
+        for (i, c) in line.text.bytes().enumerate() {
+            let dir = Direction::from(c).ok_or_else(|| {
+                AocError::parse(line.num, i + 1, format!("unexpected move {:?}", c as char))
+            })?;
+            movements.push(dir);
+        }
+    }
+
+    Ok((state, movements))
+}
+
+pub fn sum_of_box_gps_coordinates(state: &State) -> u64 {
+    state
+        .iter_boxes()
+        .map(|pos| pos.y as u64 * 100 + pos.x as u64)
+        .sum()
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    fn part1(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        let (mut state, movements) = parse_input(input)?;
+        state.move_robot_multiple(&movements);
+        Ok(sum_of_box_gps_coordinates(&state).to_string())
+    }
+
+    fn part2(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        let (state, movements) = parse_input(input)?;
+        let mut state = State::double_from(&state);
+        state.move_robot_multiple(&movements);
+        Ok(sum_of_box_gps_coordinates(&state).to_string())
+    }
+}
+
+aoc::example_tests!(Day15);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::read_to_string;
+
+    #[test]
+    fn answer_part1() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let (mut state, movements) = parse_input(&data).unwrap();
+        state.move_robot_multiple(&movements);
+        assert_eq!(sum_of_box_gps_coordinates(&state), 1526673);
+    }
+
+    #[test]
+    fn answer_part2() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let (mut state, movements) = parse_input(&data).unwrap();
+        state = State::double_from(&state);
+        state.move_robot_multiple(&movements);
+        assert_eq!(sum_of_box_gps_coordinates(&state), 1535509);
+    }
+}
@@ -0,0 +1,161 @@
+use aoc::{AocError, Solution};
+use mygrid::{Direction, Grid, Position};
+use pathfinding::directed::astar;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cell {
+    Empty,
+    Wall,
+}
+
+impl ToString for Cell {
+    fn to_string(&self) -> String {
+        match self {
+            &Cell::Empty => ".".to_string(),
+            &Cell::Wall => "#".to_string(),
+        }
+    }
+}
+
+pub struct Maze {
+    grid: Grid<Cell>,
+    start: Position,
+    end: Position,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PosAndDir {
+    pos: Position,
+    dir: Direction,
+}
+
+impl Maze {
+    pub fn from(data: &str) -> aoc::Result<Self> {
+        let grid = Grid::<Cell>::parse(data, |b| match b {
+            b'#' => Some(Cell::Wall),
+            b'.' => Some(Cell::Empty),
+            b'S' => Some(Cell::Empty),
+            b'E' => Some(Cell::Empty),
+            _ => None,
+        })?;
+
+        let grid_bytes = Grid::<u8>::parse(data, Some)?;
+        let start = grid_bytes
+            .find(&b'S')
+            .ok_or_else(|| AocError::invalid_state("no start 'S' in maze"))?;
+        let end = grid_bytes
+            .find(&b'E')
+            .ok_or_else(|| AocError::invalid_state("no end 'E' in maze"))?;
+
+        Ok(Self { grid, start, end })
+    }
+
+    pub fn successors(&self, pos_and_dir: &PosAndDir) -> Vec<(PosAndDir, usize)> {
+        assert_eq!(self.grid[&pos_and_dir.pos], Cell::Empty);
+        let s = mygrid::CARDINAL_DIRECTIONS
+            .iter()
+            .filter(|&dir| dir != &pos_and_dir.dir.opposite())
+            .filter(|&dir| {
+                self.grid
+                    .at(&pos_and_dir.pos.step(dir))
+                    .is_some_and(|v| v != &Cell::Wall)
+            })
+            .map(|dir| {
+                (
+                    PosAndDir {
+                        pos: pos_and_dir.pos.step(&dir),
+                        dir: dir.clone(),
+                    },
+                    if dir == &pos_and_dir.dir { 1 } else { 1001 },
+                )
+            })
+            .collect();
+        s
+    }
+
+    pub fn heuristic(&self, pos_and_dir: &PosAndDir) -> usize {
+        let c = match pos_and_dir.dir {
+            mygrid::LEFT => 2000,
+            mygrid::RIGHT => 0,
+            mygrid::UP => 0,
+            mygrid::DOWN => 2000,
+            _ => panic!(),
+        } + (self.end.x - pos_and_dir.pos.x).abs() as usize
+            + (self.end.y - pos_and_dir.pos.y).abs() as usize;
+        c
+    }
+
+    pub fn success(&self, pos_and_dir: &PosAndDir) -> bool {
+        pos_and_dir.pos == self.end
+    }
+
+    pub fn best_paths(&self) -> aoc::Result<(Vec<Vec<PosAndDir>>, usize)> {
+        let start: PosAndDir = PosAndDir {
+            pos: self.start,
+            dir: mygrid::RIGHT,
+        };
+        let Some((solutions, cost)) = astar::astar_bag(
+            &start,
+            |n| self.successors(n),
+            |n| self.heuristic(n),
+            |n| self.success(n),
+        ) else {
+            return Err(AocError::no_solution("no path from start to end"));
+        };
+
+        Ok((solutions.collect(), cost))
+    }
+
+    pub fn lowest_score(&self) -> aoc::Result<usize> {
+        let (_, cost) = self.best_paths()?;
+        Ok(cost)
+    }
+
+    pub fn best_seats(&self) -> aoc::Result<usize> {
+        let (paths, _) = self.best_paths()?;
+        let mut paths_grid = Grid::<bool>::new(self.grid.x_size, self.grid.y_size);
+        for pos_and_dir in paths.iter().flatten() {
+            paths_grid[&pos_and_dir.pos] = true;
+        }
+
+        Ok(paths_grid.iter().filter(|&v| v == &true).count())
+    }
+}
+
+pub fn parse_input(data: &str) -> aoc::Result<Maze> {
+    Maze::from(data)
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    fn part1(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        Ok(parse_input(input)?.lowest_score()?.to_string())
+    }
+
+    fn part2(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        Ok(parse_input(input)?.best_seats()?.to_string())
+    }
+}
+
+aoc::example_tests!(Day16);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::read_to_string;
+
+    #[test]
+    fn answer_part1() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let maze: Maze = parse_input(&data).unwrap();
+        assert_eq!(maze.lowest_score().unwrap(), 127520);
+    }
+
+    #[test]
+    fn answer_part2() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let maze: Maze = parse_input(&data).unwrap();
+        assert_eq!(maze.best_seats().unwrap(), 565);
+    }
+}
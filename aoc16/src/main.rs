@@ -1,179 +1,12 @@
-use mygrid::{Direction, Grid, Position};
-use pathfinding::directed::astar;
-use std::fs::read_to_string;
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum Cell {
-    Empty,
-    Wall,
-}
-
-impl ToString for Cell {
-    fn to_string(&self) -> String {
-        match self {
-            &Cell::Empty => ".".to_string(),
-            &Cell::Wall => "#".to_string(),
-        }
-    }
-}
-
-struct Maze {
-    grid: Grid<Cell>,
-    start: Position,
-    end: Position,
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-struct PosAndDir {
-    pos: Position,
-    dir: Direction,
-}
-
-impl Maze {
-    fn from(data: &str) -> Self {
-        let grid_bytes = Grid::<u8>::from_bytes(&data);
-
-        let start = grid_bytes.find(&b'S').unwrap();
-        let end = grid_bytes.find(&b'E').unwrap();
-
-        let grid = Grid::<Cell>::from_iter(
-            grid_bytes.x_size,
-            grid_bytes.y_size,
-            grid_bytes.iter().map(|&b| match b {
-                b'#' => Cell::Wall,
-                b'.' => Cell::Empty,
-                b'S' => Cell::Empty,
-                b'E' => Cell::Empty,
-                _ => panic!(),
-            }),
-        );
-
-        Self { grid, start, end }
-    }
-
-    fn successors(&self, pos_and_dir: &PosAndDir) -> Vec<(PosAndDir, usize)> {
-        assert_eq!(self.grid[&pos_and_dir.pos], Cell::Empty);
-        let s = mygrid::CARDINAL_DIRECTIONS
-            .iter()
-            .filter(|&dir| dir != &pos_and_dir.dir.opposite())
-            .filter(|&dir| {
-                self.grid
-                    .at(&pos_and_dir.pos.step(dir))
-                    .is_some_and(|v| v != &Cell::Wall)
-            })
-            .map(|dir| {
-                (
-                    PosAndDir {
-                        pos: pos_and_dir.pos.step(&dir),
-                        dir: dir.clone(),
-                    },
-                    if dir == &pos_and_dir.dir { 1 } else { 1001 },
-                )
-            })
-            .collect();
-        s
-    }
-
-    fn heuristic(&self, pos_and_dir: &PosAndDir) -> usize {
-        let c = match pos_and_dir.dir {
-            mygrid::LEFT => 2000,
-            mygrid::RIGHT => 0,
-            mygrid::UP => 0,
-            mygrid::DOWN => 2000,
-            _ => panic!(),
-        } + (self.end.x - pos_and_dir.pos.x).abs() as usize
-            + (self.end.y - pos_and_dir.pos.y).abs() as usize;
-        c
-    }
-
-    fn success(&self, pos_and_dir: &PosAndDir) -> bool {
-        pos_and_dir.pos == self.end
-    }
-
-    fn best_paths(&self) -> (Vec<Vec<PosAndDir>>, usize) {
-        let start: PosAndDir = PosAndDir {
-            pos: self.start,
-            dir: mygrid::RIGHT,
-        };
-        let Some((solutions, cost)) = astar::astar_bag(
-            &start,
-            |n| self.successors(n),
-            |n| self.heuristic(n),
-            |n| self.success(n),
-        ) else {
-            panic!()
-        };
-
-        (solutions.collect(), cost)
-    }
-
-    fn lowest_score(&self) -> usize {
-        let (_, cost) = self.best_paths();
-        cost
-    }
-
-    fn best_seats(&self) -> usize {
-        let (paths, _) = self.best_paths();
-        let mut paths_grid = Grid::<bool>::new(self.grid.x_size, self.grid.y_size);
-        for pos_and_dir in paths.iter().flatten() {
-            paths_grid[&pos_and_dir.pos] = true;
-        }
-
-        paths_grid.iter().filter(|&v| v == &true).count()
-    }
-}
-
-fn parse_input(data: &str) -> Maze {
-    Maze::from(data)
-}
-
-fn main() {
-    let args = std::env::args().collect::<Vec<_>>();
-    let input_file = if args.len() >= 2 {
-        std::path::PathBuf::from(&args[1])
-    } else {
-        let exe = std::env::current_exe().unwrap();
-        exe.parent()
-            .unwrap()
-            .join("../..")
-            .join(exe.file_name().unwrap())
-            .join("src/main.txt")
-    };
-    let data = read_to_string(&input_file).unwrap();
-    let maze: Maze = parse_input(&data);
-    println!("lowest score: {}", maze.lowest_score());
-    println!("best seats: {}", maze.best_seats());
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part1() {
-        let data = read_to_string("src/test.txt").unwrap();
-        let maze: Maze = parse_input(&data);
-        assert_eq!(maze.lowest_score(), 7036);
-    }
-
-    #[test]
-    fn answer_part1() {
-        let data = read_to_string("src/main.txt").unwrap();
-        let maze: Maze = parse_input(&data);
-        assert_eq!(maze.lowest_score(), 127520);
-    }
-
-    #[test]
-    fn test_part2() {
-        let data = read_to_string("src/test.txt").unwrap();
-        let maze: Maze = parse_input(&data);
-        assert_eq!(maze.best_seats(), 45);
-    }
-
-    #[test]
-    fn answer_part2() {
-        let data = read_to_string("src/main.txt").unwrap();
-        let maze: Maze = parse_input(&data);
-        assert_eq!(maze.best_seats(), 565);
-    }
+use aoc::Solution;
+use aoc16::Day16;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    aoc::run(|data| {
+        let params = aoc::Params::new();
+        println!("lowest score: {}", Day16.part1(data, &params)?);
+        println!("best seats: {}", Day16.part2(data, &params)?);
+        Ok(())
+    })
 }
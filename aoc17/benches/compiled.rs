@@ -0,0 +1,47 @@
+//! Compares the interpreter with compiled programs over many values of A, like a brute-force
+//! search would: `cargo bench -p aoc17`.
+
+use aoc17::{compiled::Compiled, parse_input, Computer};
+use std::{fs::read_to_string, hint::black_box, time::Instant};
+
+const RUNS: u64 = 1_000_000;
+
+// Runs `f` for A from 0 to RUNS and prints the time per run.  Returns the sum of the results
+// so every variant can be checked against the others.
+fn bench(name: &str, mut f: impl FnMut(u64) -> usize) -> usize {
+    let start = Instant::now();
+    let total = (0..RUNS).map(|a| f(black_box(a))).sum();
+    let elapsed = start.elapsed();
+    println!(
+        "{:<12} {:>8.1} ns/run  {:>8.3} s",
+        name,
+        elapsed.as_nanos() as f64 / RUNS as f64,
+        elapsed.as_secs_f64()
+    );
+    total
+}
+
+fn main() {
+    let data = read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/src/main.txt")).unwrap();
+    let (_, program) = parse_input(&data).unwrap();
+    let compiled = Compiled::new(&program).unwrap();
+
+    let interpreted = bench("interpreter", |a| {
+        Computer::new(a, 0, 0).run(&program).unwrap().len()
+    });
+    let mut out = vec![];
+    let buffered = bench("buffer", |a| {
+        compiled.run_into([a, 0, 0], &mut out).unwrap();
+        out.len()
+    });
+    let streamed = bench("iterator", |a| compiled.outputs([a, 0, 0]).count());
+    assert_eq!(interpreted, buffered);
+    assert_eq!(interpreted, streamed);
+
+    // Searching for the quine stops at the first wrong output.
+    let interpreted = bench("interp match", |a| {
+        (Computer::new(a, 0, 0).run(&program).unwrap() == program) as usize
+    });
+    let matched = bench("match", |a| compiled.matches([a, 0, 0], &program) as usize);
+    assert_eq!(interpreted, matched);
+}
@@ -0,0 +1,181 @@
+//! Disassembler and assembler for the 3-bit computer.
+//!
+//! Each instruction is written as its mnemonic and what it does with its operand, e.g.
+//! `adv A>>3` (A = A >> 3), `bxl B^5`, `bst A%8`, `jnz 0`, `out B%8`.  Combo operands appear
+//! as `0`..`3` or a register name.  `bxc` ignores its operand, which is kept in parentheses,
+//! `bxc (4)`, so programs round-trip exactly.  The assembler accepts the disassembler's output,
+//! including the `ip:` prefixes, and ignores `;` comments.
+
+use aoc::AocError;
+use itertools::Itertools;
+
+pub const MNEMONICS: [&str; 8] = ["adv", "bxl", "bst", "jnz", "bxc", "out", "bdv", "cdv"];
+
+fn combo(operand: u8) -> aoc::Result<String> {
+    match operand {
+        0..=3 => Ok(operand.to_string()),
+        4 => Ok("A".to_owned()),
+        5 => Ok("B".to_owned()),
+        6 => Ok("C".to_owned()),
+        _ => Err(AocError::invalid_state("reserved combo operand 7")),
+    }
+}
+
+/// Decodes one instruction, e.g. `(0, 3)` to `adv A>>3`.
+pub fn disassemble_instruction(opcode: u8, operand: u8) -> aoc::Result<String> {
+    Ok(match opcode {
+        0 | 6 | 7 => format!("{} A>>{}", MNEMONICS[opcode as usize], combo(operand)?),
+        1 => format!("bxl B^{}", operand),
+        2 | 5 => format!("{} {}%8", MNEMONICS[opcode as usize], combo(operand)?),
+        3 => format!("jnz {}", operand),
+        4 => format!("bxc ({})", operand),
+        _ => {
+            return Err(AocError::invalid_state(format!(
+                "invalid opcode {}",
+                opcode
+            )))
+        }
+    })
+}
+
+/// One `ip: instruction` line per instruction.
+pub fn disassemble(program: &[u8]) -> aoc::Result<String> {
+    if program.len() % 2 == 1 {
+        return Err(AocError::invalid_state(format!(
+            "opcode {} at {} has no operand",
+            program[program.len() - 1],
+            program.len() - 1
+        )));
+    }
+    let mut listing = String::new();
+    for (ip, pair) in program.chunks(2).enumerate() {
+        let instruction = disassemble_instruction(pair[0], pair[1])
+            .map_err(|err| AocError::invalid_state(format!("at {}: {}", ip * 2, err)))?;
+        listing += &format!("{:3}: {}\n", ip * 2, instruction);
+    }
+    Ok(listing)
+}
+
+fn parse_combo(line: &aoc::Line, operand: &str) -> aoc::Result<u8> {
+    match operand {
+        "A" => Ok(4),
+        "B" => Ok(5),
+        "C" => Ok(6),
+        _ => {
+            let n = line.parse::<u8>(operand)?;
+            if n > 3 {
+                return Err(line.error(operand, "expected 0 to 3 or a register"));
+            }
+            Ok(n)
+        }
+    }
+}
+
+fn parse_literal(line: &aoc::Line, operand: &str) -> aoc::Result<u8> {
+    let n = line.parse::<u8>(operand)?;
+    if n >= 8 {
+        return Err(line.error(operand, "expected a 3-bit number"));
+    }
+    Ok(n)
+}
+
+/// Assembles a listing back into opcodes and operands.
+pub fn assemble(listing: &str) -> aoc::Result<Vec<u8>> {
+    let mut program = vec![];
+    for line in aoc::lines(listing) {
+        let text = line.text.split(';').next().unwrap();
+        let text = match text.split_once(':') {
+            Some((ip, rest)) if ip.trim().parse::<usize>().is_ok() => rest,
+            _ => text,
+        };
+        let Some((mnemonic, operand)) = text.trim().split_once(' ') else {
+            if text.trim().is_empty() {
+                continue;
+            }
+            return Err(line.error(text.trim(), "expected \"<mnemonic> <operand>\""));
+        };
+        let operand = operand.trim();
+        let Some(opcode) = MNEMONICS.iter().position(|&m| m == mnemonic) else {
+            return Err(line.error(mnemonic, format!("unknown mnemonic {:?}", mnemonic)));
+        };
+        let expect = |prefix: &str, suffix: &str| {
+            operand
+                .strip_prefix(prefix)
+                .and_then(|rest| rest.strip_suffix(suffix))
+                .ok_or_else(|| {
+                    line.error(operand, format!("expected {}<operand>{}", prefix, suffix))
+                })
+        };
+        let operand = match opcode {
+            0 | 6 | 7 => parse_combo(&line, expect("A>>", "")?)?,
+            1 => parse_literal(&line, expect("B^", "")?)?,
+            2 | 5 => parse_combo(&line, expect("", "%8")?)?,
+            3 => parse_literal(&line, operand)?,
+            _ => parse_literal(&line, expect("(", ")")?)?,
+        };
+        program.extend([opcode as u8, operand]);
+    }
+    Ok(program)
+}
+
+/// The program in the puzzle input format, `Program: 0,1,5,4,3,0`.
+pub fn format_program(program: &[u8]) -> String {
+    format!("Program: {}", program.iter().join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+    use aoc::testing::{self, Rng};
+    use std::fs::read_to_string;
+
+    #[test]
+    fn test_disassemble() {
+        let data = read_to_string("../examples/17/test.txt").unwrap();
+        let (_, program) = parse_input(&data).unwrap();
+        assert_eq!(
+            disassemble(&program).unwrap(),
+            "  0: adv A>>1\n  2: out A%8\n  4: jnz 0\n"
+        );
+        assert_eq!(
+            disassemble_instruction(5, 5).unwrap(),
+            "out B%8",
+            "combo operands are decoded"
+        );
+        assert!(disassemble(&[0, 7]).is_err());
+        assert!(disassemble(&[0, 1, 3]).is_err());
+    }
+
+    #[test]
+    fn test_assemble() {
+        let listing = "adv A>>3 ; A = A / 8\nbxl B^7\n\n  4: bxc (0)\nout B%8\njnz 0\n";
+        let program = assemble(listing).unwrap();
+        assert_eq!(format_program(&program), "Program: 0,3,1,7,4,0,5,5,3,0");
+
+        let err = assemble("adv 3\n").unwrap_err().to_string();
+        assert!(err.contains("expected A>><operand>"), "{}", err);
+        let err = assemble("bst 7%8\n").unwrap_err().to_string();
+        assert!(err.contains("expected 0 to 3 or a register"), "{}", err);
+        let err = assemble("nop 0\n").unwrap_err().to_string();
+        assert!(err.contains("unknown mnemonic"), "{}", err);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let (_, program) = parse_input(&data).unwrap();
+        let listing = disassemble(&program).unwrap();
+        assert_eq!(assemble(&listing).unwrap(), program);
+        let text = format_program(&program);
+        assert!(data.contains(&text));
+
+        let mut rng = Rng::from_env();
+        for _ in 0..100 {
+            let len = rng.usize(0, 12);
+            let program = testing::opcode_program(&mut rng, len);
+            let listing = disassemble(&program).unwrap();
+            assert_eq!(assemble(&listing).unwrap(), program, "{}", listing);
+        }
+    }
+}
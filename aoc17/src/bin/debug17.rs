@@ -0,0 +1,50 @@
+//! Interactive debugger for a day 17 program: `debug17 [input]`, then `help` for the commands.
+//! An empty line repeats the previous command.
+
+use aoc17::{debug::Debugger, parse_input};
+use std::{
+    io::{self, BufRead, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+fn main() -> ExitCode {
+    let path = std::env::args().nth(1).map_or_else(
+        || PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/main.txt"),
+        PathBuf::from,
+    );
+    let (computer, program) = match aoc::read_input(&path).and_then(|data| parse_input(&data)) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("debug17: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut debugger = Debugger::new(computer, program);
+    println!("{}", debugger.execute("list").unwrap());
+    let mut last = String::from("step");
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        print!("(day17) ");
+        io::stdout().flush().unwrap();
+        let Some(Ok(line)) = lines.next() else {
+            println!();
+            break;
+        };
+        let command = match line.trim() {
+            "" => last.clone(),
+            command => command.to_owned(),
+        };
+        if command == "quit" || command == "q" {
+            break;
+        }
+        match debugger.execute(&command) {
+            Ok(text) if text.is_empty() => {}
+            Ok(text) => println!("{}", text),
+            Err(err) => println!("error: {}", err),
+        }
+        last = command;
+    }
+    ExitCode::SUCCESS
+}
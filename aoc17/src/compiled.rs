@@ -0,0 +1,317 @@
+//! Programs decoded once for fast repeated runs.
+//!
+//! Decoding resolves every instruction reachable from the start into an [`Instruction`], so
+//! running it skips the function-pointer dispatch and operand decoding of the interpreter.
+//! Most programs are one loop, a body without jumps followed by `jnz 0`.  These run pass by
+//! pass over the body without tracking the instruction pointer.  Outputs go into a buffer
+//! supplied by the caller, or come one at a time from [`Outputs`] so a search can stop at the
+//! first wrong value.
+
+use crate::{shr, DidNotHalt, MAX_STEPS};
+use aoc::AocError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operand {
+    Literal(u64),
+    /// Register A, B or C by index.
+    Register(usize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Instruction {
+    Adv(Operand),
+    Bxl(u64),
+    Bst(Operand),
+    Jnz(usize),
+    Bxc,
+    Out(Operand),
+    Bdv(Operand),
+    Cdv(Operand),
+}
+
+impl Instruction {
+    fn decode(opcode: u8, operand: u8) -> aoc::Result<Instruction> {
+        let combo = || match operand {
+            0..=3 => Ok(Operand::Literal(operand as u64)),
+            4..=6 => Ok(Operand::Register(operand as usize - 4)),
+            _ => Err(AocError::invalid_state("reserved combo operand 7")),
+        };
+        Ok(match opcode {
+            0 => Instruction::Adv(combo()?),
+            1 => Instruction::Bxl(operand as u64),
+            2 => Instruction::Bst(combo()?),
+            3 => Instruction::Jnz(operand as usize),
+            4 => Instruction::Bxc,
+            5 => Instruction::Out(combo()?),
+            6 => Instruction::Bdv(combo()?),
+            7 => Instruction::Cdv(combo()?),
+            _ => {
+                return Err(AocError::invalid_state(format!(
+                    "invalid opcode {}",
+                    opcode
+                )))
+            }
+        })
+    }
+
+    // Executes anything but `jnz`, returning the output if there is one.
+    #[inline(always)]
+    fn execute(self, regs: &mut [u64; 3]) -> Option<u8> {
+        let combo = |regs: &[u64; 3], operand| match operand {
+            Operand::Literal(n) => n,
+            Operand::Register(r) => regs[r],
+        };
+        match self {
+            Instruction::Adv(operand) => regs[0] = shr(regs[0], combo(regs, operand)),
+            Instruction::Bxl(n) => regs[1] ^= n,
+            Instruction::Bst(operand) => regs[1] = combo(regs, operand) % 8,
+            Instruction::Jnz(_) => unreachable!("jnz is handled by the caller"),
+            Instruction::Bxc => regs[1] ^= regs[2],
+            Instruction::Out(operand) => return Some((combo(regs, operand) % 8) as u8),
+            Instruction::Bdv(operand) => regs[1] = shr(regs[0], combo(regs, operand)),
+            Instruction::Cdv(operand) => regs[2] = shr(regs[0], combo(regs, operand)),
+        }
+        None
+    }
+}
+
+pub struct Compiled {
+    /// The instruction at each reachable instruction pointer.
+    code: Vec<Option<Instruction>>,
+    /// The loop body when the program is a single loop.
+    body: Option<Vec<Instruction>>,
+    /// How many instructions a run may execute before giving up.
+    pub max_steps: usize,
+}
+
+impl Compiled {
+    /// Decodes the instructions reachable from the start.  Unreachable ones may be invalid,
+    /// as jumps can land on odd positions.
+    pub fn new(program: &[u8]) -> aoc::Result<Self> {
+        let mut code = vec![None; program.len()];
+        let mut todo = vec![0];
+        while let Some(ip) = todo.pop() {
+            if ip + 1 >= program.len() || code[ip].is_some() {
+                continue;
+            }
+            let instruction = Instruction::decode(program[ip], program[ip + 1])
+                .map_err(|err| AocError::invalid_state(format!("at {}: {}", ip, err)))?;
+            code[ip] = Some(instruction);
+            todo.push(ip + 2);
+            if let Instruction::Jnz(target) = instruction {
+                todo.push(target);
+            }
+        }
+
+        let body = match program.split_last_chunk::<2>() {
+            Some((body, [3, 0])) if body.len().is_multiple_of(2) => (0..body.len())
+                .step_by(2)
+                .map(|ip| code[ip].filter(|i| !matches!(i, Instruction::Jnz(_))))
+                .collect(),
+            _ => None,
+        };
+        Ok(Self {
+            code,
+            body,
+            max_steps: MAX_STEPS,
+        })
+    }
+
+    /// Whether the program runs as a single loop.
+    pub fn is_loop(&self) -> bool {
+        self.body.is_some()
+    }
+
+    /// Runs the program from registers A, B and C, replacing the contents of `out` with its
+    /// output.  If it does not halt, `out` may also hold a few values printed after the limit.
+    pub fn run_into(&self, regs: [u64; 3], out: &mut Vec<u8>) -> Result<(), DidNotHalt> {
+        out.clear();
+        let Some(body) = &self.body else {
+            let mut outputs = self.outputs(regs);
+            out.extend(outputs.by_ref());
+            return outputs.error.map_or(Ok(()), Err);
+        };
+
+        let mut regs = regs;
+        let mut steps = 0;
+        loop {
+            for &instruction in body {
+                if let Some(val) = instruction.execute(&mut regs) {
+                    out.push(val);
+                }
+            }
+            // The limit is checked once per pass.  A pass that overran it was cut short at the
+            // instruction the interpreter would have stopped at.
+            let before = steps;
+            steps += body.len() + 1;
+            if steps > self.max_steps {
+                let ip = 2 * (self.max_steps - before);
+                return Err(DidNotHalt {
+                    steps: self.max_steps,
+                    ip,
+                });
+            }
+            if regs[0] == 0 {
+                return Ok(());
+            }
+            if steps == self.max_steps {
+                return Err(DidNotHalt { steps, ip: 0 });
+            }
+        }
+    }
+
+    /// The output produced one value at a time.
+    pub fn outputs(&self, regs: [u64; 3]) -> Outputs<'_> {
+        Outputs {
+            compiled: self,
+            regs,
+            ip: 0,
+            steps: 0,
+            error: None,
+        }
+    }
+
+    /// Whether the program prints exactly `target`, stopping at the first difference.
+    pub fn matches(&self, regs: [u64; 3], target: &[u8]) -> bool {
+        let mut outputs = self.outputs(regs);
+        outputs.by_ref().eq(target.iter().copied()) && outputs.error.is_none()
+    }
+}
+
+pub struct Outputs<'a> {
+    compiled: &'a Compiled,
+    regs: [u64; 3],
+    ip: usize,
+    steps: usize,
+    error: Option<DidNotHalt>,
+}
+
+impl Outputs<'_> {
+    /// Set once the iterator has ended because the step limit ran out.
+    pub fn error(&self) -> Option<&DidNotHalt> {
+        self.error.as_ref()
+    }
+
+    pub fn registers(&self) -> [u64; 3] {
+        self.regs
+    }
+}
+
+impl Iterator for Outputs<'_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        while let Some(&Some(instruction)) = self.compiled.code.get(self.ip) {
+            if self.steps == self.compiled.max_steps {
+                self.error = Some(DidNotHalt {
+                    steps: self.steps,
+                    ip: self.ip,
+                });
+                // Park past the end so the iterator stays finished.
+                self.ip = usize::MAX;
+                return None;
+            }
+            self.steps += 1;
+            if let Instruction::Jnz(target) = instruction {
+                self.ip = if self.regs[0] == 0 {
+                    self.ip + 2
+                } else {
+                    target
+                };
+                continue;
+            }
+            self.ip += 2;
+            if let Some(val) = instruction.execute(&mut self.regs) {
+                return Some(val);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, Computer, RunError};
+    use aoc::testing::{self, Rng};
+    use std::fs::read_to_string;
+
+    #[test]
+    fn test_compiled() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let (computer, program) = parse_input(&data).unwrap();
+        let compiled = Compiled::new(&program).unwrap();
+        assert!(compiled.is_loop());
+        let mut out = vec![7; 3];
+        compiled.run_into([computer.a.val, 0, 0], &mut out).unwrap();
+        assert_eq!(out, [7, 3, 1, 3, 6, 3, 6, 0, 2]);
+        assert_eq!(
+            compiled.outputs([computer.a.val, 0, 0]).collect::<Vec<_>>(),
+            out
+        );
+        assert!(compiled.matches([105843716614554, 0, 0], &program));
+        assert!(!compiled.matches([105843716614553, 0, 0], &program));
+        assert!(!compiled.matches([105843716614554, 0, 0], &program[1..]));
+
+        // A jump past the loop start, and one to an odd position.
+        assert!(!Compiled::new(&[0, 1, 5, 4, 3, 2]).unwrap().is_loop());
+        let compiled = Compiled::new(&[3, 3, 1, 5, 4, 5, 5, 5]).unwrap();
+        assert_eq!(compiled.outputs([1, 0, 0]).collect::<Vec<_>>(), [1, 0]);
+        assert_eq!(compiled.outputs([0, 0, 0]).collect::<Vec<_>>(), [5]);
+
+        assert!(Compiled::new(&[5, 7]).is_err());
+        assert!(Compiled::new(&[3, 1, 5, 7]).is_err());
+        assert!(
+            Compiled::new(&[2, 5, 7, 5]).is_ok(),
+            "5,7 at 1 is never reached"
+        );
+    }
+
+    #[test]
+    fn test_step_limit() {
+        let mut compiled = Compiled::new(&[5, 4, 3, 0]).unwrap();
+        compiled.max_steps = 10;
+        let mut out = vec![];
+        let err = compiled.run_into([1, 0, 0], &mut out).unwrap_err();
+        assert_eq!(err, DidNotHalt { steps: 10, ip: 0 });
+        assert_eq!(out.len(), 5);
+        let mut outputs = compiled.outputs([1, 0, 0]);
+        assert_eq!(outputs.by_ref().count(), 5);
+        assert_eq!(outputs.error(), Some(&err));
+        assert_eq!(outputs.next(), None);
+        assert!(!compiled.matches([1, 0, 0], &[1; 5]));
+    }
+
+    #[test]
+    fn test_against_interpreter() {
+        const MAX_STEPS: usize = 200;
+        let run = |program: &[u8], a: u64, compiled: bool| {
+            let mut fast = Compiled::new(program).ok()?;
+            if !compiled {
+                return Some(Computer::new(a, 0, 0).run_limited(program, MAX_STEPS));
+            }
+            fast.max_steps = MAX_STEPS;
+            let mut out = vec![];
+            let buffered = fast.run_into([a, 0, 0], &mut out).map(|()| out);
+            let mut outputs = fast.outputs([a, 0, 0]);
+            let streamed = outputs.by_ref().collect::<Vec<_>>();
+            let streamed = outputs.error().cloned().map_or(Ok(streamed), Err);
+            assert_eq!(buffered, streamed);
+            Some(buffered.map_err(RunError::from))
+        };
+        testing::differential(
+            "compiled",
+            500,
+            |rng: &mut Rng| {
+                let len = rng.usize(1, 8);
+                let mut program = testing::opcode_program(rng, len);
+                if rng.bool() {
+                    program.extend([3, 0]);
+                }
+                (program, rng.next_u64() >> rng.usize(0, 63))
+            },
+            |(program, a)| run(program, *a, false),
+            |(program, a)| run(program, *a, true),
+        );
+    }
+}
@@ -0,0 +1,506 @@
+//! A step debugger for the 3-bit computer.
+//!
+//! [`Debugger`] keeps the program, the computer and a trace of the last executed instructions.
+//! Execution can stop at breakpoints, which are either an instruction pointer or a comparison
+//! on a register, checked before each instruction.  [`Debugger::execute`] runs the commands of
+//! the interactive debugger:
+//!
+//! ```text
+//! step [n]              execute n instructions (default 1), up to the step limit
+//! continue              run until a breakpoint or the program halts
+//! regs                  show the registers and instruction pointer
+//! set <a|b|c> <value>   change a register
+//! break [<ip> | <a|b|c> <op> <value>]
+//!                       add a breakpoint, op is one of == != < <= > >=, or list them
+//! delete <n>            remove breakpoint n
+//! trace [n]             show the last n executed instructions (default 10)
+//! list                  disassemble the program, marking the current instruction
+//! out                   show the output so far
+//! limit <n>             set how many steps continue or step may take
+//! reset                 restore the initial registers and start again
+//! ```
+
+use crate::{asm, Computer, DidNotHalt, InvalidInstruction, RunError, Step, MAX_STEPS};
+use aoc::AocError;
+use itertools::Itertools;
+use std::{collections::VecDeque, fmt};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reg {
+    A,
+    B,
+    C,
+}
+
+impl Reg {
+    fn parse(name: &str) -> Option<Reg> {
+        match name {
+            "a" | "A" => Some(Reg::A),
+            "b" | "B" => Some(Reg::B),
+            "c" | "C" => Some(Reg::C),
+            _ => None,
+        }
+    }
+
+    pub fn get(self, computer: &Computer) -> u64 {
+        match self {
+            Reg::A => computer.a.val,
+            Reg::B => computer.b.val,
+            Reg::C => computer.c.val,
+        }
+    }
+
+    pub fn set(self, computer: &mut Computer, val: u64) {
+        match self {
+            Reg::A => computer.a.val = val,
+            Reg::B => computer.b.val = val,
+            Reg::C => computer.c.val = val,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cmp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Cmp {
+    const ALL: [(Cmp, &'static str); 6] = [
+        (Cmp::Eq, "=="),
+        (Cmp::Ne, "!="),
+        (Cmp::Lt, "<"),
+        (Cmp::Le, "<="),
+        (Cmp::Gt, ">"),
+        (Cmp::Ge, ">="),
+    ];
+
+    pub fn apply(self, lhs: u64, rhs: u64) -> bool {
+        match self {
+            Cmp::Eq => lhs == rhs,
+            Cmp::Ne => lhs != rhs,
+            Cmp::Lt => lhs < rhs,
+            Cmp::Le => lhs <= rhs,
+            Cmp::Gt => lhs > rhs,
+            Cmp::Ge => lhs >= rhs,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Breakpoint {
+    Ip(usize),
+    Register(Reg, Cmp, u64),
+}
+
+impl Breakpoint {
+    /// Parses `4` or a comparison like `a == 0`.
+    pub fn parse(text: &str) -> aoc::Result<Breakpoint> {
+        let fields = text.split_ascii_whitespace().collect::<Vec<_>>();
+        match *fields.as_slice() {
+            [ip] => ip
+                .parse()
+                .map(Breakpoint::Ip)
+                .map_err(|_| AocError::invalid_state(format!("invalid ip {:?}", ip))),
+            [reg, op, val] => {
+                let reg = Reg::parse(reg).ok_or_else(|| {
+                    AocError::invalid_state(format!("unknown register {:?}", reg))
+                })?;
+                let Some(&(cmp, _)) = Cmp::ALL.iter().find(|&&(_, name)| name == op) else {
+                    return Err(AocError::invalid_state(format!(
+                        "unknown comparison {:?}",
+                        op
+                    )));
+                };
+                Ok(Breakpoint::Register(reg, cmp, parse_value(val)?))
+            }
+            _ => Err(AocError::invalid_state(
+                "expected <ip> or <register> <op> <value>",
+            )),
+        }
+    }
+
+    pub fn hit(&self, computer: &Computer) -> bool {
+        match *self {
+            Breakpoint::Ip(ip) => computer.ip == ip,
+            Breakpoint::Register(reg, cmp, val) => cmp.apply(reg.get(computer), val),
+        }
+    }
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Breakpoint::Ip(ip) => write!(f, "ip {}", ip),
+            Breakpoint::Register(reg, cmp, val) => {
+                let op = Cmp::ALL.iter().find(|&&(c, _)| c == cmp).unwrap().1;
+                write!(f, "{:?} {} {}", reg, op, val)
+            }
+        }
+    }
+}
+
+fn parse_value(text: &str) -> aoc::Result<u64> {
+    text.parse()
+        .map_err(|_| AocError::invalid_state(format!("invalid number {:?}", text)))
+}
+
+/// Why [`Debugger::resume`] stopped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stop {
+    Halted,
+    /// The index of the breakpoint that was hit.
+    Breakpoint(usize),
+}
+
+/// How many executed instructions the trace keeps.
+pub const TRACE_LEN: usize = 1000;
+
+pub struct Debugger {
+    pub computer: Computer,
+    program: Vec<u8>,
+    initial: (u64, u64, u64),
+    pub breakpoints: Vec<Breakpoint>,
+    /// The last [`TRACE_LEN`] executed instructions, oldest first.
+    pub trace: VecDeque<Step>,
+    /// How many steps one `resume` or `step` command may take before giving up.
+    pub max_steps: usize,
+}
+
+impl Debugger {
+    pub fn new(mut computer: Computer, program: Vec<u8>) -> Self {
+        computer.restart();
+        let initial = (computer.a.val, computer.b.val, computer.c.val);
+        Self {
+            computer,
+            program,
+            initial,
+            breakpoints: vec![],
+            trace: VecDeque::with_capacity(TRACE_LEN),
+            max_steps: MAX_STEPS,
+        }
+    }
+
+    pub fn program(&self) -> &[u8] {
+        &self.program
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.computer.read_from_ip(&self.program).is_none()
+    }
+
+    /// Executes one instruction, or returns `None` if the program has halted.
+    pub fn step(&mut self) -> Result<Option<Step>, InvalidInstruction> {
+        let Some(step) = self.computer.step(&self.program)? else {
+            return Ok(None);
+        };
+        if self.trace.len() == TRACE_LEN {
+            self.trace.pop_front();
+        }
+        self.trace.push_back(step);
+        Ok(Some(step))
+    }
+
+    /// Runs until the program halts or, after at least one instruction, a breakpoint is hit.
+    pub fn resume(&mut self) -> Result<Stop, RunError> {
+        for _ in 0..self.max_steps {
+            if self.step()?.is_none() {
+                return Ok(Stop::Halted);
+            }
+            if let Some(i) = self
+                .breakpoints
+                .iter()
+                .position(|breakpoint| breakpoint.hit(&self.computer))
+            {
+                return Ok(Stop::Breakpoint(i));
+            }
+        }
+        if self.is_halted() {
+            return Ok(Stop::Halted);
+        }
+        Err(DidNotHalt {
+            steps: self.max_steps,
+            ip: self.computer.ip,
+        }
+        .into())
+    }
+
+    /// Restores the initial registers and goes back to the first instruction, keeping the
+    /// breakpoints.
+    pub fn reset(&mut self) {
+        let (a, b, c) = self.initial;
+        self.computer = Computer::new(a, b, c);
+        self.trace.clear();
+    }
+
+    fn registers(&self) -> String {
+        let computer = &self.computer;
+        format!(
+            "A={} B={} C={} ip={}",
+            computer.a.val, computer.b.val, computer.c.val, computer.ip
+        )
+    }
+
+    fn location(&self) -> String {
+        match self.computer.read_from_ip(&self.program) {
+            Some((opcode, operand)) => {
+                format!("{:3}: {}", self.computer.ip, instruction(opcode, operand))
+            }
+            None => "halted".to_owned(),
+        }
+    }
+
+    /// Runs one debugger command and returns what it prints.
+    pub fn execute(&mut self, command: &str) -> aoc::Result<String> {
+        let (name, args) = command
+            .trim()
+            .split_once(' ')
+            .map_or((command.trim(), ""), |(name, args)| (name, args.trim()));
+        let count = |default: usize| {
+            if args.is_empty() {
+                Ok(default)
+            } else {
+                parse_value(args).map(|n| n as usize)
+            }
+        };
+        match name {
+            "step" | "s" => {
+                let n = count(1)?;
+                let mut taken = 0;
+                while taken < n.min(self.max_steps) && self.step()?.is_some() {
+                    taken += 1;
+                }
+                if taken < n && !self.is_halted() {
+                    return Err(DidNotHalt {
+                        steps: taken,
+                        ip: self.computer.ip,
+                    }
+                    .into());
+                }
+                let shown = taken.min(self.trace.len());
+                let mut lines = vec![];
+                if shown < taken {
+                    lines.push(format!("... {} earlier steps", taken - shown));
+                }
+                lines.extend(
+                    self.trace
+                        .range(self.trace.len() - shown..)
+                        .map(format_step),
+                );
+                lines.push(self.location());
+                Ok(lines.join("\n"))
+            }
+            "continue" | "c" => {
+                let stop = match self.resume()? {
+                    Stop::Halted => format!("halted, output {}", self.output()),
+                    Stop::Breakpoint(i) => {
+                        format!(
+                            "breakpoint {} ({})\n{}",
+                            i,
+                            self.breakpoints[i],
+                            self.location()
+                        )
+                    }
+                };
+                Ok(stop)
+            }
+            "regs" | "r" => Ok(self.registers()),
+            "set" => {
+                let Some((reg, val)) = args.split_once(' ') else {
+                    return Err(AocError::invalid_state("expected set <register> <value>"));
+                };
+                let reg = Reg::parse(reg).ok_or_else(|| {
+                    AocError::invalid_state(format!("unknown register {:?}", reg))
+                })?;
+                reg.set(&mut self.computer, parse_value(val.trim())?);
+                Ok(self.registers())
+            }
+            "break" | "b" if args.is_empty() => Ok(self
+                .breakpoints
+                .iter()
+                .enumerate()
+                .map(|(i, breakpoint)| format!("{}: {}", i, breakpoint))
+                .join("\n")),
+            "break" | "b" => {
+                self.breakpoints.push(Breakpoint::parse(args)?);
+                Ok(format!(
+                    "breakpoint {} ({})",
+                    self.breakpoints.len() - 1,
+                    self.breakpoints.last().unwrap()
+                ))
+            }
+            "delete" | "d" => {
+                let i = count(0)?;
+                if i >= self.breakpoints.len() {
+                    return Err(AocError::invalid_state(format!("no breakpoint {}", i)));
+                }
+                Ok(format!("deleted {}", self.breakpoints.remove(i)))
+            }
+            "trace" | "t" => {
+                let n = count(10)?;
+                Ok(self
+                    .trace
+                    .range(self.trace.len().saturating_sub(n)..)
+                    .map(format_step)
+                    .join("\n"))
+            }
+            "list" | "l" => Ok(self
+                .program
+                .chunks_exact(2)
+                .enumerate()
+                .map(|(i, pair)| {
+                    let marker = if i * 2 == self.computer.ip {
+                        "=>"
+                    } else {
+                        "  "
+                    };
+                    format!("{} {:3}: {}", marker, i * 2, instruction(pair[0], pair[1]))
+                })
+                .join("\n")),
+            "out" | "o" => Ok(self.output()),
+            "limit" => {
+                self.max_steps = count(MAX_STEPS)?;
+                Ok(format!(
+                    "continue and step stop after {} steps",
+                    self.max_steps
+                ))
+            }
+            "reset" => {
+                self.reset();
+                Ok(self.registers())
+            }
+            "help" | "h" => Ok("commands: step [n], continue, regs, set <reg> <value>, \
+                                break [<ip> | <reg> <op> <value>], delete <n>, trace [n], \
+                                list, out, limit <n>, reset, quit"
+                .to_owned()),
+            _ => Err(AocError::invalid_state(format!(
+                "unknown command {:?}, try help",
+                name
+            ))),
+        }
+    }
+
+    fn output(&self) -> String {
+        self.computer.output().iter().join(",")
+    }
+}
+
+fn instruction(opcode: u8, operand: u8) -> String {
+    asm::disassemble_instruction(opcode, operand)
+        .unwrap_or_else(|_| format!("?? {} {}", opcode, operand))
+}
+
+/// One trace line: the instruction and the registers before it ran.
+pub fn format_step(step: &Step) -> String {
+    format!(
+        "{:3}: {:<10} A={} B={} C={}",
+        step.ip,
+        instruction(step.opcode, step.operand),
+        step.a,
+        step.b,
+        step.c
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+    use std::fs::read_to_string;
+
+    fn example() -> Debugger {
+        let data = read_to_string("../examples/17/test.txt").unwrap();
+        let (computer, program) = parse_input(&data).unwrap();
+        Debugger::new(computer, program)
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        let mut debugger = example();
+        debugger.breakpoints.push(Breakpoint::parse("4").unwrap());
+        assert_eq!(debugger.resume(), Ok(Stop::Breakpoint(0)));
+        assert_eq!(debugger.trace.len(), 2);
+        assert_eq!(debugger.computer.output(), [4]);
+
+        debugger.breakpoints[0] = Breakpoint::parse("a < 10").unwrap();
+        assert_eq!(debugger.resume(), Ok(Stop::Breakpoint(0)));
+        assert_eq!(debugger.computer.a.val, 5);
+        assert_eq!(
+            debugger.resume(),
+            Ok(Stop::Breakpoint(0)),
+            "still true after the next instruction"
+        );
+        debugger.breakpoints.clear();
+        assert_eq!(debugger.resume(), Ok(Stop::Halted));
+        assert_eq!(debugger.computer.output(), [4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
+        assert_eq!(debugger.resume(), Ok(Stop::Halted));
+
+        assert!(Breakpoint::parse("a = 1").is_err());
+        assert!(Breakpoint::parse("d == 1").is_err());
+        assert_eq!(Breakpoint::parse("c >= 8").unwrap().to_string(), "C >= 8");
+
+        let mut debugger = Debugger::new(Computer::new(1, 0, 0), vec![3, 0]);
+        debugger.max_steps = 50;
+        assert_eq!(
+            debugger.resume(),
+            Err(RunError::DidNotHalt(DidNotHalt { steps: 50, ip: 0 }))
+        );
+
+        let mut debugger = Debugger::new(Computer::new(1, 0, 0), vec![5, 7]);
+        assert_eq!(
+            debugger.resume(),
+            Err(RunError::Invalid(InvalidInstruction {
+                ip: 0,
+                opcode: 5,
+                operand: 7
+            }))
+        );
+    }
+
+    #[test]
+    fn test_commands() {
+        let mut debugger = example();
+        assert_eq!(
+            debugger.execute("step").unwrap(),
+            "  0: adv A>>1   A=729 B=0 C=0\n  2: out A%8"
+        );
+        assert_eq!(debugger.execute("set a 16").unwrap(), "A=16 B=0 C=0 ip=2");
+        debugger.execute("break a == 2").unwrap();
+        assert_eq!(
+            debugger.execute("continue").unwrap(),
+            "breakpoint 0 (A == 2)\n  2: out A%8"
+        );
+        assert_eq!(debugger.execute("out").unwrap(), "0,0,4");
+        assert_eq!(debugger.execute("trace 2").unwrap().lines().count(), 2);
+        assert!(debugger
+            .execute("list")
+            .unwrap()
+            .contains("=>   2: out A%8"));
+        debugger.execute("delete 0").unwrap();
+        assert_eq!(debugger.execute("c").unwrap(), "halted, output 0,0,4,2,1,0");
+        assert_eq!(debugger.execute("step").unwrap(), "halted");
+        assert_eq!(debugger.execute("reset").unwrap(), "A=729 B=0 C=0 ip=0");
+
+        let error =
+            |debugger: &mut Debugger, command| debugger.execute(command).unwrap_err().to_string();
+        assert!(error(&mut debugger, "set d 1").contains("unknown register"));
+        assert!(error(&mut debugger, "delete 3").contains("no breakpoint 3"));
+        assert!(error(&mut debugger, "jump").contains("unknown command"));
+        debugger.execute("set a 1").unwrap();
+        debugger.execute("limit 5").unwrap();
+        debugger.program = vec![3, 0];
+        assert!(error(&mut debugger, "continue").contains("did not halt after 5 steps"));
+        assert!(error(&mut debugger, "step 99999999999").contains("did not halt after 5 steps"));
+        assert_eq!(debugger.execute("step 5").unwrap().lines().count(), 6);
+
+        debugger.execute("limit 5000").unwrap();
+        let text = debugger.execute("step 2500").unwrap();
+        assert!(text.starts_with("... 1500 earlier steps\n"));
+        assert_eq!(text.lines().count(), TRACE_LEN + 2);
+        assert_eq!(debugger.trace.len(), TRACE_LEN);
+    }
+}
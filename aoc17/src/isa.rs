@@ -0,0 +1,565 @@
+//! A configurable version of the computer.
+//!
+//! A [`Spec`] describes the machine: its registers, how wide they are, how combo operands are
+//! decoded and what each opcode does.  [`Machine`] runs programs for any spec, reporting
+//! invalid instructions as errors.  [`Spec::default`] is the machine from the puzzle, which
+//! [`Computer`](crate::Computer) implements directly and faster.
+
+use crate::DidNotHalt;
+use aoc::AocError;
+
+/// How an instruction's operand is read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OperandMode {
+    Literal,
+    /// Decoded through [`Spec::combo`].
+    Combo,
+    Ignored,
+}
+
+/// What a combo operand stands for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Combo {
+    Literal(u64),
+    Register(usize),
+    /// Using it is an error.
+    Reserved,
+}
+
+/// Where an action reads a value from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// The decoded operand.
+    Operand,
+    Register(usize),
+    Const(u64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AluOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    Shl,
+    Shr,
+    And,
+    Or,
+    Xor,
+}
+
+impl AluOp {
+    // `None` for division by zero.  Results are truncated to the word width by the caller.
+    fn apply(self, lhs: u64, rhs: u64) -> Option<u64> {
+        let shift = |f: fn(u64, u32) -> Option<u64>| {
+            Some(u32::try_from(rhs).ok().and_then(|n| f(lhs, n)).unwrap_or(0))
+        };
+        match self {
+            AluOp::Add => Some(lhs.wrapping_add(rhs)),
+            AluOp::Sub => Some(lhs.wrapping_sub(rhs)),
+            AluOp::Mul => Some(lhs.wrapping_mul(rhs)),
+            AluOp::Div => lhs.checked_div(rhs),
+            AluOp::Mod => lhs.checked_rem(rhs),
+            AluOp::Shl => shift(u64::checked_shl),
+            AluOp::Shr => shift(u64::checked_shr),
+            AluOp::And => Some(lhs & rhs),
+            AluOp::Or => Some(lhs | rhs),
+            AluOp::Xor => Some(lhs ^ rhs),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Condition {
+    Always,
+    Zero(usize),
+    NonZero(usize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// `dst = lhs op rhs`.
+    Alu {
+        op: AluOp,
+        dst: usize,
+        lhs: Source,
+        rhs: Source,
+    },
+    /// Jumps to an absolute position, otherwise moves on to the next instruction.
+    Jump {
+        when: Condition,
+        target: Source,
+    },
+    /// Outputs the value, reduced modulo `modulus` if there is one.
+    Out {
+        value: Source,
+        modulus: Option<u64>,
+    },
+    Halt,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Opcode {
+    pub mnemonic: String,
+    pub operand: OperandMode,
+    pub action: Action,
+}
+
+impl Opcode {
+    pub fn new(mnemonic: &str, operand: OperandMode, action: Action) -> Self {
+        Self {
+            mnemonic: mnemonic.to_owned(),
+            operand,
+            action,
+        }
+    }
+}
+
+/// Every instruction is an opcode followed by one operand, each `operand_bits` wide.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Spec {
+    pub registers: Vec<String>,
+    /// Register values are truncated to this many bits, at most 64.
+    pub word_bits: u32,
+    pub operand_bits: u32,
+    /// What each combo operand value stands for.
+    pub combo: Vec<Combo>,
+    /// Indexed by opcode.
+    pub opcodes: Vec<Opcode>,
+}
+
+impl Default for Spec {
+    /// The 2024 day 17 machine.
+    fn default() -> Self {
+        let (a, b, c) = (0, 1, 2);
+        let alu = |op, dst, lhs, rhs| Action::Alu { op, dst, lhs, rhs };
+        let divide = |dst| alu(AluOp::Shr, dst, Source::Register(a), Source::Operand);
+        Self {
+            registers: ["A", "B", "C"].map(String::from).to_vec(),
+            word_bits: 64,
+            operand_bits: 3,
+            combo: vec![
+                Combo::Literal(0),
+                Combo::Literal(1),
+                Combo::Literal(2),
+                Combo::Literal(3),
+                Combo::Register(a),
+                Combo::Register(b),
+                Combo::Register(c),
+                Combo::Reserved,
+            ],
+            opcodes: vec![
+                Opcode::new("adv", OperandMode::Combo, divide(a)),
+                Opcode::new(
+                    "bxl",
+                    OperandMode::Literal,
+                    alu(AluOp::Xor, b, Source::Register(b), Source::Operand),
+                ),
+                Opcode::new(
+                    "bst",
+                    OperandMode::Combo,
+                    alu(AluOp::Mod, b, Source::Operand, Source::Const(8)),
+                ),
+                Opcode::new(
+                    "jnz",
+                    OperandMode::Literal,
+                    Action::Jump {
+                        when: Condition::NonZero(a),
+                        target: Source::Operand,
+                    },
+                ),
+                Opcode::new(
+                    "bxc",
+                    OperandMode::Ignored,
+                    alu(AluOp::Xor, b, Source::Register(b), Source::Register(c)),
+                ),
+                Opcode::new(
+                    "out",
+                    OperandMode::Combo,
+                    Action::Out {
+                        value: Source::Operand,
+                        modulus: Some(8),
+                    },
+                ),
+                Opcode::new("bdv", OperandMode::Combo, divide(b)),
+                Opcode::new("cdv", OperandMode::Combo, divide(c)),
+            ],
+        }
+    }
+}
+
+impl Spec {
+    /// Checks that the spec only refers to registers and operands that exist.
+    pub fn validate(&self) -> aoc::Result<()> {
+        let invalid = |msg: String| Err(AocError::invalid_state(msg));
+        if !(1..=64).contains(&self.word_bits) {
+            return invalid(format!("word width {} is not 1 to 64", self.word_bits));
+        }
+        if !(1..=16).contains(&self.operand_bits) {
+            return invalid(format!(
+                "operand width {} is not 1 to 16",
+                self.operand_bits
+            ));
+        }
+        if self.combo.len() != 1 << self.operand_bits {
+            return invalid(format!(
+                "{} combo operands for {}-bit operands",
+                self.combo.len(),
+                self.operand_bits
+            ));
+        }
+        if self.opcodes.len() > 1 << self.operand_bits {
+            return invalid(format!(
+                "{} opcodes do not fit in {} bits",
+                self.opcodes.len(),
+                self.operand_bits
+            ));
+        }
+
+        let register = |r: usize| r < self.registers.len();
+        let source = |source| match source {
+            Source::Register(r) => register(r),
+            _ => true,
+        };
+        if let Some(combo) = self
+            .combo
+            .iter()
+            .position(|&combo| matches!(combo, Combo::Register(r) if !register(r)))
+        {
+            return invalid(format!("combo operand {} is not a register", combo));
+        }
+        for opcode in &self.opcodes {
+            let ok = match opcode.action {
+                Action::Alu { dst, lhs, rhs, .. } => register(dst) && source(lhs) && source(rhs),
+                Action::Jump { when, target } => {
+                    source(target)
+                        && match when {
+                            Condition::Always => true,
+                            Condition::Zero(r) | Condition::NonZero(r) => register(r),
+                        }
+                }
+                Action::Out {
+                    modulus: Some(0), ..
+                } => {
+                    return invalid(format!("{} outputs modulo 0", opcode.mnemonic));
+                }
+                Action::Out { value, .. } => source(value),
+                Action::Halt => true,
+            };
+            if !ok {
+                return invalid(format!(
+                    "{} uses a register that does not exist",
+                    opcode.mnemonic
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn mask(&self) -> u64 {
+        u64::MAX >> (64 - self.word_bits)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Machine<'a> {
+    spec: &'a Spec,
+    pub regs: Vec<u64>,
+    ip: usize,
+    out: Vec<u64>,
+}
+
+impl<'a> Machine<'a> {
+    pub fn new(spec: &'a Spec, regs: &[u64]) -> aoc::Result<Self> {
+        spec.validate()?;
+        if regs.len() != spec.registers.len() {
+            return Err(AocError::invalid_state(format!(
+                "expected {} registers",
+                spec.registers.len()
+            )));
+        }
+        Ok(Self {
+            spec,
+            regs: regs.iter().map(|&val| val & spec.mask()).collect(),
+            ip: 0,
+            out: vec![],
+        })
+    }
+
+    /// Parses a `Register <name>: <value>` line per register, a blank line and the program.
+    pub fn parse(spec: &'a Spec, data: &str) -> aoc::Result<(Self, Vec<u64>)> {
+        let mut lines = aoc::lines(data);
+        let mut regs = vec![];
+        for name in &spec.registers {
+            let Some(line) = lines.next() else {
+                let (num, _) = aoc::line_col(data, data.len());
+                return Err(AocError::parse(
+                    num + 1,
+                    1,
+                    format!("expected register {}", name),
+                ));
+            };
+            let text = line.strip_prefix(&format!("Register {}: ", name))?;
+            let val = line.parse::<u64>(text)?;
+            if val & !spec.mask() != 0 {
+                return Err(line.error(text, "value is wider than a register"));
+            }
+            regs.push(val);
+        }
+        let (Some(blank), Some(line)) = (lines.next(), lines.next()) else {
+            let (num, _) = aoc::line_col(data, data.len());
+            return Err(AocError::parse(num + 1, 1, "expected program"));
+        };
+        if !blank.text.is_empty() {
+            return Err(blank.error(blank.text, "expected a blank line"));
+        }
+        let mut program = vec![];
+        for n in line.strip_prefix("Program: ")?.split(',') {
+            let v = line.parse::<u64>(n)?;
+            if v >> spec.operand_bits != 0 {
+                return Err(line.error(n, format!("expected a {}-bit number", spec.operand_bits)));
+            }
+            program.push(v);
+        }
+        Ok((Self::new(spec, &regs)?, program))
+    }
+
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    pub fn output(&self) -> &[u64] {
+        &self.out
+    }
+
+    fn decode(&self, mode: OperandMode, operand: u64) -> aoc::Result<u64> {
+        match mode {
+            OperandMode::Literal | OperandMode::Ignored => Ok(operand),
+            OperandMode::Combo => match self.spec.combo.get(operand as usize) {
+                Some(Combo::Literal(val)) => Ok(*val),
+                Some(Combo::Register(r)) => Ok(self.regs[*r]),
+                Some(Combo::Reserved) => Err(AocError::invalid_state(format!(
+                    "reserved combo operand {} at {}",
+                    operand, self.ip
+                ))),
+                None => Err(AocError::invalid_state(format!(
+                    "combo operand {} at {} is out of range",
+                    operand, self.ip
+                ))),
+            },
+        }
+    }
+
+    /// Whether the instruction pointer is past the end or at a halt instruction.
+    pub fn is_halted(&self, program: &[u64]) -> bool {
+        match (program.get(self.ip), program.get(self.ip + 1)) {
+            (Some(&opcode), Some(_)) => self
+                .spec
+                .opcodes
+                .get(opcode as usize)
+                .is_some_and(|opcode| opcode.action == Action::Halt),
+            _ => true,
+        }
+    }
+
+    /// Executes one instruction, or returns `false` if the program has halted.
+    pub fn step(&mut self, program: &[u64]) -> aoc::Result<bool> {
+        let (Some(&opcode), Some(&operand)) = (program.get(self.ip), program.get(self.ip + 1))
+        else {
+            return Ok(false);
+        };
+        let Some(spec) = self.spec.opcodes.get(opcode as usize) else {
+            return Err(AocError::invalid_state(format!(
+                "invalid opcode {} at {}",
+                opcode, self.ip
+            )));
+        };
+        let operand = self.decode(spec.operand, operand)?;
+        let read = |source| match source {
+            Source::Operand => operand,
+            Source::Register(r) => self.regs[r],
+            Source::Const(val) => val,
+        };
+
+        let mut next = self.ip + 2;
+        match spec.action {
+            Action::Alu { op, dst, lhs, rhs } => {
+                let Some(val) = op.apply(read(lhs), read(rhs)) else {
+                    return Err(AocError::invalid_state(format!(
+                        "{} divides by zero at {}",
+                        spec.mnemonic, self.ip
+                    )));
+                };
+                self.regs[dst] = val & self.spec.mask();
+            }
+            Action::Jump { when, target } => {
+                let jump = match when {
+                    Condition::Always => true,
+                    Condition::Zero(r) => self.regs[r] == 0,
+                    Condition::NonZero(r) => self.regs[r] != 0,
+                };
+                if jump {
+                    next = read(target) as usize;
+                }
+            }
+            Action::Out { value, modulus } => {
+                let val = read(value);
+                self.out.push(modulus.map_or(val, |m| val % m));
+            }
+            Action::Halt => return Ok(false),
+        }
+        self.ip = next;
+        Ok(true)
+    }
+
+    /// Runs the program from the start, giving up after `max_steps` instructions.
+    pub fn run(&mut self, program: &[u64], max_steps: usize) -> aoc::Result<Vec<u64>> {
+        self.ip = 0;
+        self.out.clear();
+        for _ in 0..max_steps {
+            if !self.step(program)? {
+                return Ok(self.out.clone());
+            }
+        }
+        if self.is_halted(program) {
+            return Ok(self.out.clone());
+        }
+        Err(DidNotHalt {
+            steps: max_steps,
+            ip: self.ip,
+        }
+        .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Computer;
+    use aoc::testing::{self, Rng};
+    use std::fs::read_to_string;
+
+    #[test]
+    fn test_default_spec() {
+        let spec = Spec::default();
+        spec.validate().unwrap();
+        let data = read_to_string("src/main.txt").unwrap();
+        let (mut machine, program) = Machine::parse(&spec, &data).unwrap();
+        assert_eq!(
+            machine.run(&program, 1000).unwrap(),
+            [7, 3, 1, 3, 6, 3, 6, 0, 2]
+        );
+
+        let err = Machine::new(&spec, &[1, 0, 0])
+            .unwrap()
+            .run(&[5, 7], 10)
+            .unwrap_err();
+        assert!(err.to_string().contains("reserved combo operand 7 at 0"));
+        let err = Machine::new(&spec, &[1, 0, 0])
+            .unwrap()
+            .run(&[3, 0], 10)
+            .unwrap_err();
+        assert!(err.to_string().contains("did not halt after 10 steps"));
+        let err = Machine::new(&spec, &[1, 0, 0])
+            .unwrap()
+            .run(&[2, 9], 10)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("combo operand 9 at 0 is out of range"));
+        assert!(Machine::parse(&spec, "Register A: 1\nRegister B: 0\n").is_err());
+        assert!(Machine::parse(
+            &spec,
+            "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 8\n"
+        )
+        .unwrap_err()
+        .to_string()
+        .contains("expected a 3-bit number"));
+    }
+
+    #[test]
+    fn test_against_computer() {
+        let spec = Spec::default();
+        let run = |(program, a): &(Vec<u8>, u64), machine: bool| {
+            if machine {
+                let program = program.iter().map(|&v| v as u64).collect::<Vec<_>>();
+                let out = Machine::new(&spec, &[*a, 0, 0])
+                    .unwrap()
+                    .run(&program, 200)
+                    .ok()?;
+                return Some(out.iter().map(|&v| v as u8).collect());
+            }
+            Computer::new(*a, 0, 0).run_limited(program, 200).ok()
+        };
+        testing::differential(
+            "machine",
+            300,
+            |rng: &mut Rng| {
+                let len = rng.usize(1, 8);
+                let mut program = testing::opcode_program(rng, len);
+                program.extend([3, 0]);
+                (program, rng.next_u64() >> rng.usize(0, 63))
+            },
+            |input| run(input, false),
+            |input| run(input, true),
+        );
+    }
+
+    #[test]
+    fn test_variant() {
+        // Four 8-bit registers, 4-bit operands with combo 7 reading D, and two new opcodes.
+        let mut spec = Spec::default();
+        spec.registers.push("D".to_owned());
+        spec.word_bits = 8;
+        spec.operand_bits = 4;
+        spec.combo[7] = Combo::Register(3);
+        spec.combo.extend((8..16).map(Combo::Literal));
+        spec.opcodes.push(Opcode::new(
+            "add",
+            OperandMode::Combo,
+            Action::Alu {
+                op: AluOp::Add,
+                dst: 3,
+                lhs: Source::Register(3),
+                rhs: Source::Operand,
+            },
+        ));
+        spec.opcodes
+            .push(Opcode::new("hlt", OperandMode::Ignored, Action::Halt));
+        spec.validate().unwrap();
+
+        // D += 15 until A runs out, then print D, halting before the final out.
+        let data = "Register A: 3\nRegister B: 0\nRegister C: 0\nRegister D: 250\n\n\
+                    Program: 8,15,0,1,3,0,5,7,9,0,5,4\n";
+        let (mut machine, program) = Machine::parse(&spec, data).unwrap();
+        assert_eq!(machine.run(&program, 100).unwrap(), [(250 + 30) % 256 % 8]);
+        assert_eq!(machine.regs[3], (250 + 30) % 256);
+
+        assert!(Machine::parse(&spec, &data.replace("250", "256")).is_err());
+        spec.opcodes[8].action = Action::Alu {
+            op: AluOp::Add,
+            dst: 4,
+            lhs: Source::Register(3),
+            rhs: Source::Operand,
+        };
+        assert!(spec
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("add uses a register"));
+        spec.combo.pop();
+        assert!(spec.validate().is_err());
+
+        let mut spec = Spec::default();
+        spec.opcodes[5].action = Action::Out {
+            value: Source::Operand,
+            modulus: Some(0),
+        };
+        assert!(spec
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("out outputs modulo 0"));
+    }
+}
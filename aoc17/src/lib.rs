@@ -0,0 +1,524 @@
+pub mod asm;
+pub mod compiled;
+pub mod debug;
+pub mod isa;
+pub mod quine;
+
+use aoc::{AocError, Solution};
+use itertools::Itertools;
+use std::fmt;
+
+#[derive(Debug)]
+pub struct Register {
+    pub val: u64,
+}
+
+#[derive(Debug)]
+pub struct Computer {
+    pub a: Register,
+    pub b: Register,
+    pub c: Register,
+    ip: usize,
+    out: Vec<u8>,
+}
+
+/// The state just before one instruction is executed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub ip: usize,
+    pub opcode: u8,
+    pub operand: u8,
+    pub a: u64,
+    pub b: u64,
+    pub c: u64,
+}
+
+/// The program was still running when the step limit ran out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DidNotHalt {
+    pub steps: usize,
+    pub ip: usize,
+}
+
+impl fmt::Display for DidNotHalt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "program did not halt after {} steps, at ip {}",
+            self.steps, self.ip
+        )
+    }
+}
+
+impl std::error::Error for DidNotHalt {}
+
+impl From<DidNotHalt> for AocError {
+    fn from(err: DidNotHalt) -> Self {
+        AocError::invalid_state(err.to_string())
+    }
+}
+
+/// An opcode past 7, or a combo operand that is reserved or past 7.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidInstruction {
+    pub ip: usize,
+    pub opcode: u8,
+    pub operand: u8,
+}
+
+impl fmt::Display for InvalidInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid instruction {},{} at ip {}",
+            self.opcode, self.operand, self.ip
+        )
+    }
+}
+
+impl std::error::Error for InvalidInstruction {}
+
+impl From<InvalidInstruction> for AocError {
+    fn from(err: InvalidInstruction) -> Self {
+        AocError::invalid_state(err.to_string())
+    }
+}
+
+/// Why a run stopped before the program halted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RunError {
+    DidNotHalt(DidNotHalt),
+    Invalid(InvalidInstruction),
+}
+
+impl From<DidNotHalt> for RunError {
+    fn from(err: DidNotHalt) -> Self {
+        RunError::DidNotHalt(err)
+    }
+}
+
+impl From<InvalidInstruction> for RunError {
+    fn from(err: InvalidInstruction) -> Self {
+        RunError::Invalid(err)
+    }
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunError::DidNotHalt(err) => err.fmt(f),
+            RunError::Invalid(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for RunError {}
+
+impl From<RunError> for AocError {
+    fn from(err: RunError) -> Self {
+        AocError::invalid_state(err.to_string())
+    }
+}
+
+// A / 2^n, which is 0 once n reaches 64 rather than overflowing.
+pub(crate) fn shr(x: u64, n: u64) -> u64 {
+    u32::try_from(n)
+        .ok()
+        .and_then(|n| x.checked_shr(n))
+        .unwrap_or(0)
+}
+
+// Far more than any puzzle program needs, which loop a few times per output.
+pub const MAX_STEPS: usize = 1_000_000;
+
+/// Returns `None`, leaving the computer unchanged, for an invalid combo operand.
+pub type OpcodeFn = fn(&mut Computer, operand: u8) -> Option<()>;
+
+// Opcodes whose operand is a combo operand: adv, bst, out, bdv and cdv.
+pub const COMBO_OPCODES: [u8; 5] = [0, 2, 5, 6, 7];
+
+impl Computer {
+    const OPCODE_TABLE: [OpcodeFn; 8] = [
+        Self::adv,
+        Self::bxl,
+        Self::bst,
+        Self::jnz,
+        Self::bxc,
+        Self::out,
+        Self::bdv,
+        Self::cdv,
+    ];
+
+    /// The value of a combo operand, or `None` for the reserved 7 and anything past it.
+    pub fn combo_operand(&self, operand: u8) -> Option<u64> {
+        match operand {
+            n @ 0..=3 => Some(n as u64),
+            4 => Some(self.a.val),
+            5 => Some(self.b.val),
+            6 => Some(self.c.val),
+            _ => None,
+        }
+    }
+
+    pub fn adv(&mut self, operand: u8) -> Option<()> {
+        self.a.val = shr(self.a.val, self.combo_operand(operand)?);
+        self.ip += 2;
+        Some(())
+    }
+
+    pub fn bdv(&mut self, operand: u8) -> Option<()> {
+        self.b.val = shr(self.a.val, self.combo_operand(operand)?);
+        self.ip += 2;
+        Some(())
+    }
+
+    pub fn cdv(&mut self, operand: u8) -> Option<()> {
+        self.c.val = shr(self.a.val, self.combo_operand(operand)?);
+        self.ip += 2;
+        Some(())
+    }
+
+    pub fn bxl(&mut self, operand: u8) -> Option<()> {
+        self.b.val = self.b.val ^ operand as u64;
+        self.ip += 2;
+        Some(())
+    }
+
+    pub fn bst(&mut self, operand: u8) -> Option<()> {
+        self.b.val = self.combo_operand(operand)? % 8;
+        self.ip += 2;
+        Some(())
+    }
+
+    pub fn jnz(&mut self, operand: u8) -> Option<()> {
+        if self.a.val == 0 {
+            self.ip += 2;
+        } else {
+            self.ip = operand.into();
+        }
+        Some(())
+    }
+
+    pub fn bxc(&mut self, _: u8) -> Option<()> {
+        self.b.val = self.b.val ^ self.c.val;
+        self.ip += 2;
+        Some(())
+    }
+
+    pub fn out(&mut self, operand: u8) -> Option<()> {
+        self.out.push((self.combo_operand(operand)? % 8) as u8);
+        self.ip += 2;
+        Some(())
+    }
+
+    pub fn from(lines: &[aoc::Line]) -> aoc::Result<Self> {
+        let registers = lines
+            .iter()
+            .zip(["A", "B", "C"])
+            .map(|(line, name)| {
+                let val = line.strip_prefix(&format!("Register {}: ", name))?;
+                Ok(Register {
+                    val: line.parse(val)?,
+                })
+            })
+            .collect::<aoc::Result<Vec<_>>>()?;
+        let Some((a, b, c)) = registers.into_iter().collect_tuple() else {
+            let num = lines.last().map_or(1, |line| line.num + 1);
+            return Err(AocError::parse(num, 1, "expected registers A, B and C"));
+        };
+        Ok(Self {
+            a,
+            b,
+            c,
+            ip: 0,
+            out: vec![],
+        })
+    }
+
+    pub fn new(a: u64, b: u64, c: u64) -> Self {
+        Self {
+            a: Register { val: a },
+            b: Register { val: b },
+            c: Register { val: c },
+            ip: 0,
+            out: vec![],
+        }
+    }
+
+    pub fn handle_opcode(&mut self, opcode: u8, operand: u8) -> Result<(), InvalidInstruction> {
+        Self::OPCODE_TABLE
+            .get(opcode as usize)
+            .and_then(|opcode_fn| opcode_fn(self, operand))
+            .ok_or(InvalidInstruction {
+                ip: self.ip,
+                opcode,
+                operand,
+            })
+    }
+
+    pub fn read_from_ip(&self, program: &[u8]) -> Option<(u8, u8)> {
+        if self.ip + 1 >= program.len() {
+            None
+        } else {
+            Some((program[self.ip], program[self.ip + 1]))
+        }
+    }
+
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    pub fn output(&self) -> &[u8] {
+        &self.out
+    }
+
+    /// Moves back to the first instruction and clears the output, keeping the registers.
+    pub fn restart(&mut self) {
+        self.ip = 0;
+        self.out = vec![];
+    }
+
+    /// Executes one instruction, or returns `None` if the program has halted.
+    pub fn step(&mut self, program: &[u8]) -> Result<Option<Step>, InvalidInstruction> {
+        let Some((opcode, operand)) = self.read_from_ip(program) else {
+            return Ok(None);
+        };
+        let step = Step {
+            ip: self.ip,
+            opcode,
+            operand,
+            a: self.a.val,
+            b: self.b.val,
+            c: self.c.val,
+        };
+        self.handle_opcode(opcode, operand)?;
+        Ok(Some(step))
+    }
+
+    pub fn run(&mut self, program: &[u8]) -> Result<Vec<u8>, InvalidInstruction> {
+        self.restart();
+        while self.step(program)?.is_some() {}
+        Ok(self.out.clone())
+    }
+
+    /// Like `run`, but gives up after `max_steps` instructions.
+    pub fn run_limited(&mut self, program: &[u8], max_steps: usize) -> Result<Vec<u8>, RunError> {
+        self.trace(program, max_steps, |_| ())?;
+        Ok(self.out.clone())
+    }
+
+    /// Runs the program from the start, passing every step to `record`.
+    pub fn trace<F>(
+        &mut self,
+        program: &[u8],
+        max_steps: usize,
+        mut record: F,
+    ) -> Result<(), RunError>
+    where
+        F: FnMut(Step),
+    {
+        self.restart();
+        for _ in 0..max_steps {
+            match self.step(program)? {
+                Some(step) => record(step),
+                None => return Ok(()),
+            }
+        }
+        match self.read_from_ip(program) {
+            None => Ok(()),
+            Some(_) => Err(DidNotHalt {
+                steps: max_steps,
+                ip: self.ip,
+            }
+            .into()),
+        }
+    }
+
+    pub fn run_with_string_output(&mut self, program: &[u8]) -> Result<String, InvalidInstruction> {
+        Ok(self.run(program)?.iter().map(|n| n.to_string()).join(","))
+    }
+}
+
+pub fn parse_input(data: &str) -> aoc::Result<(Computer, Vec<u8>)> {
+    let mut lines = aoc::lines(data);
+    let computer_lines = lines
+        .by_ref()
+        .take_while(|line| !line.text.is_empty())
+        .collect::<Vec<_>>();
+
+    let computer = Computer::from(&computer_lines)?;
+
+    let Some(line) = lines.next() else {
+        let (num, _) = aoc::line_col(data, data.len());
+        return Err(AocError::parse(num + 1, 1, "expected program"));
+    };
+    let mut program = vec![];
+    for n in line.strip_prefix("Program: ")?.split(",") {
+        let v = line.parse::<u8>(n)?;
+        if v >= 8 {
+            return Err(line.error(n, "expected a 3-bit number"));
+        }
+        // Combo operand 7 is reserved and will not appear in valid programs.
+        let is_combo = program.len() % 2 == 1
+            && program
+                .last()
+                .is_some_and(|opcode| COMBO_OPCODES.contains(opcode));
+        if is_combo && v == 7 {
+            return Err(line.error(n, "reserved combo operand 7"));
+        }
+        program.push(v);
+    }
+
+    Ok((computer, program))
+}
+
+pub fn find_lowest_self_reproducing_a(program: &[u8]) -> aoc::Result<u64> {
+    let a = quine::lowest_a(program, program)?;
+    let mut computer = Computer::new(a, 0, 0);
+    let out = computer.run_limited(program, MAX_STEPS)?;
+    if program != out {
+        return Err(AocError::invalid_state(format!(
+            "A={} does not reproduce the program",
+            a
+        )));
+    }
+    Ok(a)
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    fn part1(&self, input: &str, params: &aoc::Params) -> aoc::Result<String> {
+        let (mut computer, program) = parse_input(input)?;
+        let out = computer.run_limited(&program, params.get("max_steps", MAX_STEPS)?)?;
+        Ok(out.iter().join(","))
+    }
+
+    fn part2(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        let (_, program) = parse_input(input)?;
+        Ok(find_lowest_self_reproducing_a(&program)?.to_string())
+    }
+}
+
+aoc::example_tests!(Day17);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::read_to_string;
+
+    #[test]
+    fn test_small() {
+        let mut computer1 = Computer::new(0, 0, 9);
+        computer1.run(&[2, 6]).unwrap();
+        assert_eq!(computer1.b.val, 1);
+
+        let mut computer2 = Computer::new(10, 0, 0);
+        computer2.run(&[5, 0, 5, 1, 5, 4]).unwrap();
+        assert_eq!(computer2.out, vec![0, 1, 2]);
+
+        let mut computer3 = Computer::new(2024, 0, 0);
+        computer3.run(&[0, 1, 5, 4, 3, 0]).unwrap();
+        assert_eq!(computer3.out, vec![4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0]);
+        assert_eq!(computer3.a.val, 0);
+
+        let mut computer4 = Computer::new(0, 29, 0);
+        computer4.run(&[1, 7]).unwrap();
+        assert_eq!(computer4.b.val, 26);
+
+        let mut computer5 = Computer::new(0, 2024, 43690);
+        computer5.run(&[4, 0]).unwrap();
+        assert_eq!(computer5.b.val, 44354);
+
+        // A debugger can set B past 63, which shifts everything out of A.
+        let mut computer6 = Computer::new(u64::MAX, 64, 0);
+        computer6.run(&[7, 5, 0, 5]).unwrap();
+        assert_eq!((computer6.a.val, computer6.c.val), (0, 0));
+
+        // Programs that did not come from parse_input can hold anything.
+        let invalid = |opcode, operand| InvalidInstruction {
+            ip: 2,
+            opcode,
+            operand,
+        };
+        let mut computer7 = Computer::new(8, 0, 0);
+        assert_eq!(computer7.run(&[1, 1, 2, 7]), Err(invalid(2, 7)));
+        assert_eq!(computer7.b.val, 1);
+        assert_eq!(computer7.run(&[1, 1, 0, 9]), Err(invalid(0, 9)));
+        assert_eq!(computer7.run(&[1, 1, 8, 0]), Err(invalid(8, 0)));
+        assert_eq!(computer7.run(&[1, 1, 1, 9]).unwrap(), []);
+        let err = computer7.run_limited(&[1, 1, 5, 7], 10).unwrap_err();
+        assert_eq!(err, RunError::Invalid(invalid(5, 7)));
+        assert!(AocError::from(err)
+            .to_string()
+            .contains("invalid instruction 5,7"));
+    }
+
+    #[test]
+    fn answer_part1() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let (mut computer, program) = parse_input(&data).unwrap();
+        assert_eq!(
+            computer
+                .run(&program)
+                .unwrap()
+                .iter()
+                .map(|n| n.to_string())
+                .join(","),
+            "7,3,1,3,6,3,6,0,2"
+        );
+    }
+
+    #[test]
+    fn test_trace() {
+        let mut computer = Computer::new(9, 0, 0);
+        let mut steps = vec![];
+        computer
+            .trace(&[0, 1, 5, 4, 3, 0], 100, |step| steps.push(step))
+            .unwrap();
+        assert_eq!(computer.output(), [4, 2, 1, 0]);
+        assert_eq!(steps.len(), 12);
+        assert_eq!(
+            steps[3],
+            Step {
+                ip: 0,
+                opcode: 0,
+                operand: 1,
+                a: 4,
+                b: 0,
+                c: 0
+            }
+        );
+
+        // jnz 0 with A never reaching zero.
+        let mut computer = Computer::new(1, 0, 0);
+        let err = computer.run_limited(&[1, 1, 3, 0], 100).unwrap_err();
+        assert_eq!(err, RunError::DidNotHalt(DidNotHalt { steps: 100, ip: 0 }));
+        assert!(AocError::from(err).to_string().contains("did not halt"));
+        assert_eq!(computer.run_limited(&[5, 4], 1).unwrap(), [1]);
+
+        let params = aoc::Params::new().with("max_steps", "10");
+        let err = Day17.part1(
+            "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 3,0\n",
+            &params,
+        );
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_part2_small() {
+        let program = vec![0, 3, 5, 4, 3, 0];
+        let mut computer = Computer::new(117440, 0, 0);
+        assert_eq!(computer.run(&program).unwrap(), program);
+    }
+
+    #[test]
+    fn answer_part2() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let (_, program) = parse_input(&data).unwrap();
+        assert_eq!(
+            find_lowest_self_reproducing_a(&program).unwrap(),
+            105843716614554
+        );
+    }
+}
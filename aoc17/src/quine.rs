@@ -0,0 +1,306 @@
+//! Finding the smallest initial A for which a program prints a given output.
+//!
+//! Supported programs are a single loop ending in `jnz 0`.  Each pass shifts A right by a fixed
+//! number of bits with `adv` and sets B and C from A before reading them, so what a pass prints
+//! depends only on A at its start.  One symbolic pass over the loop body gives every output as
+//! an expression in A.  The search then works back from the last pass, where only the top bits
+//! of A are left, and adds the bits shifted out by each earlier pass.  Trying the smallest bits
+//! first finds the smallest A.
+
+use crate::{asm, shr};
+use aoc::AocError;
+use std::fmt;
+
+// Passes shifting A further than this would make the search too wide.
+const MAX_SHIFT: u32 = 12;
+
+/// A register value in terms of the registers at the start of the loop body.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expr {
+    A,
+    B,
+    C,
+    Const(u64),
+    Shr(Box<Expr>, Box<Expr>),
+    Xor(Box<Expr>, Box<Expr>),
+    Mod8(Box<Expr>),
+}
+
+impl Expr {
+    fn shr(lhs: Expr, rhs: Expr) -> Expr {
+        match (lhs, rhs) {
+            (Expr::Const(x), Expr::Const(y)) => Expr::Const(shr(x, y)),
+            (lhs, Expr::Const(0)) => lhs,
+            (lhs, rhs) => Expr::Shr(Box::new(lhs), Box::new(rhs)),
+        }
+    }
+
+    fn xor(lhs: Expr, rhs: Expr) -> Expr {
+        match (lhs, rhs) {
+            (Expr::Const(x), Expr::Const(y)) => Expr::Const(x ^ y),
+            (expr, Expr::Const(0)) | (Expr::Const(0), expr) => expr,
+            (Expr::Xor(inner, rhs), Expr::Const(c2)) => match *rhs {
+                Expr::Const(c1) => Expr::xor(*inner, Expr::Const(c1 ^ c2)),
+                rhs => Expr::Xor(
+                    Box::new(Expr::Xor(inner, Box::new(rhs))),
+                    Box::new(Expr::Const(c2)),
+                ),
+            },
+            (lhs, rhs) => Expr::Xor(Box::new(lhs), Box::new(rhs)),
+        }
+    }
+
+    fn mod8(expr: Expr) -> Expr {
+        match expr {
+            Expr::Const(x) => Expr::Const(x % 8),
+            expr @ Expr::Mod8(_) => expr,
+            expr => Expr::Mod8(Box::new(expr)),
+        }
+    }
+
+    /// Whether the value depends on B or C from before the loop body.
+    pub fn reads_state(&self) -> bool {
+        match self {
+            Expr::B | Expr::C => true,
+            Expr::A | Expr::Const(_) => false,
+            Expr::Shr(lhs, rhs) | Expr::Xor(lhs, rhs) => lhs.reads_state() || rhs.reads_state(),
+            Expr::Mod8(expr) => expr.reads_state(),
+        }
+    }
+
+    pub fn eval(&self, a: u64, b: u64, c: u64) -> u64 {
+        match self {
+            Expr::A => a,
+            Expr::B => b,
+            Expr::C => c,
+            Expr::Const(x) => *x,
+            Expr::Shr(lhs, rhs) => shr(lhs.eval(a, b, c), rhs.eval(a, b, c)),
+            Expr::Xor(lhs, rhs) => lhs.eval(a, b, c) ^ rhs.eval(a, b, c),
+            Expr::Mod8(expr) => expr.eval(a, b, c) % 8,
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::A => write!(f, "A"),
+            Expr::B => write!(f, "B"),
+            Expr::C => write!(f, "C"),
+            Expr::Const(x) => write!(f, "{}", x),
+            Expr::Shr(lhs, rhs) => write!(f, "({} >> {})", lhs, rhs),
+            Expr::Xor(lhs, rhs) => write!(f, "({} ^ {})", lhs, rhs),
+            Expr::Mod8(expr) => write!(f, "({} % 8)", expr),
+        }
+    }
+}
+
+/// What one pass of a supported loop does.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Loop {
+    /// How far A is shifted right per pass.
+    pub shift: u32,
+    /// The values printed per pass, in terms of A at the start of the pass.
+    pub outputs: Vec<Expr>,
+}
+
+fn unsupported(msg: impl fmt::Display) -> AocError {
+    AocError::invalid_state(format!("unsupported program: {}", msg))
+}
+
+/// Symbolically executes the loop body, or explains why the program does not fit the
+/// supported shape.
+pub fn analyze(program: &[u8]) -> aoc::Result<Loop> {
+    if program.len() % 2 == 1 {
+        return Err(unsupported("odd length"));
+    }
+    let Some((body, [3, 0])) = program.split_last_chunk::<2>() else {
+        return Err(unsupported("it does not end in jnz 0"));
+    };
+
+    let (mut a, mut b, mut c) = (Expr::A, Expr::B, Expr::C);
+    let mut shift = 0;
+    let mut outputs = vec![];
+    for (ip, pair) in body.chunks(2).enumerate() {
+        let (opcode, operand) = (pair[0], pair[1]);
+        let at = |msg: &str| {
+            let instruction = asm::disassemble_instruction(opcode, operand)
+                .unwrap_or_else(|_| format!("{} {}", opcode, operand));
+            unsupported(format!("{} at {}: {}", instruction, ip * 2, msg))
+        };
+        let combo = match operand {
+            0..=3 => Expr::Const(operand as u64),
+            4 => a.clone(),
+            5 => b.clone(),
+            6 => c.clone(),
+            _ => return Err(at("reserved combo operand 7")),
+        };
+        match opcode {
+            0 => {
+                let Expr::Const(n) = combo else {
+                    return Err(at("A must be shifted by a constant"));
+                };
+                shift += n as u32;
+                a = Expr::shr(a, combo);
+            }
+            1 => b = Expr::xor(b, Expr::Const(operand as u64)),
+            2 => b = Expr::mod8(combo),
+            3 => return Err(at("the loop can only jump at its end")),
+            4 => b = Expr::xor(b, c.clone()),
+            5 => {
+                let output = Expr::mod8(combo);
+                if output.reads_state() {
+                    return Err(at(&format!(
+                        "the output {} depends on the previous pass",
+                        output
+                    )));
+                }
+                outputs.push(output);
+            }
+            6 => b = Expr::shr(a.clone(), combo),
+            _ => c = Expr::shr(a.clone(), combo),
+        }
+    }
+
+    if shift == 0 {
+        return Err(unsupported("A is never shifted, so the loop never ends"));
+    }
+    if shift > MAX_SHIFT {
+        return Err(unsupported(format!(
+            "A is shifted by {} bits per pass",
+            shift
+        )));
+    }
+    if outputs.is_empty() {
+        return Err(unsupported("the loop prints nothing"));
+    }
+    Ok(Loop { shift, outputs })
+}
+
+/// The smallest initial A for which the program prints `target`.
+pub fn lowest_a(program: &[u8], target: &[u8]) -> aoc::Result<u64> {
+    let shape = analyze(program)?;
+    let per_pass = shape.outputs.len();
+    if target.is_empty() || !target.len().is_multiple_of(per_pass) {
+        return Err(AocError::no_solution(format!(
+            "the program prints {} values per pass, so it can't print {} values",
+            per_pass,
+            target.len()
+        )));
+    }
+
+    // `next` is A at the start of the pass after `pass`, which must not be zero except after
+    // the last pass.
+    fn search(shape: &Loop, target: &[u8], pass: usize, next: u64) -> Option<u64> {
+        if next >> (64 - shape.shift) != 0 {
+            return None;
+        }
+        let per_pass = shape.outputs.len();
+        for low in 0..1 << shape.shift {
+            let a = next << shape.shift | low;
+            if a == 0 && pass > 0 {
+                continue;
+            }
+            let matches = shape
+                .outputs
+                .iter()
+                .zip(&target[pass * per_pass..])
+                .all(|(output, &expected)| output.eval(a, 0, 0) == expected as u64);
+            if !matches {
+                continue;
+            }
+            if pass == 0 {
+                return Some(a);
+            }
+            if let Some(a) = search(shape, target, pass - 1, a) {
+                return Some(a);
+            }
+        }
+        None
+    }
+
+    search(&shape, target, target.len() / per_pass - 1, 0).ok_or_else(|| {
+        AocError::no_solution(format!(
+            "no value of A makes the program print {}",
+            target
+                .iter()
+                .map(|n| n.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, Computer};
+    use aoc::testing::Rng;
+    use std::fs::read_to_string;
+
+    #[test]
+    fn test_analyze() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let (_, program) = parse_input(&data).unwrap();
+        let shape = analyze(&program).unwrap();
+        assert_eq!(shape.shift, 3);
+        assert_eq!(
+            shape
+                .outputs
+                .iter()
+                .map(|expr| expr.to_string())
+                .collect::<Vec<_>>(),
+            ["((((A % 8) ^ 3) ^ (A >> ((A % 8) ^ 5))) % 8)"]
+        );
+
+        let error = |program: &[u8]| analyze(program).unwrap_err().to_string();
+        assert!(error(&[5, 5, 0, 3, 3, 0]).contains("out B%8 at 0: the output (B % 8) depends"));
+        assert!(error(&[0, 4, 5, 4, 3, 0]).contains("adv A>>A at 0"));
+        assert!(error(&[0, 3, 3, 0, 5, 4, 3, 0]).contains("jump at its end"));
+        assert!(error(&[0, 3, 5, 4]).contains("does not end in jnz 0"));
+        assert!(error(&[5, 4, 3, 0]).contains("never shifted"));
+        assert!(error(&[0, 3, 3, 0]).contains("prints nothing"));
+    }
+
+    #[test]
+    fn test_lowest_a() {
+        let program = [0, 3, 5, 4, 3, 0];
+        assert_eq!(lowest_a(&program, &program).unwrap(), 117440);
+
+        // The output only depends on bits 1 to 3 of each pass, so bit 0 of 729 can be cleared.
+        let program = [0, 1, 5, 4, 3, 0];
+        assert_eq!(
+            lowest_a(&program, &[4, 6, 3, 5, 6, 3, 5, 2, 1, 0]).unwrap(),
+            728
+        );
+        assert!(lowest_a(&program, &[1, 1]).is_err());
+
+        // Two outputs per pass.
+        let program = [5, 4, 2, 4, 1, 3, 5, 5, 0, 2, 3, 0];
+        assert!(lowest_a(&program, &[5, 6, 3]).is_err());
+        assert!(lowest_a(&program, &[1, 2, 3, 0]).is_err());
+        let a = lowest_a(&program, &[5, 6, 3, 0]).unwrap();
+        assert_eq!(a, 13);
+        assert_eq!(Computer::new(a, 0, 0).run(&program).unwrap(), [5, 6, 3, 0]);
+    }
+
+    #[test]
+    fn test_random_targets() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let (_, program) = parse_input(&data).unwrap();
+        let mut rng = Rng::from_env();
+        for _ in 0..100 {
+            let a = rng.next_u64() >> rng.usize(0, 63);
+            let target = Computer::new(a, 0, 0).run(&program).unwrap();
+            let lowest = lowest_a(&program, &target).unwrap();
+            assert!(
+                lowest <= a,
+                "A={} gives {:?} but found {}",
+                a,
+                target,
+                lowest
+            );
+            assert_eq!(Computer::new(lowest, 0, 0).run(&program).unwrap(), target);
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use aoc::{AocError, Solution};
+use mygrid::{Grid, Position};
+use pathfinding::directed::astar;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct Cell {
+    t: Option<usize>,
+}
+
+impl ToString for Cell {
+    fn to_string(&self) -> String {
+        match self.t {
+            None => ".".to_string(),
+            Some(_) => "#".to_string(),
+        }
+    }
+}
+
+pub struct Maze {
+    grid: Grid<Cell>,
+    start: Position,
+    end: Position,
+}
+
+impl Maze {
+    pub fn from(x_size: usize, y_size: usize, data: &str) -> aoc::Result<Self> {
+        let mut grid = Grid::<Cell>::new(x_size, y_size);
+
+        let start = Position::new(0, 0);
+        let end = Position::new(x_size as i32 - 1, y_size as i32 - 1);
+
+        for (t, line) in aoc::lines(data).enumerate() {
+            let (x, y) = line.split_once(",")?;
+            let pos = Position::new(line.parse(x)?, line.parse(y)?);
+            let Some(cell) = grid.at_mut(&pos) else {
+                return Err(line.error(line.text, "byte falls outside the memory space"));
+            };
+            *cell = Cell { t: Some(t) };
+        }
+
+        Ok(Self { grid, start, end })
+    }
+
+    pub fn with_max_t(&self, max_t: usize) -> Self {
+        let grid = Grid::<Cell>::from_iter(
+            self.grid.x_size,
+            self.grid.y_size,
+            self.grid.iter().map(|c| Cell {
+                t: match c.t {
+                    None => None,
+                    Some(t) => {
+                        if t < max_t {
+                            Some(t)
+                        } else {
+                            None
+                        }
+                    }
+                },
+            }),
+        );
+
+        Self {
+            grid,
+            start: self.start,
+            end: self.end,
+        }
+    }
+
+    pub fn successors(&self, pos: &Position, max_t: usize) -> Vec<(Position, usize)> {
+        assert!(self.grid[pos].t.map_or(true, |t| t >= max_t));
+        let s = mygrid::CARDINAL_DIRECTIONS
+            .iter()
+            .filter(|&dir| {
+                self.grid
+                    .at(&pos.step(dir))
+                    .is_some_and(|v| v.t.map_or(true, |t| t >= max_t))
+            })
+            .map(|dir| (pos.step(&dir), 1))
+            .collect();
+        s
+    }
+
+    pub fn heuristic(&self, pos: &Position) -> usize {
+        (self.end.x - pos.x).abs() as usize + (self.end.y - pos.y).abs() as usize
+    }
+
+    pub fn success(&self, pos: &Position) -> bool {
+        pos == &self.end
+    }
+
+    pub fn minimum_steps(&self, max_t: usize) -> Option<usize> {
+        //println!("{}", self.with_max_t(max_t).grid.to_string());
+        let (_, cost) = astar::astar(
+            &self.start,
+            |n| self.successors(n, max_t),
+            |n| self.heuristic(n),
+            |n| self.success(n),
+        )?;
+
+        Some(cost)
+    }
+
+    pub fn first_blocking_byte(&self) -> aoc::Result<Position> {
+        let max_t = self.grid.iter().filter_map(|c| c.t).max().unwrap_or(0);
+        for t in 1..=max_t + 1 {
+            if self.minimum_steps(t).is_none() {
+                if let Some(pos) = self
+                    .grid
+                    .iter_positions()
+                    .find(|pos| self.grid[pos].t == Some(t - 1))
+                {
+                    return Ok(pos);
+                }
+            }
+        }
+        Err(AocError::no_solution("no byte blocks the exit"))
+    }
+}
+
+pub fn parse_input(x_size: usize, y_size: usize, data: &str) -> aoc::Result<Maze> {
+    Maze::from(x_size, y_size, data)
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    fn part1(&self, input: &str, params: &aoc::Params) -> aoc::Result<String> {
+        let size = params.get("size", 71)?;
+        let maze = parse_input(size, size, input)?;
+        let bytes = params.get("bytes", 1024)?;
+        let minimum_steps = maze.minimum_steps(bytes).ok_or_else(|| {
+            AocError::no_solution(format!("exit is unreachable after {} bytes", bytes))
+        })?;
+        Ok(minimum_steps.to_string())
+    }
+
+    fn part2(&self, input: &str, params: &aoc::Params) -> aoc::Result<String> {
+        let size = params.get("size", 71)?;
+        let maze = parse_input(size, size, input)?;
+        Ok(maze.first_blocking_byte()?.to_string())
+    }
+}
+
+aoc::example_tests!(Day18);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::read_to_string;
+
+    #[test]
+    fn answer_part1() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let maze: Maze = parse_input(71, 71, &data).unwrap();
+        assert_eq!(maze.minimum_steps(1024), Some(416));
+    }
+
+    #[test]
+    fn answer_part2() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let maze: Maze = parse_input(71, 71, &data).unwrap();
+        assert_eq!(maze.first_blocking_byte().unwrap(), Position::new(50, 23));
+    }
+}
@@ -0,0 +1,106 @@
+pub mod trie;
+
+use aoc::{AocError, Solution};
+use trie::Towels;
+
+pub fn parse_input(data: &str) -> aoc::Result<(Vec<String>, Vec<String>)> {
+    let mut lines = aoc::lines(data);
+
+    let Some(first) = lines.next() else {
+        return Err(AocError::parse(1, 1, "expected available towel patterns"));
+    };
+    let available = first
+        .text
+        .split(", ")
+        .map(|pattern| {
+            if pattern.is_empty() {
+                Err(first.error(pattern, "empty towel pattern"))
+            } else {
+                Ok(String::from(pattern))
+            }
+        })
+        .collect::<aoc::Result<_>>()?;
+
+    if let Some(line) = lines.next() {
+        if !line.text.is_empty() {
+            return Err(line.error(line.text, "expected a blank line"));
+        }
+    }
+
+    let designs = lines.map(|line| String::from(line.text)).collect();
+
+    Ok((available, designs))
+}
+
+pub fn possible_designs(available: &[String], designs: &[String]) -> usize {
+    let towels = Towels::new(available);
+    designs
+        .iter()
+        .filter(|&design| towels.count(design) > 0)
+        .count()
+}
+
+/// The arrangements of all the designs, or an error if there are `usize::MAX` or more, where
+/// the counts saturate.
+pub fn count_total_arrangements(available: &[String], designs: &[String]) -> aoc::Result<usize> {
+    let towels = Towels::new(available);
+    designs.iter().try_fold(0usize, |sum, design| {
+        sum.checked_add(towels.count(design))
+            .filter(|&sum| sum < usize::MAX)
+            .ok_or_else(|| AocError::invalid_state(format!("at least {} arrangements", usize::MAX)))
+    })
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    fn part1(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        let (available, designs) = parse_input(input)?;
+        Ok(possible_designs(&available, &designs).to_string())
+    }
+
+    fn part2(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        let (available, designs) = parse_input(input)?;
+        Ok(count_total_arrangements(&available, &designs)?.to_string())
+    }
+}
+
+aoc::example_tests!(Day19);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::read_to_string;
+
+    #[test]
+    fn test_too_many_arrangements() {
+        let available = ["a".to_owned(), "aa".to_owned()];
+        let designs = ["a".repeat(200)];
+        assert!(count_total_arrangements(&available, &designs).is_err());
+        let input = format!("a, aa\n\n{}\n", "a".repeat(200));
+        assert!(Day19.part2(&input, &aoc::Params::new()).is_err());
+        // Runs of 90 have a Fibonacci number of arrangements each, and two of them still fit.
+        let designs = ["a".repeat(90), "a".repeat(90)];
+        assert_eq!(
+            count_total_arrangements(&available, &designs).unwrap(),
+            2 * 4660046610375530309
+        );
+    }
+
+    #[test]
+    fn answer_part1() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let (available, designs) = parse_input(&data).unwrap();
+        assert_eq!(possible_designs(&available, &designs), 216);
+    }
+
+    #[test]
+    fn answer_part2() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let (available, designs) = parse_input(&data).unwrap();
+        assert_eq!(
+            count_total_arrangements(&available, &designs).unwrap(),
+            603191454138773
+        );
+    }
+}
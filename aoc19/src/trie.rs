@@ -0,0 +1,228 @@
+//! Matching towels against designs with a trie.
+//!
+//! The towels share one trie, so every towel starting at a given offset of a design is found
+//! in a single walk down it.  Counting works back from the end of the design: the ways to
+//! arrange the bytes from an offset on are the sum over the towels matching there of the ways
+//! from where each one ends.  Nothing is allocated per suffix, and the same table tells which
+//! towels lead to a full arrangement, so arrangements can be listed without dead ends.
+
+#[derive(Clone, Debug, Default)]
+struct Node {
+    children: Vec<(u8, usize)>,
+    /// The towels ending here, more than one if the same towel is listed twice.
+    towels: Vec<usize>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Towels {
+    towels: Vec<String>,
+    nodes: Vec<Node>,
+}
+
+impl Towels {
+    pub fn new(available: &[String]) -> Self {
+        let mut nodes = vec![Node::default()];
+        for (i, towel) in available.iter().enumerate() {
+            let mut node = 0;
+            for &byte in towel.as_bytes() {
+                node = match nodes[node].children.iter().find(|&&(b, _)| b == byte) {
+                    Some(&(_, child)) => child,
+                    None => {
+                        nodes.push(Node::default());
+                        let child = nodes.len() - 1;
+                        nodes[node].children.push((byte, child));
+                        child
+                    }
+                };
+            }
+            nodes[node].towels.push(i);
+        }
+        Self {
+            towels: available.to_vec(),
+            nodes,
+        }
+    }
+
+    pub fn towel(&self, i: usize) -> &str {
+        &self.towels[i]
+    }
+
+    /// The towels matching `design` at `start`, shortest first, as (length, towel).
+    pub fn matches_at<'a>(
+        &'a self,
+        design: &'a [u8],
+        start: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let mut node = Some(0);
+        design[start..]
+            .iter()
+            .enumerate()
+            .map_while(move |(i, &byte)| {
+                let children = &self.nodes[node?].children;
+                let child = children.iter().find(|&&(b, _)| b == byte)?.1;
+                node = Some(child);
+                Some(
+                    self.nodes[child]
+                        .towels
+                        .iter()
+                        .map(move |&towel| (i + 1, towel)),
+                )
+            })
+            .flatten()
+    }
+
+    // The number of arrangements of the design from each offset on, saturating.
+    fn ways(&self, design: &[u8]) -> Vec<usize> {
+        let mut ways = vec![0; design.len() + 1];
+        ways[design.len()] = 1;
+        for start in (0..design.len()).rev() {
+            ways[start] = self
+                .matches_at(design, start)
+                .fold(0usize, |sum, (len, _)| {
+                    sum.saturating_add(ways[start + len])
+                });
+        }
+        ways
+    }
+
+    pub fn count(&self, design: &str) -> usize {
+        self.ways(design.as_bytes())[0]
+    }
+
+    /// One arrangement of the design, taking the shortest towel that still leads to one at
+    /// every step.
+    pub fn arrangement(&self, design: &str) -> Option<Vec<&str>> {
+        self.arrangements(design).next()
+    }
+
+    /// Every arrangement of the design, produced one at a time in the order of
+    /// [`Towels::arrangement`].
+    pub fn arrangements(&self, design: &str) -> Arrangements<'_> {
+        let design = design.as_bytes();
+        let ways = self.ways(design);
+        let options = (0..design.len())
+            .map(|start| {
+                self.matches_at(design, start)
+                    .filter(|&(len, _)| ways[start + len] > 0)
+                    .collect()
+            })
+            .collect();
+        let mut arrangements = Arrangements {
+            towels: self,
+            options,
+            stack: vec![],
+            done: ways[0] == 0,
+        };
+        arrangements.descend(0);
+        arrangements
+    }
+}
+
+/// Arrangements as a depth-first walk over the towels that can still finish the design.
+pub struct Arrangements<'a> {
+    towels: &'a Towels,
+    /// The usable (length, towel) choices at each offset.
+    options: Vec<Vec<(usize, usize)>>,
+    /// The offset and choice of each towel in the current arrangement.
+    stack: Vec<(usize, usize)>,
+    done: bool,
+}
+
+impl Arrangements<'_> {
+    // Takes the first choice from `start` to the end of the design.
+    fn descend(&mut self, start: usize) {
+        let mut pos = start;
+        while pos < self.options.len() && !self.done {
+            self.stack.push((pos, 0));
+            pos += self.options[pos][0].0;
+        }
+    }
+}
+
+impl<'a> Iterator for Arrangements<'a> {
+    type Item = Vec<&'a str>;
+
+    fn next(&mut self) -> Option<Vec<&'a str>> {
+        if self.done {
+            return None;
+        }
+        let arrangement = self
+            .stack
+            .iter()
+            .map(|&(pos, choice)| self.towels.towel(self.options[pos][choice].1))
+            .collect();
+
+        // Move on to the next choice at the deepest offset that has one.
+        self.done = true;
+        while let Some((pos, choice)) = self.stack.pop() {
+            if choice + 1 < self.options[pos].len() {
+                self.stack.push((pos, choice + 1));
+                self.done = false;
+                self.descend(pos + self.options[pos][choice + 1].0);
+                break;
+            }
+        }
+        Some(arrangement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+    use std::fs::read_to_string;
+
+    #[test]
+    fn test_towels() {
+        let data = read_to_string("../examples/19/test.txt").unwrap();
+        let (available, designs) = parse_input(&data).unwrap();
+        let towels = Towels::new(&available);
+        let counts = designs
+            .iter()
+            .map(|design| towels.count(design))
+            .collect::<Vec<_>>();
+        assert_eq!(counts, [2, 1, 4, 6, 0, 1, 2, 0]);
+        for (design, &count) in designs.iter().zip(&counts) {
+            let all = towels.arrangements(design).collect::<Vec<_>>();
+            assert_eq!(all.len(), count);
+            assert!(all.iter().all(|towels| towels.concat() == *design));
+            assert_eq!(towels.arrangement(design), all.first().cloned());
+        }
+        assert_eq!(towels.arrangement("brwrr").unwrap(), ["b", "r", "wr", "r"]);
+        assert_eq!(
+            towels.arrangements("gbbr").collect::<Vec<_>>(),
+            [
+                vec!["g", "b", "b", "r"],
+                vec!["g", "b", "br"],
+                vec!["gb", "b", "r"],
+                vec!["gb", "br"]
+            ]
+        );
+        assert_eq!(towels.count(""), 1);
+        assert_eq!(
+            towels.arrangements("").collect::<Vec<_>>(),
+            [Vec::<&str>::new()]
+        );
+
+        // A towel listed twice counts twice, as each copy can be used.
+        let towels = Towels::new(&["r".to_owned(), "b".to_owned(), "r".to_owned()]);
+        assert_eq!(towels.count("rbr"), 4);
+        assert_eq!(towels.arrangements("rbr").count(), 4);
+    }
+
+    #[test]
+    fn test_lazy_arrangements() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let (available, designs) = parse_input(&data).unwrap();
+        let towels = Towels::new(&available);
+        let design = designs
+            .iter()
+            .max_by_key(|design| towels.count(design))
+            .unwrap();
+        assert!(towels.count(design) > 1_000_000_000);
+        let first = towels.arrangements(design).take(1000).collect::<Vec<_>>();
+        assert_eq!(first.len(), 1000);
+        assert!(first.iter().all(|towels| towels.concat() == *design));
+        assert!(first.windows(2).all(|w| w[0] != w[1]));
+    }
+}
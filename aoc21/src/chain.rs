@@ -0,0 +1,338 @@
+//! Chains of keypads, each one's arm driven by pressing keys on the next.
+//!
+//! The first pad is the one the code is typed on, every later pad moves the arm of the one
+//! before it, and the human presses the keys of the last pad directly.  Costs are worked out
+//! from the human's end.  Pressing a key of the last pad takes one press.  Moving the arm of
+//! any other pad between two keys and pressing the second one takes the cheapest way of
+//! driving it from the pad after it.  That is found with Dijkstra's algorithm over where the
+//! arm points and which key the controlling arm is on, so any layout works, detours included.
+//! Costs saturate at `u64::MAX` for very long chains.  The search also keeps one cheapest way
+//! for every move, so a shortest sequence of the human's presses can be rebuilt key by key.
+
+use crate::keypad::{Keypad, Location, MOVES};
+use aoc::AocError;
+use itertools::Itertools;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+/// The presses needed to move an arm from one key to another and press it.
+pub type CostMap = HashMap<(char, char), u64>;
+
+/// The keys to press on the next keypad to move an arm from one key to another and press it.
+pub type MoveMap = HashMap<(char, char), String>;
+
+pub struct Chain {
+    pads: Vec<Keypad>,
+    costs: Vec<CostMap>,
+    /// One cheapest way for each keypad but the human's.
+    moves: Vec<MoveMap>,
+}
+
+impl Chain {
+    pub fn new(pads: Vec<Keypad>) -> aoc::Result<Self> {
+        let Some(human) = pads.last() else {
+            return Err(AocError::invalid_state("a chain needs at least one keypad"));
+        };
+        if let Some(i) = (1..pads.len()).find(|&i| !pads[i].is_directional()) {
+            return Err(AocError::invalid_state(format!(
+                "keypad {} controls another one but has no direction keys",
+                i
+            )));
+        }
+
+        let mut costs = vec![CostMap::new(); pads.len()];
+        let mut moves = vec![MoveMap::new(); pads.len() - 1];
+        costs[pads.len() - 1] = human
+            .keys()
+            .iter()
+            .flat_map(|&from| human.keys().iter().map(move |&to| ((from, to), 1)))
+            .collect();
+        for i in (0..pads.len() - 1).rev() {
+            (costs[i], moves[i]) = pad_costs(&pads[i], &pads[i + 1], &costs[i + 1])
+                .map_err(|err| AocError::invalid_state(format!("keypad {}: {}", i, err)))?;
+        }
+        Ok(Self { pads, costs, moves })
+    }
+
+    /// The door's numeric keypad, `robots` directional keypads used by robots, and the
+    /// directional keypad the human uses.
+    pub fn standard(robots: usize) -> Self {
+        let mut pads = vec![Keypad::numeric()];
+        pads.extend((0..=robots).map(|_| Keypad::directional()));
+        Self::new(pads).unwrap()
+    }
+
+    pub fn pads(&self) -> &[Keypad] {
+        &self.pads
+    }
+
+    /// The cost table of keypad `i`.
+    pub fn costs(&self, i: usize) -> &CostMap {
+        &self.costs[i]
+    }
+
+    /// The move table of keypad `i`, which is driven by keypad `i + 1`.
+    pub fn moves(&self, i: usize) -> &MoveMap {
+        &self.moves[i]
+    }
+
+    fn check_code(&self, code: &str) -> aoc::Result<()> {
+        match code
+            .chars()
+            .find(|&key| self.pads[0].location(key).is_none())
+        {
+            Some(key) => Err(AocError::invalid_state(format!(
+                "no key {:?} on the first keypad",
+                key
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// How many keys the human presses to type `code` on the first keypad.
+    pub fn presses(&self, code: &str) -> aoc::Result<u64> {
+        self.check_code(code)?;
+        Ok(("A".to_owned() + code)
+            .chars()
+            .tuple_windows()
+            .fold(0u64, |total, pair| {
+                total.saturating_add(self.costs[0][&pair])
+            }))
+    }
+
+    /// One shortest sequence of keys for the human to press to type `code`, produced as it is
+    /// needed so even sequences too long to store can be walked.
+    pub fn sequence(&self, code: &str) -> aoc::Result<Sequence<'_>> {
+        self.check_code(code)?;
+        Ok(Sequence {
+            chain: self,
+            stack: vec![Frame {
+                pad: 0,
+                keys: code.chars().collect(),
+                pos: 0,
+                prev: 'A',
+            }],
+        })
+    }
+}
+
+// Keys still to press on one keypad, and the key its arm is on.
+struct Frame {
+    pad: usize,
+    keys: Vec<char>,
+    pos: usize,
+    prev: char,
+}
+
+/// The human's presses, expanded one keypad at a time from the code down.
+pub struct Sequence<'a> {
+    chain: &'a Chain,
+    stack: Vec<Frame>,
+}
+
+impl Iterator for Sequence<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let human = self.chain.pads.len() - 1;
+        loop {
+            let frame = self.stack.last_mut()?;
+            let Some(&key) = frame.keys.get(frame.pos) else {
+                self.stack.pop();
+                continue;
+            };
+            frame.pos += 1;
+            if frame.pad == human {
+                return Some(key);
+            }
+            let prev = std::mem::replace(&mut frame.prev, key);
+            let pad = frame.pad;
+            self.stack.push(Frame {
+                pad: pad + 1,
+                keys: self.chain.moves[pad][&(prev, key)].chars().collect(),
+                pos: 0,
+                prev: 'A',
+            });
+        }
+    }
+}
+
+fn pad_costs(
+    pad: &Keypad,
+    controller: &Keypad,
+    above: &CostMap,
+) -> Result<(CostMap, MoveMap), String> {
+    let mut costs = CostMap::new();
+    let mut moves = MoveMap::new();
+    for &from in pad.keys() {
+        let start = (pad.button_location(from), 'A');
+        let mut dist = HashMap::from([(start, 0u64)]);
+        let mut prev = HashMap::new();
+        let mut heap = BinaryHeap::from([Reverse((0, start.0.x, start.0.y, 'A'))]);
+        while let Some(Reverse((cost, x, y, held))) = heap.pop() {
+            let loc = Location { x, y };
+            if dist[&(loc, held)] < cost {
+                continue;
+            }
+            for &(key, dx, dy) in &MOVES {
+                let next = loc.step(dx, dy);
+                if pad.key_at(next).is_none() {
+                    continue;
+                }
+                let cost = cost.saturating_add(above[&(held, key)]);
+                if dist.get(&(next, key)).is_none_or(|&d| cost < d) {
+                    dist.insert((next, key), cost);
+                    prev.insert((next, key), (loc, held));
+                    heap.push(Reverse((cost, next.x, next.y, key)));
+                }
+            }
+        }
+
+        for &to in pad.keys() {
+            let loc = pad.button_location(to);
+            let best = controller
+                .keys()
+                .iter()
+                .filter_map(|&held| {
+                    let cost = dist.get(&(loc, held))?;
+                    Some((cost.saturating_add(above[&(held, 'A')]), held))
+                })
+                .min();
+            let Some((cost, held)) = best else {
+                return Err(format!("can't move from {:?} to {:?}", from, to));
+            };
+
+            // Each state was reached by pressing the direction key the controller holds.
+            let mut keys = vec!['A'];
+            let mut state = (loc, held);
+            while state != start {
+                keys.push(state.1);
+                state = prev[&state];
+            }
+            keys.reverse();
+            costs.insert((from, to), cost);
+            moves.insert((from, to), keys.into_iter().collect());
+        }
+    }
+    Ok((costs, moves))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keypad::{DIRECTIONAL, NUMERIC};
+    use crate::sim::{simulate, Arms};
+    use aoc::testing::Rng;
+    use std::collections::{HashSet, VecDeque};
+
+    // The fewest presses found by searching every state of the chain's arms.
+    fn brute_force(pads: &[Keypad], code: &str) -> Option<u64> {
+        let code = code.chars().collect::<Vec<_>>();
+        let start = (Arms::new(pads), 0);
+        let mut seen = HashSet::from([(start.0.keys().to_vec(), 0)]);
+        let mut queue = VecDeque::from([(start, 0)]);
+        while let Some(((arms, typed), presses)) = queue.pop_front() {
+            if typed == code.len() {
+                return Some(presses);
+            }
+            for &pressed in pads.last().unwrap().keys() {
+                let mut arms = arms.clone();
+                let typed = match arms.press(pressed) {
+                    Ok(None) => typed,
+                    Ok(Some(key)) if key == code[typed] => typed + 1,
+                    _ => continue,
+                };
+                if seen.insert((arms.keys().to_vec(), typed)) {
+                    queue.push_back(((arms, typed), presses + 1));
+                }
+            }
+        }
+        None
+    }
+
+    // Checks that the reconstructed sequence is as long as the cost says and types the code.
+    fn check_sequence(chain: &Chain, code: &str) {
+        let presses = chain.sequence(code).unwrap().collect::<String>();
+        assert_eq!(presses.len() as u64, chain.presses(code).unwrap());
+        assert_eq!(simulate(chain.pads(), presses.chars()).unwrap(), code);
+    }
+
+    #[test]
+    fn test_standard() {
+        let chain = Chain::standard(2);
+        let presses =
+            ["029A", "980A", "179A", "456A", "379A"].map(|code| chain.presses(code).unwrap());
+        assert_eq!(presses, [68, 60, 68, 64, 64]);
+        assert_eq!(brute_force(chain.pads(), "029A"), Some(68));
+        assert!(chain.presses("12B").is_err());
+        assert_eq!(Chain::standard(200).presses("029A").unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn test_sequence() {
+        let chain = Chain::standard(2);
+        for code in ["029A", "980A", "179A", "456A", "379A", ""] {
+            check_sequence(&chain, code);
+        }
+        assert_eq!(chain.moves(0)[&('A', '0')], "<A");
+        assert!(chain.sequence("12B").is_err());
+        check_sequence(&Chain::standard(10), "029A");
+
+        // Far too long to store, but a prefix can still be replayed.
+        let chain = Chain::standard(25);
+        let prefix = chain.sequence("029A").unwrap().take(100_000);
+        assert!("029A".starts_with(&simulate(chain.pads(), prefix).unwrap()));
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = |pads: Vec<Keypad>| Chain::new(pads).err().unwrap().to_string();
+        assert!(err(vec![]).contains("at least one keypad"));
+        assert!(err(vec![Keypad::directional(), Keypad::numeric()]).contains("no direction keys"));
+        let split = Keypad::parse("A.1").unwrap();
+        assert!(err(vec![split, Keypad::directional()]).contains("can't move from 'A' to '1'"));
+    }
+
+    #[test]
+    fn test_any_layout() {
+        let first = [
+            NUMERIC,
+            "A1.\n.23",
+            "A.1\n234",
+            "12A\n3.4\n567",
+            DIRECTIONAL,
+        ];
+        let controllers = [
+            DIRECTIONAL,
+            "<^>\n.vA",
+            "A^.\n<v>",
+            "^.A\n<v>",
+            "v^<>A",
+            ">\n<A\n.^v",
+        ];
+        let mut rng = Rng::from_env();
+        for _ in 0..40 {
+            let mut pads = vec![Keypad::parse(rng.choose::<&str>(&first)).unwrap()];
+            for _ in 0..rng.usize(1, 3) {
+                pads.push(Keypad::parse(rng.choose::<&str>(&controllers)).unwrap());
+            }
+            let keys = pads[0].keys();
+            let code = (0..rng.usize(1, 4))
+                .map(|_| *rng.choose(keys))
+                .collect::<String>();
+            let chain = Chain::new(pads.clone()).unwrap();
+            check_sequence(&chain, &code);
+            assert_eq!(
+                Some(chain.presses(&code).unwrap()),
+                brute_force(&pads, &code),
+                "{:?} on {:?}",
+                code,
+                pads.iter()
+                    .map(|pad| pad.keys().iter().collect::<String>())
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+}
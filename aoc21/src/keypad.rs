@@ -0,0 +1,190 @@
+//! Keypads with any layout.
+//!
+//! A layout is written as rows of keys, with `.` or a space marking a gap the robot arm must
+//! never point at.  Rows may have different lengths, and the missing keys are gaps too.  Every
+//! arm starts at `A`.
+
+use aoc::AocError;
+use std::collections::{HashMap, VecDeque};
+
+pub const NUMERIC: &str = "789\n456\n123\n.0A";
+pub const DIRECTIONAL: &str = ".^A\n<v>";
+
+/// The direction keys and how far each one moves an arm.
+pub const MOVES: [(char, i32, i32); 4] = [('^', 0, -1), ('v', 0, 1), ('<', -1, 0), ('>', 1, 0)];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Location {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Location {
+    pub fn step(self, dx: i32, dy: i32) -> Self {
+        Self {
+            x: self.x + dx,
+            y: self.y + dy,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Keypad {
+    /// The keys in reading order.
+    keys: Vec<char>,
+    buttons: HashMap<char, Location>,
+    rows: Vec<Vec<Option<char>>>,
+}
+
+impl Keypad {
+    pub fn parse(layout: &str) -> aoc::Result<Self> {
+        let mut keys = vec![];
+        let mut buttons = HashMap::new();
+        let mut rows = vec![];
+        for line in aoc::lines(layout) {
+            let mut row = vec![];
+            for (x, (i, key)) in line.text.char_indices().enumerate() {
+                if key == '.' || key == ' ' {
+                    row.push(None);
+                    continue;
+                }
+                let loc = Location {
+                    x: x as i32,
+                    y: rows.len() as i32,
+                };
+                if buttons.insert(key, loc).is_some() {
+                    return Err(line.error(&line.text[i..], format!("key {:?} appears twice", key)));
+                }
+                keys.push(key);
+                row.push(Some(key));
+            }
+            rows.push(row);
+        }
+        if !buttons.contains_key(&'A') {
+            return Err(AocError::invalid_state("keypad has no A key to start on"));
+        }
+        Ok(Self {
+            keys,
+            buttons,
+            rows,
+        })
+    }
+
+    pub fn numeric() -> Self {
+        Self::parse(NUMERIC).unwrap()
+    }
+
+    pub fn directional() -> Self {
+        Self::parse(DIRECTIONAL).unwrap()
+    }
+
+    pub fn keys(&self) -> &[char] {
+        &self.keys
+    }
+
+    pub fn location(&self, key: char) -> Option<Location> {
+        self.buttons.get(&key).copied()
+    }
+
+    pub fn button_location(&self, key: char) -> Location {
+        self.buttons[&key]
+    }
+
+    pub fn key_at(&self, loc: Location) -> Option<char> {
+        let row = self.rows.get(usize::try_from(loc.y).ok()?)?;
+        *row.get(usize::try_from(loc.x).ok()?)?
+    }
+
+    /// Whether the keypad can control another one.
+    pub fn is_directional(&self) -> bool {
+        MOVES
+            .iter()
+            .all(|&(key, _, _)| self.buttons.contains_key(&key))
+    }
+
+    /// Every shortest way to move the arm from `from` to `to` around the gaps, followed by
+    /// `A`.  Only the moves with the fewest turns are kept, as turning costs presses further up
+    /// the chain.
+    pub fn sequences(&self, from: Location, to: Location) -> Vec<String> {
+        // Distances to `to`, so the search below only takes steps that get closer.
+        let mut dist = HashMap::from([(to, 0)]);
+        let mut queue = VecDeque::from([to]);
+        while let Some(loc) = queue.pop_front() {
+            for &(_, dx, dy) in &MOVES {
+                let next = loc.step(dx, dy);
+                if self.key_at(next).is_some() && !dist.contains_key(&next) {
+                    dist.insert(next, dist[&loc] + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        let mut paths = vec![];
+        let mut todo = vec![(from, String::new())];
+        while let Some((loc, path)) = todo.pop() {
+            if loc == to {
+                paths.push(path + "A");
+                continue;
+            }
+            for &(key, dx, dy) in &MOVES {
+                let next = loc.step(dx, dy);
+                if dist.get(&next).is_some_and(|&d| d + 1 == dist[&loc]) {
+                    todo.push((next, format!("{}{}", path, key)));
+                }
+            }
+        }
+
+        let turns = |path: &str| path.as_bytes().windows(2).filter(|w| w[0] != w[1]).count();
+        let fewest = paths.iter().map(|path| turns(path)).min().unwrap_or(0);
+        paths.retain(|path| turns(path) == fewest);
+        paths.sort();
+        paths
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let numpad = Keypad::numeric();
+        assert_eq!(numpad.keys().len(), 11);
+        assert_eq!(numpad.key_at(Location { x: 0, y: 3 }), None);
+        assert_eq!(numpad.key_at(Location { x: 1, y: 3 }), Some('0'));
+        assert!(!numpad.is_directional());
+        assert!(Keypad::directional().is_directional());
+
+        let ragged = Keypad::parse("12\n 3A4").unwrap();
+        assert_eq!(ragged.key_at(Location { x: 2, y: 0 }), None);
+        assert_eq!(ragged.location('4'), Some(Location { x: 3, y: 1 }));
+
+        let err = Keypad::parse("A1\n1.").unwrap_err().to_string();
+        assert!(
+            err.contains("line 2, column 1: key '1' appears twice"),
+            "{}",
+            err
+        );
+        assert!(Keypad::parse("123").is_err());
+    }
+
+    #[test]
+    fn test_sequences() {
+        let numpad = Keypad::numeric();
+        let at = |key| numpad.button_location(key);
+        assert_eq!(numpad.sequences(at('A'), at('0')), ["<A"]);
+        assert_eq!(numpad.sequences(at('A'), at('1')), ["^<<A"]);
+        assert_eq!(numpad.sequences(at('1'), at('A')), [">>vA"]);
+        assert_eq!(numpad.sequences(at('2'), at('6')), [">^A", "^>A"]);
+        assert_eq!(numpad.sequences(at('5'), at('5')), ["A"]);
+
+        // Gaps in the middle leave a zigzag as the only shortest way.
+        let pad = Keypad::parse("A1.\n.23").unwrap();
+        let at = |key| pad.button_location(key);
+        assert_eq!(pad.sequences(at('A'), at('3')), [">v>A"]);
+        // Around a gap, which takes longer than the distance.
+        let pad = Keypad::parse("A.1\n234").unwrap();
+        let at = |key| pad.button_location(key);
+        assert_eq!(pad.sequences(at('A'), at('1')), ["v>>^A"]);
+    }
+}
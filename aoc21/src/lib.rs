@@ -0,0 +1,128 @@
+pub mod chain;
+pub mod keypad;
+pub mod sim;
+
+use aoc::{AocError, Solution};
+use chain::Chain;
+
+pub fn numeric_part(target: &str) -> u64 {
+    target[..target.len() - 1].parse().unwrap()
+}
+
+/// The sum over the codes of the presses times the numeric part, or an error if it doesn't
+/// fit in a u64.  A chain whose presses saturate at `u64::MAX` counts as too long as well.
+pub fn sum_of_complexities(targets: &[String], chain: &Chain) -> aoc::Result<u64> {
+    targets.iter().try_fold(0u64, |sum, target| {
+        Some(chain.presses(target)?)
+            .filter(|&presses| presses < u64::MAX)
+            .and_then(|presses| presses.checked_mul(numeric_part(target)))
+            .and_then(|complexity| sum.checked_add(complexity))
+            .ok_or_else(|| {
+                AocError::invalid_state(format!("complexity of {} overflows a u64", target))
+            })
+    })
+}
+
+pub fn parse_input(data: &str) -> aoc::Result<Vec<String>> {
+    aoc::lines(data)
+        .map(|line| {
+            let Some(digits) = line.text.strip_suffix('A') else {
+                return Err(line.error(line.end(), "expected code to end with 'A'"));
+            };
+            if let Some(i) = digits.find(|c: char| !c.is_ascii_digit()) {
+                return Err(line.error(&digits[i..], "expected a digit"));
+            }
+            line.parse::<u64>(digits)?;
+            Ok(String::from(line.text))
+        })
+        .collect()
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    fn part1(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        Ok(sum_of_complexities(&parse_input(input)?, &Chain::standard(2))?.to_string())
+    }
+
+    fn part2(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        Ok(sum_of_complexities(&parse_input(input)?, &Chain::standard(25))?.to_string())
+    }
+}
+
+aoc::example_tests!(Day21);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sim::simulate;
+    use std::fs::read_to_string;
+
+    #[test]
+    fn test_example_sequences() {
+        // The example's presses for the first two codes, with no robots and then one or two
+        // between the door and the human.
+        let examples = [
+            (
+                "029A",
+                [
+                    "<A^A>^^AvvvA",
+                    "v<<A>>^A<A>AvA<^AA>A<vAAA>^A",
+                    "<vA<AA>>^AvAA<^A>Av<<A>>^AvA^A<vA>^Av<<A>^A>AAvA^Av<<A>A>^AAAvA<^A>A",
+                ],
+            ),
+            (
+                "379A",
+                [
+                    "^A^^<<A>>AvvvA",
+                    "<A>A<AAv<AA>>^AvAA^Av<AAA^>A",
+                    "v<<A>>^AvA^A<vA<AA>>^AAvA<^A>AAvA^A<vA>^AA<A>Av<<A>A>^AAAvA<^A>A",
+                ],
+            ),
+        ];
+        for (code, presses) in examples {
+            for (robots, example) in presses.into_iter().enumerate() {
+                let chain = Chain::standard(robots);
+                assert_eq!(simulate(chain.pads(), example.chars()).unwrap(), code);
+                assert_eq!(chain.presses(code).unwrap(), example.len() as u64);
+                let sequence = chain.sequence(code).unwrap().collect::<String>();
+                assert_eq!(sequence.len(), example.len());
+                assert_eq!(simulate(chain.pads(), sequence.chars()).unwrap(), code);
+            }
+        }
+    }
+
+    #[test]
+    fn test_overflow() {
+        let codes = ["18446744073709551615A".to_owned()];
+        assert!(sum_of_complexities(&codes, &Chain::standard(0)).is_err());
+        let codes = ["029A".to_owned()];
+        assert!(sum_of_complexities(&codes, &Chain::standard(200)).is_err());
+        assert_eq!(
+            sum_of_complexities(&codes, &Chain::standard(2)).unwrap(),
+            68 * 29
+        );
+    }
+
+    #[test]
+    fn answer_part1() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let targets = parse_input(&data).unwrap();
+
+        assert_eq!(
+            sum_of_complexities(&targets, &Chain::standard(2)).unwrap(),
+            163920
+        );
+    }
+
+    #[test]
+    fn answer_part2() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let targets = parse_input(&data).unwrap();
+
+        assert_eq!(
+            sum_of_complexities(&targets, &Chain::standard(25)).unwrap(),
+            204040805018350
+        );
+    }
+}
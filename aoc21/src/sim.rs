@@ -0,0 +1,113 @@
+//! Replaying the human's presses through a chain of keypads.
+//!
+//! A direction key moves the arm of the keypad before it, and `A` presses whatever key that arm
+//! is on, which in turn acts on the keypad before that.  A press that reaches the first keypad
+//! types a key of the code.  Pointing an arm at a gap is an error, as is pressing a key that is
+//! neither a direction nor `A` on a keypad that controls another one.
+
+use crate::keypad::{Keypad, MOVES};
+use aoc::AocError;
+
+/// Where every robot arm points.
+#[derive(Clone, Debug)]
+pub struct Arms<'a> {
+    pads: &'a [Keypad],
+    /// The key each arm is on, for every keypad but the human's.
+    keys: Vec<char>,
+}
+
+impl<'a> Arms<'a> {
+    pub fn new(pads: &'a [Keypad]) -> Self {
+        Self {
+            pads,
+            keys: vec!['A'; pads.len().saturating_sub(1)],
+        }
+    }
+
+    pub fn keys(&self) -> &[char] {
+        &self.keys
+    }
+
+    /// Presses `key` on the human's keypad, returning the key typed on the first keypad if the
+    /// press gets that far.
+    pub fn press(&mut self, key: char) -> aoc::Result<Option<char>> {
+        self.try_press(key).map_err(AocError::invalid_state)
+    }
+
+    fn try_press(&mut self, key: char) -> Result<Option<char>, String> {
+        let mut pad = self.pads.len() - 1;
+        if self.pads[pad].location(key).is_none() {
+            return Err(format!("no key {:?} on the human's keypad", key));
+        }
+        let mut key = key;
+        loop {
+            if pad == 0 {
+                return Ok(Some(key));
+            }
+            if key == 'A' {
+                pad -= 1;
+                key = self.keys[pad];
+                continue;
+            }
+            let Some(&(_, dx, dy)) = MOVES.iter().find(|&&(dir, _, _)| dir == key) else {
+                return Err(format!("key {:?} on keypad {} does nothing", key, pad));
+            };
+            let target = &self.pads[pad - 1];
+            let next = target.button_location(self.keys[pad - 1]).step(dx, dy);
+            let Some(moved) = target.key_at(next) else {
+                return Err(format!(
+                    "arm of keypad {} moves off {:?} into a gap",
+                    pad - 1,
+                    self.keys[pad - 1]
+                ));
+            };
+            self.keys[pad - 1] = moved;
+            return Ok(None);
+        }
+    }
+}
+
+/// The code typed on the first keypad by pressing `presses` on the last one.
+pub fn simulate(pads: &[Keypad], presses: impl IntoIterator<Item = char>) -> aoc::Result<String> {
+    if pads.is_empty() {
+        return Err(AocError::invalid_state("a chain needs at least one keypad"));
+    }
+    let mut arms = Arms::new(pads);
+    let mut code = String::new();
+    for (i, key) in presses.into_iter().enumerate() {
+        let typed = arms
+            .try_press(key)
+            .map_err(|err| AocError::invalid_state(format!("press {}: {}", i + 1, err)))?;
+        code.extend(typed);
+    }
+    Ok(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::Chain;
+
+    #[test]
+    fn test_simulate() {
+        let chain = Chain::standard(2);
+        let presses = "<vA<AA>>^AvAA<^A>A<v<A>>^AvA^A<vA>^A<v<A>^A>AAvA^A<v<A>A>^AAAvA<^A>A";
+        assert_eq!(simulate(chain.pads(), presses.chars()).unwrap(), "029A");
+        assert_eq!(simulate(chain.pads(), "".chars()).unwrap(), "");
+
+        let err = |presses: &str| {
+            simulate(chain.pads(), presses.chars())
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(err("^").ends_with("state: press 1: arm of keypad 2 moves off 'A' into a gap"));
+        assert!(err("<<").contains("press 2: arm of keypad 2 moves off '^' into a gap"));
+        assert!(err("1").contains("no key '1'"));
+
+        let pads = [Keypad::directional(), Keypad::parse("<>^vAB").unwrap()];
+        assert!(simulate(&pads, "B".chars())
+            .unwrap_err()
+            .to_string()
+            .contains("key 'B' on keypad 1 does nothing"));
+    }
+}
@@ -0,0 +1,174 @@
+//! The secret generator as a linear map over 24 bits.
+//!
+//! Every step of [`Secret::next_num`] xors the number with a shifted copy of itself and drops
+//! the bits past the 24th, which is linear over GF(2).  A step is therefore a 24x24 bit
+//! matrix, stored here as the image of each bit.  Jumping `n` steps ahead is a matrix power,
+//! and walking backwards is the inverse matrix.
+
+use crate::Secret;
+use std::{collections::HashMap, ops::Mul};
+
+pub const BITS: usize = 24;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BitMatrix {
+    /// Column `j` is the image of bit `j`.
+    cols: [u32; BITS],
+}
+
+impl BitMatrix {
+    pub fn identity() -> Self {
+        Self::from_linear(|num| num)
+    }
+
+    /// The matrix of `f`, which must be linear over GF(2).
+    pub fn from_linear(f: impl Fn(u32) -> u32) -> Self {
+        Self {
+            cols: std::array::from_fn(|j| f(1 << j)),
+        }
+    }
+
+    /// One step of the secret generator.
+    pub fn step() -> Self {
+        Self::from_linear(Secret::next_num)
+    }
+
+    pub fn apply(&self, num: u32) -> u32 {
+        self.cols
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| num >> j & 1 == 1)
+            .fold(0, |acc, (_, col)| acc ^ col)
+    }
+
+    pub fn pow(&self, n: u64) -> Self {
+        let mut result = Self::identity();
+        let mut base = *self;
+        let mut n = n;
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            n >>= 1;
+        }
+        result
+    }
+
+    // Row `i` holds bit `i` of every column.
+    fn transpose(&self) -> Self {
+        Self::from_linear(|bit| {
+            let j = bit.trailing_zeros();
+            self.cols
+                .iter()
+                .enumerate()
+                .fold(0, |acc, (i, col)| acc | (col >> j & 1) << i)
+        })
+    }
+
+    /// The inverse, by Gauss-Jordan elimination on the rows, or `None` if the map loses
+    /// information.
+    pub fn inverse(&self) -> Option<Self> {
+        let mut rows = self.transpose().cols;
+        let mut inverse = Self::identity().cols;
+        for j in 0..BITS {
+            let pivot = (j..BITS).find(|&i| rows[i] >> j & 1 == 1)?;
+            rows.swap(j, pivot);
+            inverse.swap(j, pivot);
+            for i in 0..BITS {
+                if i != j && rows[i] >> j & 1 == 1 {
+                    rows[i] ^= rows[j];
+                    inverse[i] ^= inverse[j];
+                }
+            }
+        }
+        Some(Self { cols: inverse }.transpose())
+    }
+
+    /// The fewest steps, at least one, after which `num` comes back to itself, where `back` is
+    /// the inverse of the matrix.  Baby steps go backwards from `num` and giant steps forwards,
+    /// so it takes about 2 * 2^12 steps rather than up to 2^24.
+    pub fn cycle_length(&self, back: &Self, num: u32) -> u64 {
+        const STRIDE: u64 = 1 << (BITS / 2);
+        let mut behind = HashMap::new();
+        let mut state = num;
+        for j in 0..STRIDE {
+            if j > 0 && state == num {
+                return j;
+            }
+            behind.insert(state, j);
+            state = back.apply(state);
+        }
+        let giant = self.pow(STRIDE);
+        let mut state = num;
+        for i in 1..=STRIDE {
+            state = giant.apply(state);
+            if let Some(j) = behind.get(&state) {
+                return i * STRIDE + j;
+            }
+        }
+        unreachable!("an invertible map on 24 bits has cycles of at most 2^24 steps")
+    }
+}
+
+impl Mul for BitMatrix {
+    type Output = Self;
+
+    /// The map applying `rhs` first and then `self`.
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            cols: rhs.cols.map(|col| self.apply(col)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::testing::Rng;
+
+    #[test]
+    fn test_step() {
+        let step = BitMatrix::step();
+        let mut rng = Rng::from_env();
+        for _ in 0..100 {
+            let num = rng.next_u64() as u32 & 0xffffff;
+            assert_eq!(step.apply(num), Secret::next_num(num));
+            let n = rng.usize(0, 3000);
+            let stepped = (0..n).fold(num, |num, _| Secret::next_num(num));
+            assert_eq!(step.pow(n as u64).apply(num), stepped);
+        }
+        assert_eq!(step.transpose().transpose(), step);
+        assert_eq!(step * BitMatrix::identity(), step);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let step = BitMatrix::step();
+        let back = step.inverse().unwrap();
+        assert_eq!(step * back, BitMatrix::identity());
+        assert_eq!(back * step, BitMatrix::identity());
+        assert_eq!(back.apply(15887950), 123);
+        assert_eq!(BitMatrix::from_linear(|num| num & !1).inverse(), None);
+    }
+
+    #[test]
+    fn test_cycle_length() {
+        let step = BitMatrix::step();
+        let back = step.inverse().unwrap();
+        assert_eq!(step.cycle_length(&back, 0), 1);
+        // Only the lowest bits, where a swap of two bits cycles every second step.
+        let swap = BitMatrix::from_linear(|num| num & !3 | (num & 1) << 1 | (num >> 1) & 1);
+        assert_eq!(swap.cycle_length(&swap, 1), 2);
+        assert_eq!(swap.cycle_length(&swap, 3), 1);
+
+        let length = step.cycle_length(&back, 123);
+        let mut num = Secret::next_num(123);
+        let mut steps = 1;
+        while num != 123 {
+            num = Secret::next_num(num);
+            steps += 1;
+        }
+        assert_eq!(length, steps);
+    }
+}
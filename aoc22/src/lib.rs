@@ -0,0 +1,265 @@
+pub mod gf2;
+pub mod market;
+
+use aoc::Solution;
+use gf2::BitMatrix;
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use rayon::prelude::*;
+
+lazy_static! {
+    static ref STEP: BitMatrix = BitMatrix::step();
+    static ref BACK: BitMatrix = STEP.inverse().expect("every step can be undone");
+}
+
+pub struct Secret {
+    num: u32,
+}
+
+impl Secret {
+    pub fn from(line: &aoc::Line) -> aoc::Result<Self> {
+        let num = line.parse(line.text)?;
+        if num >= 0x1000000 {
+            return Err(line.error(line.text, "secret must fit in 24 bits"));
+        }
+        Ok(Self::new(num))
+    }
+
+    pub fn new(num: u32) -> Self {
+        Self { num }
+    }
+
+    pub fn next_num(num: u32) -> u32 {
+        let mut num = num;
+        num ^= num << 6;
+        num %= 0x1000000;
+        num ^= num >> 5;
+        num %= 0x1000000;
+        num ^= num << 11;
+        num %= 0x1000000;
+        num
+    }
+
+    pub fn next(&self) -> Self {
+        Self {
+            num: Self::next_num(self.num),
+        }
+    }
+
+    /// The secret `n` steps ahead, by a matrix power rather than `n` steps.
+    pub fn nth(&self, n: usize) -> Self {
+        Self {
+            num: STEP.pow(n as u64).apply(self.num),
+        }
+    }
+
+    pub fn prev(&self) -> Self {
+        Self {
+            num: BACK.apply(self.num),
+        }
+    }
+
+    /// The secret `n` steps back.
+    pub fn nth_back(&self, n: usize) -> Self {
+        Self {
+            num: BACK.pow(n as u64).apply(self.num),
+        }
+    }
+
+    /// How many steps it takes to get back to this secret.
+    pub fn cycle_length(&self) -> u64 {
+        STEP.cycle_length(&BACK, self.num)
+    }
+
+    pub fn first_n(&self, n: usize) -> Vec<u32> {
+        let mut num = self.num;
+        let mut secrets = Vec::<u32>::with_capacity(n + 1);
+
+        secrets.push(num);
+        for _ in 0..n {
+            num = Self::next_num(num);
+            secrets.push(num);
+        }
+        secrets
+    }
+
+    pub fn sequences_and_prices_for_first_n(&self, n: usize) -> Vec<([i32; 4], u32)> {
+        let secrets = self.first_n(n);
+        let prices: Vec<_> = secrets.into_iter().map(|secret| secret % 10).collect();
+
+        let deltas = prices
+            .iter()
+            .tuple_windows()
+            .map(|(&a, &b)| b as i32 - a as i32)
+            .collect::<Vec<_>>();
+
+        let sequences = deltas
+            .windows(4)
+            .map(|window| window.try_into().unwrap())
+            .collect::<Vec<_>>();
+
+        sequences
+            .into_iter()
+            .zip(prices.into_iter().skip(4))
+            .collect()
+    }
+}
+
+pub fn sum_of_2000th(secrets: &[Secret]) -> u64 {
+    let jump = STEP.pow(2000);
+    secrets
+        .par_iter()
+        .map(|secret| jump.apply(secret.num) as u64)
+        .sum()
+}
+
+pub fn price_for_sequence(sequences_and_prices: &[([i32; 4], u32)], sequence: &[i32; 4]) -> u32 {
+    sequences_and_prices
+        .iter()
+        .find(|&(s, _)| s == sequence)
+        .map_or(0, |&(_, price)| price)
+}
+
+/// The sequence of four price changes that earns the most bananas over the day, and how many.
+pub fn best_sequence(secrets: &[Secret]) -> market::Best {
+    market::best_sequence(secrets, 2000)
+}
+
+pub fn total_bananas(secrets: &[Secret]) -> u64 {
+    best_sequence(secrets).bananas
+}
+
+pub fn parse_input(data: &str) -> aoc::Result<Vec<Secret>> {
+    aoc::lines(data).map(|line| Secret::from(&line)).collect()
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    fn part1(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        Ok(sum_of_2000th(&parse_input(input)?).to_string())
+    }
+
+    fn part2(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        Ok(total_bananas(&parse_input(input)?).to_string())
+    }
+}
+
+aoc::example_tests!(Day22);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::read_to_string;
+
+    #[test]
+    fn test_nth() {
+        let secret = Secret::new(123);
+        assert_eq!(secret.next().num, 15887950);
+        assert_eq!(Secret::new(15887950).next().num, 16495136);
+        assert_eq!(secret.next().next().num, 16495136);
+        assert_eq!(secret.nth(1).num, 15887950);
+        assert_eq!(secret.nth(2).num, 16495136);
+        assert_eq!(secret.nth(3).num, 527345);
+        assert_eq!(secret.nth(4).num, 704524);
+        assert_eq!(secret.nth(5).num, 1553684);
+        assert_eq!(secret.nth(6).num, 12683156);
+        assert_eq!(secret.nth(7).num, 11100544);
+        assert_eq!(secret.nth(8).num, 12249484);
+        assert_eq!(secret.nth(9).num, 7753432);
+        assert_eq!(secret.nth(10).num, 5908254);
+
+        let iterated = (0..2000).fold(123, |num, _| Secret::next_num(num));
+        assert_eq!(secret.nth(2000).num, iterated);
+        assert_eq!(Secret::new(iterated).nth_back(2000).num, 123);
+        assert_eq!(secret.next().prev().num, 123);
+        assert_eq!(Secret::new(5908254).nth_back(10).num, 123);
+    }
+
+    #[test]
+    fn test_cycle_length() {
+        let secret = Secret::new(123);
+        let length = secret.cycle_length();
+        assert_eq!(length, 0xffffff);
+        assert_eq!(secret.nth(length as usize).num, 123);
+        assert_eq!(Secret::new(0).cycle_length(), 1);
+    }
+
+    #[test]
+    fn answer_part1() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let secrets = parse_input(&data).unwrap();
+        assert_eq!(sum_of_2000th(&secrets), 20411980517);
+    }
+
+    #[test]
+    fn test_price_for_sequence() {
+        let secret = Secret::new(1);
+        let sequences_and_prices = secret.sequences_and_prices_for_first_n(2000);
+        assert_eq!(
+            price_for_sequence(
+                &Secret::new(123).sequences_and_prices_for_first_n(2000),
+                &[-3, 6, -1, -1]
+            ),
+            4
+        );
+        assert_eq!(
+            price_for_sequence(&sequences_and_prices, &[-2, 1, -1, 3]),
+            7
+        );
+    }
+
+    #[test]
+    fn test_prices() {
+        let data = "1\n2\n3\n2024";
+        let secrets = parse_input(&data).unwrap();
+        let sequence = [-2, 1, -1, 3];
+        assert_eq!(
+            price_for_sequence(
+                &Secret::new(1).sequences_and_prices_for_first_n(2000),
+                &sequence
+            ),
+            7
+        );
+        assert_eq!(
+            price_for_sequence(
+                &Secret::new(2).sequences_and_prices_for_first_n(2000),
+                &sequence
+            ),
+            7
+        );
+        assert_eq!(
+            price_for_sequence(
+                &Secret::new(3).sequences_and_prices_for_first_n(2000),
+                &sequence
+            ),
+            0
+        );
+        assert_eq!(
+            price_for_sequence(
+                &Secret::new(2024).sequences_and_prices_for_first_n(2000),
+                &sequence
+            ),
+            9
+        );
+
+        let all_sequences_and_prices: Vec<_> = secrets
+            .iter()
+            .map(|secret| secret.sequences_and_prices_for_first_n(2000))
+            .collect();
+
+        let prices: Vec<_> = all_sequences_and_prices
+            .iter()
+            .map(|sequences_and_prices| price_for_sequence(&sequences_and_prices, &[-2, 1, -1, 3]))
+            .collect();
+        assert_eq!(prices, &[7, 7, 0, 9]);
+    }
+
+    #[test]
+    fn answer_part2() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let secrets = parse_input(&data).unwrap();
+        assert_eq!(total_bananas(&secrets), 2362);
+        assert_eq!(best_sequence(&secrets).sequence, [-2, 1, -1, 2]);
+    }
+}
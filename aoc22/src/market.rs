@@ -0,0 +1,150 @@
+//! Finding the sequence of four price changes that earns the most bananas.
+//!
+//! Each change is between -9 and 9, so a window of four is a number in base 19 and every
+//! possible sequence gets a slot in one dense array.  Buyers add the price at the first time
+//! each window appears, with a bitmap marking the windows already seen for the current buyer.
+//! Buyers are split across threads, each adding into its own array, and the arrays are summed
+//! at the end.
+
+use crate::Secret;
+use rayon::prelude::*;
+use std::cmp::Reverse;
+
+/// How many sequences of four changes there are.
+pub const SEQUENCES: usize = 19 * 19 * 19 * 19;
+
+pub fn sequence_index(changes: [i32; 4]) -> usize {
+    changes
+        .iter()
+        .fold(0, |index, &change| index * 19 + (change + 9) as usize)
+}
+
+pub fn index_sequence(index: usize) -> [i32; 4] {
+    let mut changes = [0; 4];
+    let mut index = index;
+    for change in changes.iter_mut().rev() {
+        *change = (index % 19) as i32 - 9;
+        index /= 19;
+    }
+    changes
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Best {
+    pub sequence: [i32; 4],
+    pub bananas: u64,
+}
+
+// Per-thread totals and the windows the current buyer has already sold at.
+struct Totals {
+    bananas: Vec<u32>,
+    seen: Vec<u64>,
+}
+
+impl Totals {
+    fn new() -> Self {
+        Self {
+            bananas: vec![0; SEQUENCES],
+            seen: vec![0; SEQUENCES.div_ceil(64)],
+        }
+    }
+
+    fn add_buyer(mut self, secret: &Secret, n: usize) -> Self {
+        self.seen.fill(0);
+        let mut num = secret.num;
+        let mut price = num % 10;
+        let mut index = 0;
+        for i in 0..n {
+            num = Secret::next_num(num);
+            let next = num % 10;
+            // Slide the window along, dropping the oldest change.
+            index = (index * 19 + (next + 9 - price) as usize) % SEQUENCES;
+            price = next;
+            if i < 3 {
+                continue;
+            }
+            let (word, bit) = (index / 64, 1 << (index % 64));
+            if self.seen[word] & bit == 0 {
+                self.seen[word] |= bit;
+                self.bananas[index] += price;
+            }
+        }
+        self
+    }
+
+    fn merge(mut self, other: Self) -> Self {
+        for (total, more) in self.bananas.iter_mut().zip(other.bananas) {
+            *total += more;
+        }
+        self
+    }
+}
+
+/// The sequence that earns the most bananas over the first `n` new secrets of every buyer,
+/// preferring the lowest index on ties.
+pub fn best_sequence(secrets: &[Secret], n: usize) -> Best {
+    let totals = secrets
+        .par_iter()
+        .fold(Totals::new, |totals, secret| totals.add_buyer(secret, n))
+        .reduce(Totals::new, Totals::merge);
+    let index = (0..SEQUENCES)
+        .max_by_key(|&index| (totals.bananas[index], Reverse(index)))
+        .unwrap();
+    Best {
+        sequence: index_sequence(index),
+        bananas: totals.bananas[index] as u64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price_for_sequence;
+
+    #[test]
+    fn test_index() {
+        assert_eq!(sequence_index([-9, -9, -9, -9]), 0);
+        assert_eq!(sequence_index([9, 9, 9, 9]), SEQUENCES - 1);
+        for index in [0, 1, 18, 19, 12345, SEQUENCES - 1] {
+            assert_eq!(sequence_index(index_sequence(index)), index);
+        }
+    }
+
+    #[test]
+    fn test_best_sequence() {
+        let secrets = [1, 2, 3, 2024].map(Secret::new);
+        assert_eq!(
+            best_sequence(&secrets, 2000),
+            Best {
+                sequence: [-2, 1, -1, 3],
+                bananas: 23
+            }
+        );
+
+        // Against the scan over each buyer's windows.
+        let secrets = (0..20)
+            .map(|i| Secret::new(i * 7919 + 1))
+            .collect::<Vec<_>>();
+        let best = best_sequence(&secrets, 300);
+        let windows = secrets
+            .iter()
+            .map(|secret| secret.sequences_and_prices_for_first_n(300))
+            .collect::<Vec<_>>();
+        let total = |sequence| {
+            windows
+                .iter()
+                .map(|windows| price_for_sequence(windows, sequence) as u64)
+                .sum::<u64>()
+        };
+        assert_eq!(total(&best.sequence), best.bananas);
+        let most = windows
+            .iter()
+            .flatten()
+            .map(|(sequence, _)| total(sequence))
+            .max();
+        assert_eq!(most, Some(best.bananas));
+
+        assert_eq!(best_sequence(&[], 2000).bananas, 0);
+        assert_eq!(best_sequence(&secrets, 3).bananas, 0);
+    }
+}
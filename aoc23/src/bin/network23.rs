@@ -0,0 +1,52 @@
+//! Reports on the day 23 network: `network23 [report|cliques|members|dot|graphml] [input]`.
+
+use aoc23::{parse_input, report::Report};
+use std::{path::PathBuf, process::ExitCode};
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let command = args.next().unwrap_or_else(|| String::from("report"));
+    let path = args.next().map_or_else(
+        || PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/main.txt"),
+        PathBuf::from,
+    );
+    let report = match aoc::read_input(&path)
+        .and_then(|data| parse_input(&data))
+        .and_then(|connections| Report::new(&connections))
+    {
+        Ok(report) => report,
+        Err(err) => {
+            eprintln!("network23: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match command.as_str() {
+        "report" => println!("{}", report),
+        "cliques" => {
+            for clique in report.cliques() {
+                println!("{:>3} {}", clique.len(), clique.join(","));
+            }
+        }
+        "members" => {
+            for (name, cliques) in report.membership() {
+                println!(
+                    "{} {}",
+                    name,
+                    cliques
+                        .iter()
+                        .map(|i| i.to_string())
+                        .collect::<Vec<_>>()
+                        .join(",")
+                );
+            }
+        }
+        "dot" => print!("{}", report.to_dot()),
+        "graphml" => print!("{}", report.to_graphml()),
+        _ => {
+            eprintln!("network23: expected report, cliques, members, dot or graphml");
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
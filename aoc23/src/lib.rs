@@ -0,0 +1,100 @@
+pub mod report;
+
+use aoc::{AocError, Solution};
+use mygraph::Graph;
+use std::collections::HashMap;
+
+pub type Connections = HashMap<String, Vec<String>>;
+
+/// The network as a graph, with node ids in sorted name order.
+pub fn graph(connections: &Connections) -> aoc::Result<Graph> {
+    let mut names = connections.keys().collect::<Vec<_>>();
+    names.sort();
+    let mut graph = Graph::new();
+    for name in &names {
+        graph.add_node(name)?;
+    }
+    for name in names {
+        for other in &connections[name] {
+            graph.add_edge(name, other)?;
+        }
+    }
+    Ok(graph)
+}
+
+pub fn count_interconnected_computers_with_t(graph: &Graph) -> usize {
+    graph
+        .triangles()
+        .iter()
+        .filter(|triangle| triangle.iter().any(|&id| graph.name(id).starts_with('t')))
+        .count()
+}
+
+pub fn largest_interconnected_set(graph: &Graph) -> String {
+    graph.sorted_names(&graph.maximum_clique()).join(",")
+}
+
+pub fn parse_input(data: &str) -> aoc::Result<Connections> {
+    let mut map = Connections::new();
+
+    for line in aoc::lines(data) {
+        let (a, b) = line.split_once("-")?;
+        if a.is_empty() || b.is_empty() || a == b {
+            return Err(line.error(line.text, "expected a connection between two computers"));
+        }
+        map.entry(a.to_owned()).or_default().push(b.to_owned());
+        map.entry(b.to_owned()).or_default().push(a.to_owned());
+    }
+
+    if map.is_empty() {
+        return Err(AocError::parse(1, 1, "expected at least one connection"));
+    }
+
+    Ok(map)
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    fn part1(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        let graph = graph(&parse_input(input)?)?;
+        Ok(count_interconnected_computers_with_t(&graph).to_string())
+    }
+
+    fn part2(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        Ok(largest_interconnected_set(&graph(&parse_input(input)?)?))
+    }
+}
+
+aoc::example_tests!(Day23);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::read_to_string;
+
+    #[test]
+    fn test_graph_ids() {
+        let data = read_to_string("../examples/23/test.txt").unwrap();
+        let graph = graph(&parse_input(&data).unwrap()).unwrap();
+        let names = graph.nodes().map(|v| graph.name(v)).collect::<Vec<_>>();
+        assert!(names.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn answer_part1() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let graph = graph(&parse_input(&data).unwrap()).unwrap();
+        assert_eq!(count_interconnected_computers_with_t(&graph), 1215);
+    }
+
+    #[test]
+    fn answer_part2() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let graph = graph(&parse_input(&data).unwrap()).unwrap();
+        assert_eq!(
+            largest_interconnected_set(&graph),
+            "bm,by,dv,ep,ia,ja,jb,ks,lv,ol,oy,uz,yt"
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! What the LAN looks like beyond the two puzzle answers.
+
+use crate::{graph, Connections};
+use mygraph::{Graph, NodeId};
+use std::{cmp::Reverse, collections::BTreeMap, fmt};
+
+pub struct Report {
+    graph: Graph,
+    /// Every maximal clique, largest first and then by name.
+    cliques: Vec<Vec<NodeId>>,
+    cores: Vec<usize>,
+}
+
+impl Report {
+    pub fn new(connections: &Connections) -> aoc::Result<Self> {
+        let graph = graph(connections)?;
+        let mut cliques = graph.maximal_cliques();
+        cliques.sort_by_cached_key(|clique| (Reverse(clique.len()), graph.sorted_names(clique)));
+        let cores = graph.core_numbers();
+        Ok(Self {
+            graph,
+            cliques,
+            cores,
+        })
+    }
+
+    pub fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    /// How many computers have each number of connections.
+    pub fn degree_distribution(&self) -> BTreeMap<usize, usize> {
+        let mut counts = BTreeMap::new();
+        for v in self.graph.nodes() {
+            *counts.entry(self.graph.degree(v)).or_default() += 1;
+        }
+        counts
+    }
+
+    /// The computers with each core number, sorted by name.
+    pub fn cores(&self) -> BTreeMap<usize, Vec<&str>> {
+        let mut cores = BTreeMap::<_, Vec<_>>::new();
+        for v in self.graph.nodes() {
+            cores
+                .entry(self.cores[v as usize])
+                .or_default()
+                .push(self.graph.name(v));
+        }
+        cores.values_mut().for_each(|names| names.sort());
+        cores
+    }
+
+    /// Every maximal clique as sorted names, largest first.
+    pub fn cliques(&self) -> Vec<Vec<&str>> {
+        self.cliques
+            .iter()
+            .map(|clique| self.graph.sorted_names(clique))
+            .collect()
+    }
+
+    pub fn largest_clique(&self) -> &[NodeId] {
+        self.cliques.first().map_or(&[], Vec::as_slice)
+    }
+
+    /// For each computer, the positions in [`Report::cliques`] of the cliques it is in.
+    pub fn membership(&self) -> BTreeMap<&str, Vec<usize>> {
+        let mut membership = self
+            .graph
+            .nodes()
+            .map(|v| (self.graph.name(v), vec![]))
+            .collect::<BTreeMap<_, _>>();
+        for (i, clique) in self.cliques.iter().enumerate() {
+            for &v in clique {
+                membership.get_mut(self.graph.name(v)).unwrap().push(i);
+            }
+        }
+        membership
+    }
+
+    pub fn to_dot(&self) -> String {
+        self.graph.to_dot("lan", self.largest_clique())
+    }
+
+    pub fn to_graphml(&self) -> String {
+        self.graph.to_graphml(self.largest_clique())
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let graph = &self.graph;
+        writeln!(
+            f,
+            "{} computers, {} connections, {} components, {} triangles",
+            graph.len(),
+            graph.edge_count(),
+            graph.components().len(),
+            graph.triangle_count()
+        )?;
+        writeln!(f, "degrees:")?;
+        for (degree, count) in self.degree_distribution() {
+            writeln!(f, "  {:>3}: {}", degree, count)?;
+        }
+        writeln!(f, "cores:")?;
+        for (k, names) in self.cores() {
+            writeln!(f, "  {:>3}: {}", k, names.len())?;
+        }
+        let mut sizes = BTreeMap::<_, usize>::new();
+        for clique in &self.cliques {
+            *sizes.entry(Reverse(clique.len())).or_default() += 1;
+        }
+        writeln!(f, "maximal cliques: {}", self.cliques.len())?;
+        for (Reverse(size), count) in sizes {
+            writeln!(f, "  {:>3}: {}", size, count)?;
+        }
+        write!(
+            f,
+            "largest clique: {}",
+            graph.sorted_names(self.largest_clique()).join(",")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+    use std::fs::read_to_string;
+
+    #[test]
+    fn test_report() {
+        let data = read_to_string("../examples/23/test.txt").unwrap();
+        let report = Report::new(&parse_input(&data).unwrap()).unwrap();
+        assert_eq!(report.degree_distribution(), BTreeMap::from([(4, 16)]));
+        // Every computer has four connections, so they all form one 4-core.
+        assert_eq!(report.cores().keys().copied().collect::<Vec<_>>(), [4]);
+        let cliques = report.cliques();
+        assert_eq!(cliques[0], ["co", "de", "ka", "ta"]);
+        assert!(cliques.windows(2).all(|w| w[0].len() >= w[1].len()));
+        let membership = report.membership();
+        assert_eq!(membership.len(), 16);
+        assert!(membership["co"].contains(&0));
+        assert!(!membership["tc"].contains(&0));
+        assert!(membership.values().all(|cliques| !cliques.is_empty()));
+
+        let text = report.to_string();
+        assert!(text.starts_with("16 computers, 32 connections, 1 components, 12 triangles\n"));
+        assert!(text.ends_with("largest clique: co,de,ka,ta"));
+        assert!(report
+            .to_dot()
+            .contains("\"co\" -- \"de\" [color=red, penwidth=2];"));
+        assert!(report
+            .to_graphml()
+            .contains("<node id=\"ka\"><data key=\"highlight\">true</data></node>"));
+    }
+}
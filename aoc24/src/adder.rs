@@ -0,0 +1,428 @@
+//! Checks the circuit against a ripple-carry adder and repairs swapped gate outputs.
+//!
+//! Bit 0 must be a half adder (`x00 XOR y00 -> z00`, `x00 AND y00 -> carry`) and every later
+//! bit a full adder fed by the previous carry, with the last carry driving the top `z` wire.
+//! Repairs swap outputs near the first bit that does not match, backtracking when a swap
+//! leads nowhere, and the whole circuit is simulated once no faults remain.
+
+use crate::{
+    gates::Op,
+    netlist::{GateId, Netlist, WireId},
+    sim,
+};
+use aoc::AocError;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Repair {
+    pub bit: usize,
+    pub wires: (String, String),
+}
+
+/// The most swaps [`Adder::repair`] looks for; the puzzle has four.
+pub const MAX_SWAPS: usize = 4;
+
+pub struct Adder {
+    netlist: Netlist,
+    by_inputs: HashMap<(Op, WireId, WireId), GateId>,
+}
+
+fn wire(prefix: &str, bit: usize) -> String {
+    format!("{}{:02}", prefix, bit)
+}
+
+fn input_key(op: Op, a: WireId, b: WireId) -> (Op, WireId, WireId) {
+    (op, a.min(b), a.max(b))
+}
+
+impl Adder {
+    pub fn new(netlist: Netlist) -> aoc::Result<Self> {
+        if netlist.bits() == 0 {
+            return Err(AocError::invalid_state("circuit has no x inputs"));
+        }
+        let by_inputs = netlist
+            .gates()
+            .iter()
+            .enumerate()
+            .filter_map(|(id, gate)| match gate.inputs[..] {
+                [a, b] => Some((input_key(gate.op, a, b), id)),
+                _ => None,
+            })
+            .collect();
+        Ok(Self { netlist, by_inputs })
+    }
+
+    pub fn netlist(&self) -> &Netlist {
+        &self.netlist
+    }
+
+    pub fn swap(&mut self, a: &str, b: &str) -> aoc::Result<()> {
+        self.netlist.swap_outputs(a, b)
+    }
+
+    fn find(&self, op: Op, a: WireId, b: WireId) -> Option<WireId> {
+        let gate = *self.by_inputs.get(&input_key(op, a, b))?;
+        Some(self.netlist.gates()[gate].output)
+    }
+
+    /// Returns the carry out of `bit` if it is wired as an adder stage.
+    fn check_bit(&self, bit: usize, carry_in: Option<WireId>) -> Option<WireId> {
+        let x = self.netlist.wire(&wire("x", bit))?;
+        let y = self.netlist.wire(&wire("y", bit))?;
+        let z = self.netlist.wire(&wire("z", bit))?;
+        let sum = self.find(Op::Xor, x, y)?;
+        let generate = self.find(Op::And, x, y)?;
+        let carry_out = match carry_in {
+            None => (sum == z).then_some(generate)?,
+            Some(carry_in) => {
+                if self.find(Op::Xor, sum, carry_in)? != z {
+                    return None;
+                }
+                let propagate = self.find(Op::And, sum, carry_in)?;
+                self.find(Op::Or, generate, propagate)?
+            }
+        };
+        let bits = self.netlist.bits();
+        if bit + 1 == bits && Some(carry_out) != self.netlist.wire(&wire("z", bits)) {
+            return None;
+        }
+        Some(carry_out)
+    }
+
+    /// Returns the first bit that is not a correctly wired adder stage, with its carry in.
+    pub fn first_fault(&self) -> Option<(usize, Option<WireId>)> {
+        let mut carry = None;
+        for bit in 0..self.netlist.bits() {
+            match self.check_bit(bit, carry) {
+                Some(carry_out) => carry = Some(carry_out),
+                None => return Some((bit, carry)),
+            }
+        }
+        None
+    }
+
+    /// Gate outputs within two gates of the inputs of `bit`, which is where a swap that breaks
+    /// the stage has to be.
+    fn candidates(&self, bit: usize, carry_in: Option<WireId>) -> Vec<String> {
+        let mut wires = [wire("x", bit), wire("y", bit)]
+            .iter()
+            .filter_map(|name| self.netlist.wire(name))
+            .chain(carry_in)
+            .collect::<HashSet<_>>();
+        let mut outputs = self
+            .netlist
+            .wire(&wire("z", bit))
+            .into_iter()
+            .chain(carry_in)
+            .filter(|&wire| self.netlist.driver(wire).is_some())
+            .collect::<BTreeSet<_>>();
+        for _ in 0..2 {
+            let reached = self
+                .netlist
+                .gates()
+                .iter()
+                .filter(|gate| gate.inputs.iter().any(|input| wires.contains(input)))
+                .map(|gate| gate.output)
+                .collect::<Vec<_>>();
+            outputs.extend(reached.iter().copied());
+            wires.extend(reached);
+        }
+        outputs
+            .into_iter()
+            .map(|wire| self.netlist.name(wire).to_owned())
+            .collect()
+    }
+
+    /// The smallest set of at most [`MAX_SWAPS`] swaps that makes the circuit an adder, found
+    /// by searching with one more swap allowed each round.
+    pub fn repair(&mut self) -> aoc::Result<Vec<Repair>> {
+        for limit in 0..=MAX_SWAPS {
+            let mut repairs = vec![];
+            if self.search(limit, &mut repairs)? {
+                return Ok(repairs);
+            }
+        }
+        let bit = self
+            .first_fault()
+            .map_or(self.netlist.bits(), |(bit, _)| bit);
+        Err(AocError::no_solution(format!(
+            "no {} or fewer swaps repair the circuit, which first fails at bit {}",
+            MAX_SWAPS, bit
+        )))
+    }
+
+    // Tries swaps near the first fault, those that move the fault to a later bit first.  A swap
+    // that leaves the fault where it is only helps if another swap at the same bit follows.  No
+    // wire is swapped twice, so the swaps commute and those at one bit are tried in name order
+    // to try each set once.
+    fn search(&mut self, budget: usize, repairs: &mut Vec<Repair>) -> aoc::Result<bool> {
+        let Some((bit, carry_in)) = self.first_fault() else {
+            return Ok(self.verify().is_ok());
+        };
+        if budget == 0 {
+            return Ok(false);
+        }
+        let last = repairs.last().filter(|last| last.bit == bit);
+        let candidates = self.candidates(bit, carry_in);
+        let mut moves = vec![];
+        for (i, a) in candidates.iter().enumerate() {
+            for b in &candidates[i + 1..] {
+                let repair = Repair {
+                    bit,
+                    wires: (a.clone(), b.clone()),
+                };
+                let used = |wire: &String| {
+                    repairs
+                        .iter()
+                        .any(|repair| [&repair.wires.0, &repair.wires.1].contains(&wire))
+                };
+                if last.is_some_and(|last| last.wires >= repair.wires) || used(a) || used(b) {
+                    continue;
+                }
+                // Swaps that would create a loop are rejected by the netlist.
+                if self.swap(a, b).is_err() {
+                    continue;
+                }
+                let fault = self.first_fault().map_or(usize::MAX, |(fault, _)| fault);
+                self.swap(a, b)?;
+                if fault > bit || (fault == bit && budget >= 2) {
+                    moves.push((fault == bit, repair));
+                }
+            }
+        }
+        moves.sort_by_key(|&(stays, _)| stays);
+
+        for (_, repair) in moves {
+            let (a, b) = &repair.wires;
+            self.swap(a, b)?;
+            repairs.push(repair);
+            if self.search(budget - 1, repairs)? {
+                return Ok(true);
+            }
+            let repair = repairs.pop().unwrap();
+            self.swap(&repair.wires.0, &repair.wires.1)?;
+        }
+        Ok(false)
+    }
+
+    fn verify(&self) -> aoc::Result<()> {
+        let report = sim::check_adder(&self.netlist, sim::carry_vectors(self.netlist.bits()));
+        if let Some(mismatch) = report.mismatches.first() {
+            return Err(AocError::invalid_state(format!(
+                "repaired circuit does not add {:#x} + {:#x}",
+                mismatch.x, mismatch.y
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Gate outputs that break the usual shape of a ripple-carry adder, without simulating it.
+/// Every `z` but the top one must come from an XOR, the top one from an OR, XORs of internal
+/// wires must drive a `z`, and above bit 0 the half sums must feed an XOR and the ANDs an OR.
+pub fn suspicious_wires(netlist: &Netlist) -> Vec<WireId> {
+    let mut readers = vec![vec![]; netlist.wire_count()];
+    for gate in netlist.gates() {
+        for &input in &gate.inputs {
+            readers[input].push(gate.op);
+        }
+    }
+    let is_input = |wire: WireId| netlist.initial(wire).is_some();
+    let top = netlist.wire(&wire("z", netlist.bits()));
+    let first = [netlist.wire("x00"), netlist.wire("y00")];
+
+    let mut wires = netlist
+        .gates()
+        .iter()
+        .filter(|gate| {
+            let output = gate.output;
+            let is_z = netlist.name(output).starts_with('z');
+            let from_inputs = gate.inputs.iter().all(|&input| is_input(input));
+            let first_bit = gate
+                .inputs
+                .iter()
+                .all(|&input| first.contains(&Some(input)));
+            let feeds = |op| readers[output].contains(&op);
+            match gate.op {
+                _ if Some(output) == top => gate.op != Op::Or && netlist.bits() > 1,
+                _ if is_z && gate.op != Op::Xor => true,
+                Op::Xor if !from_inputs => !is_z,
+                Op::Xor => !first_bit && !feeds(Op::Xor),
+                Op::And => !first_bit && readers[output].iter().any(|&op| op != Op::Or),
+                Op::Or => false,
+                // None of the other gates belong in the adder.
+                _ => true,
+            }
+        })
+        .map(|gate| gate.output)
+        .collect::<Vec<_>>();
+    wires.sort_by_key(|&wire| netlist.name(wire));
+    wires
+}
+
+/// The sorted, comma separated wires whose outputs have to be swapped back.
+pub fn swapped_wires(netlist: Netlist) -> aoc::Result<String> {
+    let mut wires = Adder::new(netlist)?
+        .repair()?
+        .into_iter()
+        .flat_map(|repair| [repair.wires.0, repair.wires.1])
+        .collect::<Vec<_>>();
+    wires.sort();
+    Ok(wires.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::testing::Rng;
+
+    // A correct ripple-carry adder with random internal wire names, and the wires of each bit
+    // that a swap could confuse.
+    fn ripple_adder(rng: &mut Rng, bits: usize) -> (String, Vec<Vec<String>>) {
+        let mut names = (0..bits * 4)
+            .map(|i| format!("{}{:03}", ["a", "b", "c"][i % 3], i))
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut names);
+        let mut names = names.into_iter();
+
+        let mut s = String::new();
+        for prefix in ["x", "y"] {
+            for bit in 0..bits {
+                s += &format!("{}: {}\n", wire(prefix, bit), rng.below(2));
+            }
+        }
+        s.push('\n');
+
+        let mut lines = vec![];
+        let mut stages = vec![];
+        let mut carry = String::new();
+        for bit in 0..bits {
+            let (x, y, z) = (wire("x", bit), wire("y", bit), wire("z", bit));
+            let sum = if bit == 0 {
+                z.clone()
+            } else {
+                names.next().unwrap()
+            };
+            let last = bit + 1 == bits;
+            let generate = if bit == 0 && last {
+                wire("z", bits)
+            } else {
+                names.next().unwrap()
+            };
+            lines.push(format!("{} XOR {} -> {}", x, y, sum));
+            lines.push(format!("{} AND {} -> {}", y, x, generate));
+            if bit == 0 {
+                stages.push(vec![sum, generate.clone()]);
+                carry = generate;
+                continue;
+            }
+            let propagate = names.next().unwrap();
+            let carry_out = if last {
+                wire("z", bits)
+            } else {
+                names.next().unwrap()
+            };
+            lines.push(format!("{} XOR {} -> {}", carry, sum, z));
+            lines.push(format!("{} AND {} -> {}", sum, carry, propagate));
+            lines.push(format!("{} OR {} -> {}", generate, propagate, carry_out));
+            stages.push(vec![sum, generate, z, propagate, carry_out.clone()]);
+            carry = carry_out;
+        }
+        rng.shuffle(&mut lines);
+        (s + &lines.join("\n") + "\n", stages)
+    }
+
+    #[test]
+    fn test_correct_adder() {
+        let mut rng = Rng::new(1);
+        let (data, _) = ripple_adder(&mut rng, 8);
+        let mut adder = Adder::new(Netlist::parse(&data).unwrap()).unwrap();
+        assert_eq!(adder.first_fault(), None);
+        assert_eq!(adder.netlist().evaluate(200, 100), 300);
+        assert_eq!(adder.repair().unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_repair_random_swaps() {
+        let mut rng = Rng::from_env();
+        for _ in 0..50 {
+            let bits = rng.usize(4, 16);
+            let (data, stages) = ripple_adder(&mut rng, bits);
+            let mut adder = Adder::new(Netlist::parse(&data).unwrap()).unwrap();
+
+            let mut faulty = (0..bits).collect::<Vec<_>>();
+            rng.shuffle(&mut faulty);
+            let mut expected = vec![];
+            for &bit in faulty.iter().take(rng.usize(1, 3)) {
+                // Swapping generate and propagate, the inputs of the carry OR gate, changes
+                // nothing, and swaps that create a loop are rejected.
+                let stage = &stages[bit];
+                let (a, b) = loop {
+                    let (a, b) = (rng.below(stage.len() as u64), rng.below(stage.len() as u64));
+                    let (a, b) = (a as usize, b as usize);
+                    if a < b && (a, b) != (1, 3) && adder.swap(&stage[a], &stage[b]).is_ok() {
+                        break (&stage[a], &stage[b]);
+                    }
+                };
+                expected.extend([a.clone(), b.clone()]);
+            }
+            assert!(adder.first_fault().is_some());
+
+            let mut found = adder
+                .repair()
+                .unwrap()
+                .into_iter()
+                .flat_map(|repair| [repair.wires.0, repair.wires.1])
+                .collect::<Vec<_>>();
+            found.sort();
+            expected.sort();
+            assert_eq!(found, expected, "{}", data);
+        }
+    }
+
+    #[test]
+    fn test_repair_two_swaps_in_one_bit() {
+        let mut rng = Rng::new(1);
+        let (data, stages) = ripple_adder(&mut rng, 6);
+        let mut adder = Adder::new(Netlist::parse(&data).unwrap()).unwrap();
+        // Bit 2 has its half sum and carry generate swapped, and its z and carry propagate.
+        let swaps = [(2, 0, 1), (2, 2, 3), (4, 2, 4)];
+        for &(bit, a, b) in &swaps {
+            adder.swap(&stages[bit][a], &stages[bit][b]).unwrap();
+        }
+
+        // No single swap gets the first fault past bit 2, so repairing one swap at a time
+        // gives up there.
+        let (bit, carry_in) = adder.first_fault().unwrap();
+        assert_eq!(bit, 2);
+        let candidates = adder.candidates(bit, carry_in);
+        for (i, a) in candidates.iter().enumerate() {
+            for b in &candidates[i + 1..] {
+                if adder.swap(a, b).is_ok() {
+                    assert_eq!(adder.first_fault().map(|(fault, _)| fault), Some(2));
+                    adder.swap(a, b).unwrap();
+                }
+            }
+        }
+
+        let mut found = adder
+            .repair()
+            .unwrap()
+            .into_iter()
+            .map(|repair| repair.wires)
+            .collect::<Vec<_>>();
+        let mut expected = swaps
+            .iter()
+            .map(|&(bit, a, b)| (stages[bit][a].clone(), stages[bit][b].clone()))
+            .collect::<Vec<_>>();
+        for pair in found.iter_mut().chain(&mut expected) {
+            if pair.0 > pair.1 {
+                *pair = (pair.1.clone(), pair.0.clone());
+            }
+        }
+        found.sort();
+        expected.sort();
+        assert_eq!(found, expected);
+        assert_eq!(adder.first_fault(), None);
+    }
+}
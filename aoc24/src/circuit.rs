@@ -0,0 +1,339 @@
+//! The circuit language: day 24 input extended with more gates, constants and macros.
+//!
+//! Initial values come first, one `<wire>: <0 or 1>` per line, followed by a blank line and
+//! the gates.  A gate is written either infix like the puzzle input, `a AND b -> c`, or prefix
+//! with any number of inputs, `NAND a b c -> d`, `NOT a -> b` or `MUX s a b -> c`.  The wires
+//! `0` and `1` are constants.  A macro is a named sub-circuit,
+//!
+//! ```text
+//! def HALF a b -> sum carry
+//!   a XOR b -> sum
+//!   a AND b -> carry
+//! end
+//! ```
+//!
+//! which is used like a gate with several outputs, `HALF x00 y00 -> z00 c00`.  Its inner wires
+//! are renamed for every use.  A macro can only use gates and macros defined before it, and
+//! lines starting with `#` are comments.
+
+use crate::{
+    gates::Op,
+    netlist::{self, Builder, Netlist},
+};
+use std::collections::HashMap;
+
+struct Macro<'a> {
+    inputs: Vec<&'a str>,
+    outputs: Vec<&'a str>,
+    body: Vec<aoc::Line<'a>>,
+}
+
+// One use of a macro: the prefix for its inner wires, and the actual wires its parameters are
+// bound to.
+struct Scope<'a, 'b> {
+    prefix: String,
+    bindings: HashMap<&'a str, &'b str>,
+}
+
+impl Scope<'_, '_> {
+    fn resolve(&self, name: &str) -> String {
+        match self.bindings.get(name) {
+            Some(wire) => wire.to_string(),
+            None if self.prefix.is_empty() || name == "0" || name == "1" => name.to_owned(),
+            None => format!("{}{}", self.prefix, name),
+        }
+    }
+}
+
+struct Parser<'a> {
+    builder: Builder,
+    macros: HashMap<&'a str, Macro<'a>>,
+    instances: usize,
+}
+
+// A gate line split into its operation or macro name, inputs and outputs, after skipping
+// `skip` leading fields.
+fn split_statement<'a>(
+    line: &aoc::Line<'a>,
+    skip: usize,
+) -> aoc::Result<(&'a str, Vec<&'a str>, Vec<&'a str>)> {
+    let fields = line
+        .text
+        .split_ascii_whitespace()
+        .skip(skip)
+        .collect::<Vec<_>>();
+    let Some(arrow) = fields.iter().position(|&field| field == "->") else {
+        return Err(line.error(line.text, "expected \"->\""));
+    };
+    let (lhs, outputs) = (&fields[..arrow], fields[arrow + 1..].to_vec());
+    match lhs {
+        &[input1, op, input2] if Op::from_name(op).is_some() => {
+            Ok((op, vec![input1, input2], outputs))
+        }
+        [name, inputs @ ..] => Ok((name, inputs.to_vec(), outputs)),
+        [] => Err(line.error(line.text, "expected \"<wire> <op> <wire> -> <wire>\"")),
+    }
+}
+
+impl<'a> Parser<'a> {
+    fn statement(&mut self, line: &aoc::Line<'a>, scope: &Scope) -> aoc::Result<()> {
+        let (name, inputs, outputs) = split_statement(line, 0)?;
+        let inputs = inputs
+            .iter()
+            .map(|&input| scope.resolve(input))
+            .collect::<Vec<_>>();
+        let inputs = inputs.iter().map(String::as_str).collect::<Vec<_>>();
+
+        if let Some(op) = Op::from_name(name) {
+            let &[output] = outputs.as_slice() else {
+                return Err(line.error(name, format!("{} has one output", name)));
+            };
+            let output = scope.resolve(output);
+            self.builder
+                .gate(op, &inputs, &output)
+                .map_err(netlist::located(line, name))?;
+            return Ok(());
+        }
+
+        let Some(def) = self.macros.get(name) else {
+            return Err(line.error(name, format!("unknown gate or macro {:?}", name)));
+        };
+        if inputs.len() != def.inputs.len() || outputs.len() != def.outputs.len() {
+            return Err(line.error(
+                name,
+                format!(
+                    "{} takes {} inputs and {} outputs",
+                    name,
+                    def.inputs.len(),
+                    def.outputs.len()
+                ),
+            ));
+        }
+        let outputs = outputs
+            .iter()
+            .map(|&output| scope.resolve(output))
+            .collect::<Vec<_>>();
+
+        self.instances += 1;
+        let inner = Scope {
+            prefix: format!("_{}{}_", name, self.instances),
+            bindings: def
+                .inputs
+                .iter()
+                .copied()
+                .zip(inputs.iter().copied())
+                .chain(
+                    def.outputs
+                        .iter()
+                        .copied()
+                        .zip(outputs.iter().map(String::as_str)),
+                )
+                .collect(),
+        };
+        let body = def.body.clone();
+        for body_line in &body {
+            self.statement(body_line, &inner)?;
+        }
+        Ok(())
+    }
+
+    // Reads the body of the macro defined on `line`, up to its `end`.
+    fn define(
+        &mut self,
+        line: &aoc::Line<'a>,
+        lines: &mut impl Iterator<Item = aoc::Line<'a>>,
+    ) -> aoc::Result<()> {
+        let (name, inputs, outputs) = split_statement(line, 1)?;
+        if Op::from_name(name).is_some() || self.macros.contains_key(name) {
+            return Err(line.error(name, format!("{} is already defined", name)));
+        }
+
+        let mut body = vec![];
+        loop {
+            let Some(body_line) = lines.next() else {
+                return Err(line.error(name, format!("{} has no end", name)));
+            };
+            let text = body_line.text.trim();
+            if text == "end" {
+                break;
+            }
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+            // Check the names now so a macro can't use itself or later macros.
+            let (callee, _, _) = split_statement(&body_line, 0)?;
+            if Op::from_name(callee).is_none() && !self.macros.contains_key(callee) {
+                return Err(body_line.error(callee, format!("unknown gate or macro {:?}", callee)));
+            }
+            body.push(body_line);
+        }
+
+        self.macros.insert(
+            name,
+            Macro {
+                inputs,
+                outputs,
+                body,
+            },
+        );
+        Ok(())
+    }
+}
+
+pub fn parse(data: &str) -> aoc::Result<Netlist> {
+    let mut parser = Parser {
+        builder: Builder::new(),
+        macros: HashMap::new(),
+        instances: 0,
+    };
+
+    let mut lines = aoc::lines(data);
+    for input_line in lines.by_ref().take_while(|line| !line.text.is_empty()) {
+        let (name, val) = input_line.split_once(": ")?;
+        let val = match val {
+            "0" => false,
+            "1" => true,
+            _ => return Err(input_line.error(val, "expected 0 or 1")),
+        };
+        parser
+            .builder
+            .input(name, val)
+            .map_err(netlist::located(&input_line, name))?;
+    }
+
+    let top = Scope {
+        prefix: String::new(),
+        bindings: HashMap::new(),
+    };
+    while let Some(line) = lines.next() {
+        let text = line.text.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+        if text.starts_with("def ") {
+            parser.define(&line, &mut lines)?;
+        } else {
+            parser.statement(&line, &top)?;
+        }
+    }
+
+    parser.builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim;
+
+    const FULL_ADDER: &str = "\
+def HALF a b -> sum carry
+  a XOR b -> sum
+  a AND b -> carry
+end
+def FULL a b c -> sum carry
+  HALF a b -> partial generate
+  HALF partial c -> sum propagate
+  generate OR propagate -> carry
+end
+";
+
+    // An n-bit ripple-carry circuit built from `stage`, which maps bit i's inputs and carry to
+    // output bit i and the next carry.
+    fn ripple(bits: usize, stage: &str, carry_in: &str) -> String {
+        let mut data = String::new();
+        for prefix in ["x", "y"] {
+            for bit in 0..bits {
+                data += &format!("{}{:02}: 0\n", prefix, bit);
+            }
+        }
+        data.push('\n');
+        data += FULL_ADDER;
+        let mut carry = carry_in.to_owned();
+        for bit in 0..bits {
+            let next = format!("c{:02}", bit);
+            data += &format!(
+                "{} x{:02} y{:02} {} -> z{:02} {}\n",
+                stage, bit, bit, carry, bit, next
+            );
+            carry = next;
+        }
+        data
+    }
+
+    #[test]
+    fn test_adder_macro() {
+        let mut data = ripple(6, "FULL", "0");
+        data += "BUF c05 -> z06\n";
+        let netlist = parse(&data).unwrap();
+        assert!(sim::check_adder(&netlist, sim::exhaustive(6).unwrap()).is_ok());
+        assert!(netlist.wire("_HALF2_partial").is_none());
+        assert!(netlist.wire("_FULL1_partial").is_some());
+    }
+
+    #[test]
+    fn test_subtractor() {
+        // x - y is x + !y + 1, and the final carry is set when there is no borrow.
+        let stage =
+            "def SUB a b c -> diff carry\n  NOT b -> nb\n  FULL a nb c -> diff carry\nend\n";
+        let data = ripple(5, "SUB", "1").replacen(FULL_ADDER, &(FULL_ADDER.to_owned() + stage), 1);
+        let data = data + "NOT c04 -> z05\n";
+        let netlist = parse(&data).unwrap();
+        let report = sim::check(&netlist, sim::exhaustive(5).unwrap(), |x, y| {
+            x.wrapping_sub(y)
+        });
+        assert!(report.is_ok(), "{:?}", report.mismatches.first());
+    }
+
+    #[test]
+    fn test_comparator() {
+        // z00 is x < y, z01 is x == y, for 3 bit numbers compared from the top bit down.
+        let mut data = String::new();
+        for prefix in ["x", "y"] {
+            for bit in 0..3 {
+                data += &format!("{}{:02}: 0\n", prefix, bit);
+            }
+        }
+        data += "
+def CMP a b lt_in eq_in -> lt eq
+  NOT a -> na
+  AND na b eq_in -> less
+  less OR lt_in -> lt
+  XNOR a b -> same
+  same AND eq_in -> eq
+end
+CMP x02 y02 0 1 -> lt2 eq2
+CMP x01 y01 lt2 eq2 -> lt1 eq1
+CMP x00 y00 lt1 eq1 -> z00 z01
+";
+        let netlist = parse(&data).unwrap();
+        let report = sim::check(&netlist, sim::exhaustive(3).unwrap(), |x, y| {
+            (x < y) as u64 | ((x == y) as u64) << 1
+        });
+        assert!(report.is_ok(), "{:?}", report.mismatches.first());
+    }
+
+    #[test]
+    fn test_gates() {
+        let data = "x00: 1\nx01: 0\nx02: 1\n\n\
+                    NAND x00 x01 x02 -> z00\n\
+                    NOR x00 x01 -> z01\n\
+                    XNOR x00 x02 -> z02\n\
+                    MUX x01 x00 0 -> z03\n\
+                    x00 XOR 1 -> z04\n";
+        let netlist = parse(data).unwrap();
+        assert_eq!(netlist.evaluate_initial(), 0b01101);
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |data: &str| parse(data).unwrap_err().to_string();
+        assert!(error("x00: 1\n\nNOT x00 x00 -> z00\n").contains("NOT does not take 2 inputs"));
+        assert!(error("x00: 1\n\nx00 AND x00 -> z00 z01\n").contains("AND has one output"));
+        assert!(error("x00: 1\n\nFOO x00 -> z00\n").contains("unknown gate or macro \"FOO\""));
+        assert!(error("x00: 1\n\nNOT x00 -> 1\n").contains("cannot drive constant 1"));
+        assert!(error("x00: 1\n\ndef A a -> b\n  A a -> b\nend\n").contains("line 4"));
+        assert!(error("x00: 1\n\ndef A a -> b\n  NOT a -> b\n").contains("A has no end"));
+        assert!(error(&format!("x00: 1\n\n{}HALF x00 -> z00\n", FULL_ADDER))
+            .contains("HALF takes 2 inputs and 2 outputs"));
+    }
+}
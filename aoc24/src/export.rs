@@ -0,0 +1,463 @@
+//! Graphviz, Verilog and BLIF views of a netlist, and a parser for the BLIF they produce.
+//!
+//! The BLIF subset is `.model`, `.inputs`, `.outputs`, `.end`, constant tables for the wires
+//! `0` and `1`, and `.names` tables whose on-set matches one of the library gates.
+
+use crate::{
+    gates::Op,
+    netlist::{self, Builder, Netlist, WireId},
+};
+use std::{collections::BTreeMap, fmt::Write};
+
+// Library gates are matched against BLIF tables by enumerating their truth tables.
+const MAX_TABLE_INPUTS: usize = 8;
+
+fn inputs(netlist: &Netlist) -> impl Iterator<Item = WireId> + '_ {
+    (0..netlist.wire_count())
+        .filter(|&wire| netlist.initial(wire).is_some() && !netlist.is_constant(wire))
+}
+
+fn outputs(netlist: &Netlist) -> impl Iterator<Item = WireId> + '_ {
+    netlist.z_wires().iter().map(|&(_, wire)| wire)
+}
+
+fn constants(netlist: &Netlist) -> impl Iterator<Item = WireId> + '_ {
+    ["0", "1"].into_iter().filter_map(|name| netlist.wire(name))
+}
+
+/// The highest `x` or `y` bit each gate depends on, which for an adder is the bit it belongs
+/// to.
+fn gate_bits(netlist: &Netlist) -> Vec<Option<usize>> {
+    let mut wire_bits = vec![None; netlist.wire_count()];
+    for &(bit, wire) in netlist.x_wires().iter().chain(netlist.y_wires()) {
+        wire_bits[wire] = Some(bit);
+    }
+    let mut bits = vec![None; netlist.gates().len()];
+    for &id in netlist.order() {
+        let gate = &netlist.gates()[id];
+        let bit = gate.inputs.iter().filter_map(|&wire| wire_bits[wire]).max();
+        wire_bits[gate.output] = bit;
+        bits[id] = bit;
+    }
+    bits
+}
+
+/// A Graphviz digraph with a node per gate, colored by operation and clustered by the bit it
+/// belongs to.  Gates driving the `highlight` wires are drawn in red.
+pub fn to_dot(netlist: &Netlist, highlight: &[WireId]) -> String {
+    let mut dot = String::from("digraph circuit {\n    rankdir=LR;\n");
+    dot += "    node [style=filled];\n";
+    for wire in inputs(netlist) {
+        let name = netlist.name(wire);
+        writeln!(dot, "    {} [shape=box, fillcolor=white];", name).unwrap();
+    }
+    for wire in constants(netlist) {
+        let name = netlist.name(wire);
+        writeln!(dot, "    \"{}\" [shape=plaintext, fillcolor=white];", name).unwrap();
+    }
+    for wire in outputs(netlist) {
+        let name = netlist.name(wire);
+        writeln!(dot, "    {} [shape=doublecircle, fillcolor=white];", name).unwrap();
+    }
+
+    let mut clusters = BTreeMap::<Option<usize>, Vec<usize>>::new();
+    for (id, bit) in gate_bits(netlist).into_iter().enumerate() {
+        clusters.entry(bit).or_default().push(id);
+    }
+    for (bit, ids) in clusters {
+        let indent = if let Some(bit) = bit {
+            writeln!(dot, "    subgraph cluster_{:02} {{", bit).unwrap();
+            writeln!(dot, "        label=\"bit {}\";", bit).unwrap();
+            "        "
+        } else {
+            "    "
+        };
+        for id in ids {
+            let gate = &netlist.gates()[id];
+            let color = match gate.op {
+                Op::And | Op::Nand => "lightblue",
+                Op::Or | Op::Nor => "palegreen",
+                Op::Xor | Op::Xnor => "orange",
+                Op::Not | Op::Buf | Op::Mux => "lightgrey",
+            };
+            let mut attrs = format!(
+                "label=\"{}\\n{}\", fillcolor={}",
+                gate.op.name(),
+                netlist.name(gate.output),
+                color
+            );
+            if highlight.contains(&gate.output) {
+                attrs += ", color=red, penwidth=3";
+            }
+            writeln!(dot, "{}g{} [{}];", indent, id, attrs).unwrap();
+        }
+        if bit.is_some() {
+            dot += "    }\n";
+        }
+    }
+
+    let node = |wire: WireId| match netlist.driver(wire) {
+        Some(id) => format!("g{}", id),
+        None => format!("\"{}\"", netlist.name(wire)),
+    };
+    for (id, gate) in netlist.gates().iter().enumerate() {
+        for &input in &gate.inputs {
+            writeln!(dot, "    {} -> g{};", node(input), id).unwrap();
+        }
+    }
+    for wire in outputs(netlist) {
+        writeln!(dot, "    {} -> {};", node(wire), netlist.name(wire)).unwrap();
+    }
+    dot += "}\n";
+    dot
+}
+
+/// A structural Verilog module built from gate primitives, with an `assign` for each MUX.
+pub fn to_verilog(netlist: &Netlist, module: &str) -> String {
+    let name = |wire: WireId| match netlist.name(wire) {
+        "0" => "1'b0",
+        "1" => "1'b1",
+        name => name,
+    };
+    let mut ports = inputs(netlist)
+        .map(|wire| format!("input {}", name(wire)))
+        .collect::<Vec<_>>();
+    ports.extend(outputs(netlist).map(|wire| format!("output {}", name(wire))));
+    let mut verilog = format!("module {}({});\n", module, ports.join(", "));
+
+    for gate in netlist.gates() {
+        if !netlist.name(gate.output).starts_with('z') {
+            writeln!(verilog, "  wire {};", name(gate.output)).unwrap();
+        }
+    }
+    for (id, gate) in netlist.gates().iter().enumerate() {
+        let inputs = gate
+            .inputs
+            .iter()
+            .map(|&wire| name(wire))
+            .collect::<Vec<_>>();
+        if gate.op == Op::Mux {
+            writeln!(
+                verilog,
+                "  assign {} = {} ? {} : {};",
+                name(gate.output),
+                inputs[0],
+                inputs[2],
+                inputs[1]
+            )
+            .unwrap();
+        } else {
+            writeln!(
+                verilog,
+                "  {} g{}({}, {});",
+                gate.op.name().to_lowercase(),
+                id,
+                name(gate.output),
+                inputs.join(", ")
+            )
+            .unwrap();
+        }
+    }
+    verilog += "endmodule\n";
+    verilog
+}
+
+// Every input pattern of `n` inputs, in counting order with the first input as the high bit.
+fn patterns(n: usize) -> impl Iterator<Item = Vec<bool>> {
+    (0..1usize << n).map(move |i| {
+        (0..n)
+            .map(|input| (i >> (n - 1 - input)) & 1 == 1)
+            .collect()
+    })
+}
+
+fn on_set(op: Op, n: usize) -> Vec<Vec<bool>> {
+    patterns(n).filter(|pattern| op.apply(pattern)).collect()
+}
+
+/// A BLIF model with one `.names` table per gate, in topological order.
+pub fn to_blif(netlist: &Netlist, model: &str) -> String {
+    let names = |wires: &mut dyn Iterator<Item = WireId>| {
+        wires
+            .map(|wire| netlist.name(wire))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let mut blif = format!(".model {}\n", model);
+    writeln!(blif, ".inputs {}", names(&mut inputs(netlist))).unwrap();
+    writeln!(blif, ".outputs {}", names(&mut outputs(netlist))).unwrap();
+    for wire in constants(netlist) {
+        match netlist.name(wire) {
+            "0" => blif += ".names 0\n",
+            _ => blif += ".names 1\n1\n",
+        }
+    }
+    for &id in netlist.order() {
+        let gate = &netlist.gates()[id];
+        writeln!(
+            blif,
+            ".names {} {}",
+            names(&mut gate.inputs.iter().copied()),
+            netlist.name(gate.output)
+        )
+        .unwrap();
+        for pattern in on_set(gate.op, gate.inputs.len()) {
+            let row = pattern.iter().map(|&val| if val { '1' } else { '0' });
+            writeln!(blif, "{} 1", row.collect::<String>()).unwrap();
+        }
+    }
+    blif += ".end\n";
+    blif
+}
+
+// A `.names` line being read, and the input patterns of its on-set so far.
+struct Table<'a> {
+    line: aoc::Line<'a>,
+    names: Vec<&'a str>,
+    on_set: Vec<Vec<bool>>,
+}
+
+impl Table<'_> {
+    fn finish(mut self, builder: &mut Builder) -> aoc::Result<()> {
+        let (&output, inputs) = self.names.split_last().unwrap();
+        self.on_set.sort();
+        self.on_set.dedup();
+
+        if inputs.is_empty() {
+            let val = !self.on_set.is_empty();
+            if output != if val { "1" } else { "0" } {
+                return Err(self.line.error(
+                    output,
+                    "constant tables are only supported for the wires 0 and 1",
+                ));
+            }
+            return Ok(());
+        }
+
+        let Some(op) = Op::ALL
+            .into_iter()
+            .filter(|op| op.accepts(inputs.len()))
+            .find(|&op| on_set(op, inputs.len()) == self.on_set)
+        else {
+            return Err(self
+                .line
+                .error(self.line.text, "cover is not a library gate"));
+        };
+        builder
+            .gate(op, inputs, output)
+            .map_err(netlist::located(&self.line, output))?;
+        Ok(())
+    }
+}
+
+/// Parses the BLIF subset written by [`to_blif`].  Inputs start out as 0.
+pub fn parse_blif(data: &str) -> aoc::Result<Netlist> {
+    let mut builder = Builder::new();
+    let mut outputs = vec![];
+    let mut table: Option<Table> = None;
+
+    let mut ended = false;
+    for line in aoc::lines(data) {
+        let text = line.text.split('#').next().unwrap().trim();
+        if text.is_empty() {
+            continue;
+        }
+        if ended {
+            return Err(line.error(text, "text after .end"));
+        }
+        let fields = text.split_ascii_whitespace().collect::<Vec<_>>();
+        if !fields[0].starts_with('.') {
+            let Some(table) = table.as_mut() else {
+                return Err(line.error(text, "cover row outside .names"));
+            };
+            let inputs = table.names.len() - 1;
+            let (pattern, out) = match *fields.as_slice() {
+                [out] if inputs == 0 => ("", out),
+                [pattern, out] => (pattern, out),
+                _ => return Err(line.error(text, "expected an on-set row like \"1- 1\"")),
+            };
+            if out != "1" {
+                return Err(line.error(out, "only on-set rows are supported"));
+            }
+            if pattern.len() != inputs {
+                return Err(line.error(pattern, format!("expected {} inputs", inputs)));
+            }
+            let mut expanded = vec![vec![]];
+            for c in pattern.bytes() {
+                let values = match c {
+                    b'0' => vec![false],
+                    b'1' => vec![true],
+                    b'-' => vec![false, true],
+                    _ => return Err(line.error(pattern, "expected 0, 1 or -")),
+                };
+                expanded = expanded
+                    .into_iter()
+                    .flat_map(|prefix: Vec<bool>| {
+                        values.iter().map(move |&val| {
+                            let mut pattern = prefix.clone();
+                            pattern.push(val);
+                            pattern
+                        })
+                    })
+                    .collect();
+            }
+            table.on_set.extend(expanded);
+            continue;
+        }
+
+        if let Some(table) = table.take() {
+            table.finish(&mut builder)?;
+        }
+        match fields[0] {
+            ".model" => {}
+            ".inputs" => {
+                for &name in &fields[1..] {
+                    builder
+                        .input(name, false)
+                        .map_err(netlist::located(&line, name))?;
+                }
+            }
+            ".outputs" => outputs.extend(fields[1..].iter().map(|&name| (line, name))),
+            ".names" => {
+                if fields.len() < 2 || fields.len() - 2 > MAX_TABLE_INPUTS {
+                    return Err(line.error(
+                        text,
+                        format!("expected an output and at most {} inputs", MAX_TABLE_INPUTS),
+                    ));
+                }
+                table = Some(Table {
+                    line,
+                    names: fields[1..].to_vec(),
+                    on_set: vec![],
+                });
+            }
+            ".end" => ended = true,
+            directive => {
+                return Err(line.error(directive, format!("unsupported {}", directive)));
+            }
+        }
+    }
+    if let Some(table) = table {
+        table.finish(&mut builder)?;
+    }
+
+    let netlist = builder.build()?;
+    for (line, name) in outputs {
+        if netlist.wire(name).is_none() {
+            return Err(line.error(name, format!("output {} is never driven", name)));
+        }
+    }
+    Ok(netlist)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{adder, sim};
+    use aoc::testing::Rng;
+    use std::fs::read_to_string;
+
+    const HALF_ADDER: &str = "x00: 0\ny00: 0\n\nx00 XOR y00 -> z00\nx00 AND y00 -> z01\n";
+
+    #[test]
+    fn test_verilog() {
+        let netlist = Netlist::parse(HALF_ADDER).unwrap();
+        assert_eq!(
+            to_verilog(&netlist, "half_adder"),
+            "module half_adder(input x00, input y00, output z00, output z01);\n  \
+             xor g0(z00, x00, y00);\n  and g1(z01, x00, y00);\nendmodule\n"
+        );
+    }
+
+    #[test]
+    fn test_dot() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let netlist = Netlist::parse(&data).unwrap();
+        let suspicious = adder::suspicious_wires(&netlist);
+        let names = suspicious
+            .iter()
+            .map(|&wire| netlist.name(wire))
+            .collect::<Vec<_>>();
+        assert_eq!(names.join(","), "fgt,fpq,nqk,pcp,srn,z07,z24,z32");
+        let dot = to_dot(&netlist, &suspicious);
+        assert!(dot.contains("subgraph cluster_44 {"));
+        assert_eq!(dot.matches("color=red").count(), 8);
+        assert_eq!(dot.matches(" -> g").count(), 2 * netlist.gates().len());
+    }
+
+    #[test]
+    fn test_blif_round_trip() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let netlist = Netlist::parse(&data).unwrap();
+        let blif = to_blif(&netlist, "day24");
+        let parsed = parse_blif(&blif).unwrap();
+        assert_eq!(parsed.gates().len(), netlist.gates().len());
+        assert_eq!(to_blif(&parsed, "day24"), blif);
+
+        let vectors = sim::random(&mut Rng::new(1), 45, 256);
+        let report = sim::check(&parsed, vectors, |x, y| netlist.evaluate(x, y));
+        assert!(report.is_ok());
+    }
+
+    #[test]
+    fn test_library_round_trip() {
+        let data = "x00: 1\nx01: 0\nx02: 1\n\n\
+                    NAND x00 x01 x02 -> a\n\
+                    NOR x00 a -> b\n\
+                    XNOR b x02 1 -> c\n\
+                    MUX c x00 0 -> z00\n\
+                    NOT c -> z01\n\
+                    BUF a -> z02\n";
+        let netlist = Netlist::parse(data).unwrap();
+        let blif = to_blif(&netlist, "library");
+        assert!(blif.contains(".names 0\n.names 1\n1\n"));
+        let parsed = parse_blif(&blif).unwrap();
+        assert_eq!(to_blif(&parsed, "library"), blif);
+        let ops = |netlist: &Netlist| {
+            let mut ops = netlist
+                .gates()
+                .iter()
+                .map(|gate| gate.op.name())
+                .collect::<Vec<_>>();
+            ops.sort();
+            ops
+        };
+        assert_eq!(ops(&parsed), ops(&netlist));
+        let report = sim::check(&parsed, sim::exhaustive(3).unwrap(), |x, y| {
+            netlist.evaluate(x, y)
+        });
+        assert!(report.is_ok());
+
+        let verilog = to_verilog(&netlist, "library");
+        assert!(verilog.contains("  xnor g2(c, b, x02, 1'b1);\n"));
+        assert!(verilog.contains("  assign z00 = c ? 1'b0 : x00;\n"));
+        assert!(to_dot(&netlist, &[]).contains("\"1\" -> g2;"));
+    }
+
+    #[test]
+    fn test_blif_errors() {
+        let blif = ".model m\n.inputs a b\n.outputs z00\n.names a b z00\n11 0\n.end\n";
+        let err = parse_blif(blif).unwrap_err().to_string();
+        assert!(err.contains("line 5"), "{}", err);
+
+        let blif = ".model m\n.inputs a b\n.outputs z00\n.names a b z00\n1- 1\n.end\n";
+        let err = parse_blif(blif).unwrap_err().to_string();
+        assert!(err.contains("cover is not a library gate"), "{}", err);
+
+        let blif = ".model m\n.inputs a b\n.outputs z00\n.names a b z00\n0- 1\n-0 1\n.end\n";
+        let netlist = parse_blif(blif).unwrap();
+        assert_eq!(netlist.gates()[0].op, Op::Nand);
+
+        let blif = ".model m\n.inputs a\n.outputs z00\n.names c\n1\n.names a c z00\n11 1\n.end\n";
+        let err = parse_blif(blif).unwrap_err().to_string();
+        assert!(
+            err.contains("only supported for the wires 0 and 1"),
+            "{}",
+            err
+        );
+
+        let blif = ".model m\n.inputs a b\n.outputs z00 z01\n.names a b z00\n01 1\n10 1\n.end\n";
+        let err = parse_blif(blif).unwrap_err().to_string();
+        assert!(err.contains("z01 is never driven"), "{}", err);
+    }
+}
@@ -0,0 +1,124 @@
+//! The gate library: every operation a netlist gate can perform.
+//!
+//! Gates are evaluated word-wise, so the same code serves single evaluations (only the low
+//! bit matters) and the 64-lane simulator.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Op {
+    And,
+    Or,
+    Xor,
+    Nand,
+    Nor,
+    Xnor,
+    Not,
+    Buf,
+    /// `MUX s a b` is `a` when `s` is 0 and `b` when it is 1.
+    Mux,
+}
+
+impl Op {
+    pub const ALL: [Op; 9] = [
+        Op::And,
+        Op::Or,
+        Op::Xor,
+        Op::Nand,
+        Op::Nor,
+        Op::Xnor,
+        Op::Not,
+        Op::Buf,
+        Op::Mux,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Op::And => "AND",
+            Op::Or => "OR",
+            Op::Xor => "XOR",
+            Op::Nand => "NAND",
+            Op::Nor => "NOR",
+            Op::Xnor => "XNOR",
+            Op::Not => "NOT",
+            Op::Buf => "BUF",
+            Op::Mux => "MUX",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Op> {
+        Self::ALL.into_iter().find(|op| op.name() == name)
+    }
+
+    /// The smallest and largest number of inputs, `None` if there is no limit.
+    pub fn arity(self) -> (usize, Option<usize>) {
+        match self {
+            Op::Not | Op::Buf => (1, Some(1)),
+            Op::Mux => (3, Some(3)),
+            _ => (2, None),
+        }
+    }
+
+    pub fn accepts(self, inputs: usize) -> bool {
+        let (min, max) = self.arity();
+        inputs >= min && max.is_none_or(|max| inputs <= max)
+    }
+
+    /// Applies the gate to 64 independent lanes at once.
+    pub fn apply_word(self, inputs: &[u64]) -> u64 {
+        let and = || inputs.iter().fold(!0, |acc, &val| acc & val);
+        let or = || inputs.iter().fold(0, |acc, &val| acc | val);
+        let xor = || inputs.iter().fold(0, |acc, &val| acc ^ val);
+        match self {
+            Op::And => and(),
+            Op::Or => or(),
+            Op::Xor => xor(),
+            Op::Nand => !and(),
+            Op::Nor => !or(),
+            Op::Xnor => !xor(),
+            Op::Not => !inputs[0],
+            Op::Buf => inputs[0],
+            Op::Mux => (!inputs[0] & inputs[1]) | (inputs[0] & inputs[2]),
+        }
+    }
+
+    pub fn apply(self, inputs: &[bool]) -> bool {
+        let words = inputs.iter().map(|&val| val as u64).collect::<Vec<_>>();
+        self.apply_word(&words) & 1 == 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truth_tables() {
+        let table = |op: Op| {
+            [[false, false], [false, true], [true, false], [true, true]]
+                .map(|inputs| op.apply(&inputs))
+        };
+        assert_eq!(table(Op::And), [false, false, false, true]);
+        assert_eq!(table(Op::Or), [false, true, true, true]);
+        assert_eq!(table(Op::Xor), [false, true, true, false]);
+        assert_eq!(table(Op::Nand), [true, true, true, false]);
+        assert_eq!(table(Op::Nor), [true, false, false, false]);
+        assert_eq!(table(Op::Xnor), [true, false, false, true]);
+        assert!(Op::Not.apply(&[false]));
+        assert!(Op::Buf.apply(&[true]));
+        assert!(!Op::Mux.apply(&[false, false, true]));
+        assert!(Op::Mux.apply(&[true, false, true]));
+        assert!(Op::Xor.apply(&[true, true, true]));
+        assert!(!Op::And.apply(&[true, true, false]));
+    }
+
+    #[test]
+    fn test_registry() {
+        for op in Op::ALL {
+            assert_eq!(Op::from_name(op.name()), Some(op));
+        }
+        assert_eq!(Op::from_name("and"), None);
+        assert!(Op::Or.accepts(5));
+        assert!(!Op::Or.accepts(1));
+        assert!(!Op::Not.accepts(2));
+        assert!(Op::Mux.accepts(3));
+    }
+}
@@ -0,0 +1,188 @@
+pub mod adder;
+pub mod circuit;
+pub mod export;
+pub mod gates;
+pub mod netlist;
+pub mod sim;
+
+use aoc::Solution;
+use netlist::Netlist;
+
+pub fn parse_input(data: &str) -> aoc::Result<Netlist> {
+    Netlist::parse(data)
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    fn part1(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        let netlist = parse_input(input)?;
+        Ok(netlist.evaluate_initial().to_string())
+    }
+
+    fn part2(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        adder::swapped_wires(parse_input(input)?)
+    }
+}
+
+aoc::example_tests!(Day24);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::testing::{self, Rng};
+    use std::{collections::HashMap, fs::read_to_string};
+
+    // Evaluates the gate lines by repeatedly applying every gate whose inputs are known.
+    fn reference_evaluate(data: &str) -> u64 {
+        let (inputs, gates) = data.split_once("\n\n").unwrap();
+        let mut values = inputs
+            .lines()
+            .map(|line| {
+                let (name, val) = line.split_once(": ").unwrap();
+                (name.to_owned(), val == "1")
+            })
+            .collect::<HashMap<_, _>>();
+        let gates = gates
+            .lines()
+            .map(|line| line.split(' ').collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        while values.len() < gates.len() + inputs.lines().count() {
+            for gate in &gates {
+                let (Some(&a), Some(&b)) = (values.get(gate[0]), values.get(gate[2])) else {
+                    continue;
+                };
+                let val = match gate[1] {
+                    "AND" => a && b,
+                    "OR" => a || b,
+                    _ => a ^ b,
+                };
+                values.insert(gate[4].to_owned(), val);
+            }
+        }
+        values
+            .iter()
+            .filter(|(name, &val)| name.starts_with('z') && val)
+            .map(|(name, _)| 1 << name[1..].parse::<u64>().unwrap())
+            .sum()
+    }
+
+    #[test]
+    fn test_evaluate() {
+        testing::differential(
+            "netlist",
+            200,
+            |rng| {
+                let bits = rng.usize(1, 8);
+                let gates = rng.usize(bits + 1, 40);
+                (rng.next_u64(), (bits as u64, gates as u64))
+            },
+            |&(seed, (bits, gates))| {
+                let data = testing::netlist(&mut Rng::new(seed), bits as usize, gates as usize);
+                reference_evaluate(&data)
+            },
+            |&(seed, (bits, gates))| {
+                let data = testing::netlist(&mut Rng::new(seed), bits as usize, gates as usize);
+                parse_input(&data).unwrap().evaluate_initial()
+            },
+        );
+    }
+
+    #[test]
+    fn test_repeated_evaluate() {
+        let data = read_to_string("../examples/24/test.txt").unwrap();
+        let netlist = parse_input(&data).unwrap();
+        assert_eq!(netlist.evaluate_initial(), 2024);
+        assert_eq!(netlist.evaluate_initial(), 2024);
+        assert_eq!(netlist.evaluate(0, 0), netlist.evaluate(0, 0));
+    }
+
+    #[test]
+    fn test_loop() {
+        let data = "x00: 1\ny00: 0\n\nx00 AND b -> a\na OR y00 -> b\na XOR b -> z00\n";
+        let err = parse_input(data).unwrap_err().to_string();
+        assert!(err.contains("b -> a -> b"), "{}", err);
+
+        let data = "x00: 1\ny00: 0\n\nx00 AND y00 -> a\na OR y00 -> z00\n";
+        let mut netlist = parse_input(data).unwrap();
+        assert!(netlist.swap_outputs("a", "z00").is_err());
+        assert_eq!(netlist.evaluate(1, 1), 1);
+        netlist.swap_outputs("a", "a").unwrap();
+    }
+
+    #[test]
+    fn test_wide_wires() {
+        let data = "x63: 1
+y63: 1
+
+x63 AND y63 -> z63
+";
+        assert_eq!(
+            parse_input(data).unwrap().evaluate(1 << 63, 1 << 63),
+            1 << 63
+        );
+        let data = "x00: 1
+y00: 1
+
+x00 AND y00 -> z64
+";
+        let err = parse_input(data).unwrap_err().to_string();
+        assert!(err.contains("z64 is past bit 63"), "{}", err);
+    }
+
+    #[test]
+    fn answer_part1() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let netlist = parse_input(&data).unwrap();
+        assert_eq!(netlist.evaluate_initial(), 61886126253040);
+    }
+
+    #[test]
+    fn answer_part2() {
+        let data = read_to_string("src/main.txt").unwrap();
+        assert_eq!(
+            Day24.part2(&data, &aoc::Params::new()).unwrap(),
+            "fgt,fpq,nqk,pcp,srn,z07,z24,z32"
+        );
+
+        let mut adder = adder::Adder::new(parse_input(&data).unwrap()).unwrap();
+        let swaps = adder.repair().unwrap();
+        let mut netlist = parse_input(&data).unwrap();
+        let vectors = sim::random(&mut Rng::new(1), 45, 1000);
+        let report = sim::check_adder(&netlist, vectors.iter().copied());
+        assert_eq!(report.bad_bits().first(), Some(&7));
+        for swap in &swaps {
+            netlist.swap_outputs(&swap.wires.0, &swap.wires.1).unwrap();
+        }
+        assert!(sim::check_adder(&netlist, vectors).is_ok());
+        assert_eq!(netlist.evaluate(0, 0), 0, "expect 0 + 0 = 0");
+        for i in 0..45 {
+            let a = netlist.evaluate(1 << i, 0);
+            assert_eq!(a, 1u64 << i, "expect {:x} + 0 = {:x}", 1u64 << i, 1u64 << i);
+
+            let a = netlist.evaluate(0, 1 << i);
+            assert_eq!(a, 1u64 << i, "expect 0 + {:x} = {:x}", 1u64 << i, 1u64 << i);
+        }
+        for i in 0..44 {
+            let a = netlist.evaluate(3 << i, 1 << i);
+            assert_eq!(
+                a,
+                4u64 << i,
+                "expect {:x} + {:x} = {:x}",
+                (3u64 << i),
+                (1u64 << i),
+                (4u64 << i),
+            );
+
+            let a = netlist.evaluate(1 << i, 3 << i);
+            assert_eq!(
+                a,
+                4u64 << i,
+                "expect {:x} + {:x} = {:x}",
+                1u64 << i,
+                3u64 << i,
+                4u64 << i
+            );
+        }
+    }
+}
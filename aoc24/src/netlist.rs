@@ -0,0 +1,369 @@
+//! An arena of wires and gates that can be evaluated any number of times.
+//!
+//! Wires and gates are referred to by index.  The gates are kept in topological order, so
+//! evaluation is a single pass over them, and rewiring the netlist re-sorts it and reports any
+//! loop the change creates.  The wires `0` and `1` are constants.
+
+use crate::gates::Op;
+use aoc::AocError;
+use std::collections::HashMap;
+
+pub type WireId = usize;
+pub type GateId = usize;
+
+#[derive(Clone, Debug)]
+pub struct Gate {
+    pub op: Op,
+    pub inputs: Vec<WireId>,
+    pub output: WireId,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Netlist {
+    names: Vec<String>,
+    ids: HashMap<String, WireId>,
+    gates: Vec<Gate>,
+    drivers: Vec<Option<GateId>>,
+    initial: Vec<Option<bool>>,
+    x: Vec<(usize, WireId)>,
+    y: Vec<(usize, WireId)>,
+    z: Vec<(usize, WireId)>,
+    order: Vec<GateId>,
+}
+
+/// Collects inputs and gates, then checks and sorts them into a [`Netlist`].
+#[derive(Default)]
+pub struct Builder {
+    netlist: Netlist,
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn input(&mut self, name: &str, val: bool) -> aoc::Result<WireId> {
+        let wire = self.netlist.intern(name);
+        if self.netlist.is_constant(wire) {
+            return Err(AocError::invalid_state(format!(
+                "wire {} is a constant",
+                name
+            )));
+        }
+        if self.netlist.initial[wire].is_some() {
+            return Err(AocError::invalid_state(format!(
+                "wire {} is set twice",
+                name
+            )));
+        }
+        if self.netlist.drivers[wire].is_some() {
+            return Err(AocError::invalid_state(format!(
+                "wire {} is driven twice",
+                name
+            )));
+        }
+        self.netlist.initial[wire] = Some(val);
+        Ok(wire)
+    }
+
+    pub fn gate(&mut self, op: Op, inputs: &[&str], output: &str) -> aoc::Result<GateId> {
+        if !op.accepts(inputs.len()) {
+            return Err(AocError::invalid_state(format!(
+                "{} does not take {} input{}",
+                op.name(),
+                inputs.len(),
+                if inputs.len() == 1 { "" } else { "s" }
+            )));
+        }
+        let netlist = &mut self.netlist;
+        let output_wire = netlist.intern(output);
+        if netlist.is_constant(output_wire) {
+            return Err(AocError::invalid_state(format!(
+                "cannot drive constant {}",
+                output
+            )));
+        }
+        if netlist.initial[output_wire].is_some() || netlist.drivers[output_wire].is_some() {
+            return Err(AocError::invalid_state(format!(
+                "wire {} is driven twice",
+                output
+            )));
+        }
+        let inputs = inputs.iter().map(|input| netlist.intern(input)).collect();
+        let id = netlist.gates.len();
+        netlist.drivers[output_wire] = Some(id);
+        netlist.gates.push(Gate {
+            op,
+            inputs,
+            output: output_wire,
+        });
+        Ok(id)
+    }
+
+    pub fn build(self) -> aoc::Result<Netlist> {
+        let mut netlist = self.netlist;
+        for wire in 0..netlist.names.len() {
+            if netlist.initial[wire].is_none() && netlist.drivers[wire].is_none() {
+                return Err(AocError::invalid_state(format!(
+                    "wire {} is never driven",
+                    netlist.names[wire]
+                )));
+            }
+        }
+
+        netlist.x = netlist.numbered("x")?;
+        netlist.y = netlist.numbered("y")?;
+        netlist.z = netlist.numbered("z")?;
+        netlist.sort()?;
+        Ok(netlist)
+    }
+}
+
+/// Reports a builder error at `part` of `line`.
+pub(crate) fn located<'a>(
+    line: &'a aoc::Line,
+    part: &'a str,
+) -> impl FnOnce(AocError) -> AocError + 'a {
+    move |err| match err {
+        AocError::InvalidState(msg) => line.error(part, msg),
+        err => err,
+    }
+}
+
+impl Netlist {
+    /// Parses a circuit in the day 24 language, see [`crate::circuit`].
+    pub fn parse(data: &str) -> aoc::Result<Self> {
+        crate::circuit::parse(data)
+    }
+
+    fn intern(&mut self, name: &str) -> WireId {
+        if let Some(&wire) = self.ids.get(name) {
+            return wire;
+        }
+        let wire = self.names.len();
+        self.names.push(name.to_owned());
+        self.ids.insert(name.to_owned(), wire);
+        self.drivers.push(None);
+        self.initial.push(match name {
+            "0" => Some(false),
+            "1" => Some(true),
+            _ => None,
+        });
+        wire
+    }
+
+    pub fn is_constant(&self, wire: WireId) -> bool {
+        matches!(self.names[wire].as_str(), "0" | "1")
+    }
+
+    fn numbered(&self, prefix: &str) -> aoc::Result<Vec<(usize, WireId)>> {
+        let mut wires = self
+            .names
+            .iter()
+            .enumerate()
+            .filter(|(_, name)| name.starts_with(prefix))
+            .map(|(wire, name)| {
+                let bit = name[prefix.len()..].parse::<usize>().map_err(|_| {
+                    AocError::invalid_state(format!("wire {} is not numbered", name))
+                })?;
+                // Numbers are read and written as u64.
+                if bit >= 64 {
+                    return Err(AocError::invalid_state(format!(
+                        "wire {} is past bit 63",
+                        name
+                    )));
+                }
+                Ok((bit, wire))
+            })
+            .collect::<aoc::Result<Vec<_>>>()?;
+        wires.sort();
+        Ok(wires)
+    }
+
+    /// Orders the gates so every gate comes after the gates driving its inputs.
+    fn sort(&mut self) -> aoc::Result<()> {
+        let mut pending = self
+            .gates
+            .iter()
+            .map(|gate| {
+                gate.inputs
+                    .iter()
+                    .filter(|&&wire| self.drivers[wire].is_some())
+                    .count()
+            })
+            .collect::<Vec<_>>();
+        let mut fanout = vec![vec![]; self.names.len()];
+        for (id, gate) in self.gates.iter().enumerate() {
+            for &wire in &gate.inputs {
+                fanout[wire].push(id);
+            }
+        }
+
+        let mut order = (0..self.gates.len())
+            .filter(|&id| pending[id] == 0)
+            .collect::<Vec<_>>();
+        let mut next = 0;
+        while next < order.len() {
+            let gate = &self.gates[order[next]];
+            for &reader in &fanout[gate.output] {
+                pending[reader] -= 1;
+                if pending[reader] == 0 {
+                    order.push(reader);
+                }
+            }
+            next += 1;
+        }
+
+        if order.len() < self.gates.len() {
+            let start = (0..self.gates.len()).find(|&id| pending[id] > 0).unwrap();
+            return Err(AocError::invalid_state(format!(
+                "circuit has a loop: {}",
+                self.find_loop(start, &pending)
+            )));
+        }
+        self.order = order;
+        Ok(())
+    }
+
+    /// Follows unsorted gates backwards from `start` until one repeats and names the wires on
+    /// the loop, in signal order.
+    fn find_loop(&self, start: GateId, pending: &[usize]) -> String {
+        let mut path = vec![start];
+        loop {
+            let gate = &self.gates[*path.last().unwrap()];
+            let driver = gate
+                .inputs
+                .iter()
+                .filter_map(|&wire| self.drivers[wire])
+                .find(|&driver| pending[driver] > 0)
+                .unwrap();
+            if let Some(i) = path.iter().position(|&id| id == driver) {
+                let mut wires = path[i..]
+                    .iter()
+                    .rev()
+                    .map(|&id| self.names[self.gates[id].output].as_str())
+                    .collect::<Vec<_>>();
+                wires.push(wires[0]);
+                return wires.join(" -> ");
+            }
+            path.push(driver);
+        }
+    }
+
+    pub fn wire(&self, name: &str) -> Option<WireId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, wire: WireId) -> &str {
+        &self.names[wire]
+    }
+
+    pub fn wire_count(&self) -> usize {
+        self.names.len()
+    }
+
+    /// The value an input or constant wire was parsed with, or `None` for gate outputs.
+    pub fn initial(&self, wire: WireId) -> Option<bool> {
+        self.initial[wire]
+    }
+
+    /// The `xNN` wires with their bit numbers, in bit order.
+    pub fn x_wires(&self) -> &[(usize, WireId)] {
+        &self.x
+    }
+
+    pub fn y_wires(&self) -> &[(usize, WireId)] {
+        &self.y
+    }
+
+    pub fn z_wires(&self) -> &[(usize, WireId)] {
+        &self.z
+    }
+
+    pub fn gates(&self) -> &[Gate] {
+        &self.gates
+    }
+
+    pub fn driver(&self, wire: WireId) -> Option<GateId> {
+        self.drivers[wire]
+    }
+
+    /// Gates in topological order.
+    pub fn order(&self) -> &[GateId] {
+        &self.order
+    }
+
+    pub fn bits(&self) -> usize {
+        self.x.len()
+    }
+
+    /// Exchanges the gates driving wires `a` and `b`.  A swap that would create a loop is
+    /// rejected and leaves the netlist unchanged.
+    pub fn swap_outputs(&mut self, a: &str, b: &str) -> aoc::Result<()> {
+        let (Some(wire_a), Some(wire_b)) = (self.wire(a), self.wire(b)) else {
+            return Err(AocError::invalid_state(format!(
+                "cannot swap {} and {}: unknown wire",
+                a, b
+            )));
+        };
+        let (Some(gate_a), Some(gate_b)) = (self.drivers[wire_a], self.drivers[wire_b]) else {
+            return Err(AocError::invalid_state(format!(
+                "cannot swap {} and {}: not both gate outputs",
+                a, b
+            )));
+        };
+        self.exchange(wire_a, gate_a, wire_b, gate_b);
+        if let Err(err) = self.sort() {
+            self.exchange(wire_a, gate_b, wire_b, gate_a);
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    fn exchange(&mut self, wire_a: WireId, gate_a: GateId, wire_b: WireId, gate_b: GateId) {
+        self.gates[gate_a].output = wire_b;
+        self.gates[gate_b].output = wire_a;
+        self.drivers[wire_a] = Some(gate_b);
+        self.drivers[wire_b] = Some(gate_a);
+    }
+
+    // Values are kept as words so gates are applied the same way as in the 64-lane simulator;
+    // only the low bit is used.
+    fn initial_values(&self) -> Vec<u64> {
+        self.initial
+            .iter()
+            .map(|&val| val.unwrap_or(false) as u64)
+            .collect()
+    }
+
+    fn run(&self, values: &mut [u64]) -> u64 {
+        let mut args = vec![];
+        for &id in &self.order {
+            let gate = &self.gates[id];
+            args.clear();
+            args.extend(gate.inputs.iter().map(|&wire| values[wire]));
+            values[gate.output] = gate.op.apply_word(&args) & 1;
+        }
+        self.z
+            .iter()
+            .filter(|&&(_, wire)| values[wire] == 1)
+            .fold(0, |n, &(bit, _)| n | 1 << bit)
+    }
+
+    /// Evaluates the circuit with the input values it was parsed with.
+    pub fn evaluate_initial(&self) -> u64 {
+        let mut values = self.initial_values();
+        self.run(&mut values)
+    }
+
+    /// Evaluates the circuit with the `xNN` and `yNN` wires set from the bits of `x` and `y`.
+    pub fn evaluate(&self, x: u64, y: u64) -> u64 {
+        let mut values = self.initial_values();
+        for (inputs, n) in [(&self.x, x), (&self.y, y)] {
+            for &(bit, wire) in inputs {
+                values[wire] = (n >> bit) & 1;
+            }
+        }
+        self.run(&mut values)
+    }
+}
@@ -0,0 +1,254 @@
+//! Bit-parallel simulation and a test-vector driver.
+//!
+//! Every wire holds a `u64` whose bits are 64 independent test vectors, so one pass over the
+//! gates in topological order evaluates 64 inputs at once.  Netlists have no `x`, `y` or `z`
+//! wires past bit 63, so every bit of a vector fits a `u64`.
+
+use crate::netlist::Netlist;
+use aoc::{testing::Rng, AocError};
+
+pub const LANES: usize = 64;
+
+// Mismatches beyond this are only counted, to keep reports of badly broken circuits small.
+const MAX_MISMATCHES: usize = 100;
+
+pub struct Simulator<'a> {
+    netlist: &'a Netlist,
+    values: Vec<u64>,
+}
+
+impl<'a> Simulator<'a> {
+    pub fn new(netlist: &'a Netlist) -> Self {
+        let values = (0..netlist.wire_count())
+            .map(|wire| match netlist.initial(wire) {
+                Some(true) => !0,
+                _ => 0,
+            })
+            .collect();
+        Self { netlist, values }
+    }
+
+    /// Evaluates up to 64 `(x, y)` vectors and returns the `z` output for each.
+    pub fn run(&mut self, vectors: &[(u64, u64)]) -> Vec<u64> {
+        assert!(vectors.len() <= LANES, "at most {} vectors per run", LANES);
+        for &(bit, wire) in self.netlist.x_wires() {
+            self.values[wire] = lanes(vectors.iter().map(|&(x, _)| x), bit);
+        }
+        for &(bit, wire) in self.netlist.y_wires() {
+            self.values[wire] = lanes(vectors.iter().map(|&(_, y)| y), bit);
+        }
+
+        let mut args = vec![];
+        for &id in self.netlist.order() {
+            let gate = &self.netlist.gates()[id];
+            args.clear();
+            args.extend(gate.inputs.iter().map(|&wire| self.values[wire]));
+            self.values[gate.output] = gate.op.apply_word(&args);
+        }
+
+        let mut outputs = vec![0; vectors.len()];
+        for &(bit, wire) in self.netlist.z_wires() {
+            let word = self.values[wire];
+            for (lane, output) in outputs.iter_mut().enumerate() {
+                *output |= ((word >> lane) & 1) << bit;
+            }
+        }
+        outputs
+    }
+}
+
+/// Packs bit `bit` of each value into one lane of a word.
+fn lanes(values: impl Iterator<Item = u64>, bit: usize) -> u64 {
+    values
+        .enumerate()
+        .fold(0, |word, (lane, val)| word | ((val >> bit) & 1) << lane)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    pub x: u64,
+    pub y: u64,
+    pub expected: u64,
+    pub actual: u64,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    pub vectors: usize,
+    /// How often each `z` bit disagreed with the reference.
+    pub bit_errors: Vec<usize>,
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl Report {
+    pub fn is_ok(&self) -> bool {
+        self.bit_errors.iter().all(|&errors| errors == 0)
+    }
+
+    /// The output bits that disagreed with the reference at least once.
+    pub fn bad_bits(&self) -> Vec<usize> {
+        (0..self.bit_errors.len())
+            .filter(|&bit| self.bit_errors[bit] > 0)
+            .collect()
+    }
+}
+
+/// Runs `vectors` through the circuit and compares every output with `reference`, truncated
+/// to the width of the `z` wires.
+pub fn check<I, F>(netlist: &Netlist, vectors: I, reference: F) -> Report
+where
+    I: IntoIterator<Item = (u64, u64)>,
+    F: Fn(u64, u64) -> u64,
+{
+    let width = netlist.z_wires().last().map_or(0, |&(bit, _)| bit + 1);
+    let mask = if width >= 64 { !0 } else { (1 << width) - 1 };
+    let mut report = Report {
+        bit_errors: vec![0; width],
+        ..Report::default()
+    };
+
+    let mut simulator = Simulator::new(netlist);
+    let mut vectors = vectors.into_iter().peekable();
+    while vectors.peek().is_some() {
+        let chunk = vectors.by_ref().take(LANES).collect::<Vec<_>>();
+        let outputs = simulator.run(&chunk);
+        for (&(x, y), actual) in chunk.iter().zip(outputs) {
+            let expected = reference(x, y) & mask;
+            let wrong = expected ^ actual;
+            if wrong == 0 {
+                continue;
+            }
+            for (bit, errors) in report.bit_errors.iter_mut().enumerate() {
+                *errors += ((wrong >> bit) & 1) as usize;
+            }
+            if report.mismatches.len() < MAX_MISMATCHES {
+                report.mismatches.push(Mismatch {
+                    x,
+                    y,
+                    expected,
+                    actual,
+                });
+            }
+        }
+        report.vectors += chunk.len();
+    }
+    report
+}
+
+/// Checks that the circuit computes `x + y`.
+pub fn check_adder<I>(netlist: &Netlist, vectors: I) -> Report
+where
+    I: IntoIterator<Item = (u64, u64)>,
+{
+    check(netlist, vectors, u64::wrapping_add)
+}
+
+/// Every pair of `bits`-bit numbers.
+pub fn exhaustive(bits: usize) -> aoc::Result<impl Iterator<Item = (u64, u64)>> {
+    if bits > 16 {
+        return Err(AocError::invalid_state(format!(
+            "{} bits is too many to test exhaustively",
+            bits
+        )));
+    }
+    Ok((0..1u64 << bits).flat_map(move |x| (0..1u64 << bits).map(move |y| (x, y))))
+}
+
+/// `count` random pairs of `bits`-bit numbers.
+pub fn random(rng: &mut Rng, bits: usize, count: usize) -> Vec<(u64, u64)> {
+    let mask = if bits >= 64 { !0 } else { (1 << bits) - 1 };
+    (0..count)
+        .map(|_| (rng.next_u64() & mask, rng.next_u64() & mask))
+        .collect()
+}
+
+/// Single bits on either input, both inputs set, and a carry rippling in from every lower bit.
+/// Bits past 63 are left out.
+pub fn carry_vectors(bits: usize) -> Vec<(u64, u64)> {
+    let mask = if bits >= 64 { !0 } else { (1 << bits) - 1 };
+    (0..bits.min(64))
+        .flat_map(|bit| {
+            let one = 1 << bit;
+            [(one, 0), (0, one), (one, one), (mask, one)]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::testing;
+
+    const HALF_ADDER: &str = "x00: 0\ny00: 0\n\nx00 XOR y00 -> z00\nx00 AND y00 -> z01\n";
+
+    #[test]
+    fn test_check() {
+        let netlist = Netlist::parse(HALF_ADDER).unwrap();
+        let report = check_adder(&netlist, exhaustive(1).unwrap());
+        assert_eq!(report.vectors, 4);
+        assert!(report.is_ok());
+
+        let report = check(&netlist, exhaustive(1).unwrap(), |x, y| x ^ y);
+        assert_eq!(report.bad_bits(), vec![1]);
+        assert_eq!(
+            report.mismatches,
+            vec![Mismatch {
+                x: 1,
+                y: 1,
+                expected: 0,
+                actual: 2
+            }]
+        );
+
+        let mut netlist = netlist;
+        netlist.swap_outputs("z00", "z01").unwrap();
+        assert_eq!(
+            check_adder(&netlist, exhaustive(1).unwrap()).bad_bits(),
+            vec![0, 1]
+        );
+        assert!(exhaustive(17).is_err());
+    }
+
+    #[test]
+    fn test_top_bit() {
+        let netlist = Netlist::parse("x63: 0\ny63: 0\n\nx63 XOR y63 -> z63\n").unwrap();
+        let top = 1 << 63;
+        let report = check_adder(&netlist, [(top, 0), (0, top), (top, top)]);
+        assert!(report.is_ok());
+        assert_eq!(report.bit_errors.len(), 64);
+        assert_eq!(carry_vectors(70).len(), 64 * 4);
+    }
+
+    #[test]
+    fn test_simulator() {
+        testing::differential(
+            "simulator",
+            100,
+            |rng| {
+                let bits = rng.usize(1, 8);
+                let gates = rng.usize(bits + 1, 40);
+                (rng.next_u64(), (bits as u64, gates as u64))
+            },
+            |&(seed, (bits, gates))| {
+                let mut rng = Rng::new(seed);
+                let data = testing::netlist(&mut rng, bits as usize, gates as usize);
+                let netlist = Netlist::parse(&data).unwrap();
+                random(&mut rng, bits as usize, 100)
+                    .iter()
+                    .map(|&(x, y)| netlist.evaluate(x, y))
+                    .collect::<Vec<_>>()
+            },
+            |&(seed, (bits, gates))| {
+                let mut rng = Rng::new(seed);
+                let data = testing::netlist(&mut rng, bits as usize, gates as usize);
+                let netlist = Netlist::parse(&data).unwrap();
+                let vectors = random(&mut rng, bits as usize, 100);
+                let mut simulator = Simulator::new(&netlist);
+                vectors
+                    .chunks(LANES)
+                    .flat_map(|chunk| simulator.run(chunk))
+                    .collect::<Vec<_>>()
+            },
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use aoc::{AocError, Solution};
+
+#[derive(Clone)]
+pub struct LockOrKey {
+    tumblers: Vec<usize>,
+    typ: Typ,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum Typ {
+    LOCK,
+    KEY,
+}
+
+impl LockOrKey {
+    pub fn from<'a, F>(iter: &mut F) -> aoc::Result<Option<Self>>
+    where
+        F: Iterator<Item = aoc::Line<'a>>,
+    {
+        let mut iter = iter.take_while(|line| !line.text.is_empty());
+        let Some(top_line) = iter.next() else {
+            return Ok(None);
+        };
+        let top = top_line.text;
+        let mut tumblers = vec![0; top.len()];
+        let mut bottom = None;
+        for line in iter {
+            if line.text.len() != top.len() {
+                return Err(line.error(line.end(), format!("expected {} columns", top.len())));
+            }
+            for (i, c) in line.text.chars().enumerate() {
+                match c {
+                    '#' => tumblers[i] += 1,
+                    '.' => (),
+                    _ => {
+                        return Err(AocError::parse(
+                            line.num,
+                            i + 1,
+                            format!("unexpected character {:?}", c),
+                        ))
+                    }
+                }
+            }
+            bottom = Some(line.text);
+        }
+
+        if top.chars().all(|c| c == '#') {
+            // lock
+            Ok(Some(LockOrKey {
+                tumblers,
+                typ: Typ::LOCK,
+            }))
+        } else if top.chars().all(|c| c == '.')
+            && bottom.is_some_and(|b| b.chars().all(|c| c == '#'))
+        {
+            // key
+            for t in tumblers.iter_mut() {
+                *t -= 1;
+            }
+            Ok(Some(LockOrKey {
+                tumblers,
+                typ: Typ::KEY,
+            }))
+        } else {
+            Err(top_line.error(top, "expected a lock or a key"))
+        }
+    }
+}
+
+pub fn parse_input(data: &str) -> aoc::Result<(Vec<LockOrKey>, Vec<LockOrKey>)> {
+    let mut lines = aoc::lines(data);
+
+    let mut locks_and_keys = Vec::new();
+
+    while let Some(lock_or_key) = LockOrKey::from(&mut lines)? {
+        locks_and_keys.push(lock_or_key);
+    }
+
+    let width = locks_and_keys
+        .first()
+        .map_or(0, |first| first.tumblers.len());
+    if locks_and_keys
+        .iter()
+        .any(|lock_or_key| lock_or_key.tumblers.len() != width)
+    {
+        return Err(AocError::invalid_state(
+            "locks and keys have different widths",
+        ));
+    }
+
+    Ok(locks_and_keys
+        .into_iter()
+        .partition(|lock_or_key| lock_or_key.typ == Typ::LOCK))
+}
+
+pub fn lock_key_fit(lock: &LockOrKey, key: &LockOrKey) -> bool {
+    lock.tumblers
+        .iter()
+        .enumerate()
+        .all(|(i, _)| lock.tumblers[i] + key.tumblers[i] < 6)
+}
+
+pub fn lock_key_pairs_that_fit(locks: &[LockOrKey], keys: &[LockOrKey]) -> usize {
+    locks
+        .iter()
+        .map(|lock| keys.iter().filter(|key| lock_key_fit(lock, key)).count())
+        .sum()
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    fn part1(&self, input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        let (locks, keys) = parse_input(input)?;
+        Ok(lock_key_pairs_that_fit(&locks, &keys).to_string())
+    }
+
+    fn part2(&self, _input: &str, _params: &aoc::Params) -> aoc::Result<String> {
+        Err(aoc::AocError::no_solution("day 25 has no part 2"))
+    }
+}
+
+aoc::example_tests!(Day25);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::read_to_string;
+
+    #[test]
+    fn test_tumblers() {
+        let data = read_to_string("../examples/25/test.txt").unwrap();
+        let (locks, keys) = parse_input(&data).unwrap();
+        assert_eq!(locks.len(), 2);
+        assert_eq!(locks[0].tumblers, vec![0, 5, 3, 4, 3]);
+        assert_eq!(locks[1].tumblers, vec![1, 2, 0, 5, 3]);
+        assert_eq!(keys.len(), 3);
+        assert_eq!(keys[0].tumblers, vec![5, 0, 2, 1, 3]);
+        assert_eq!(keys[1].tumblers, vec![4, 3, 4, 0, 2]);
+        assert_eq!(keys[2].tumblers, vec![3, 0, 2, 0, 1]);
+
+        assert_eq!(lock_key_pairs_that_fit(&locks, &keys), 3);
+    }
+
+    #[test]
+    fn answer_part1() {
+        let data = read_to_string("src/main.txt").unwrap();
+        let (locks, keys) = parse_input(&data).unwrap();
+
+        assert_eq!(lock_key_pairs_that_fit(&locks, &keys), 3196);
+    }
+}
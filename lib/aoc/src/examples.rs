@@ -0,0 +1,168 @@
+//! Example fixtures.
+//!
+//! Each day's examples live in `examples/NN/` at the top of the workspace.  Every `NAME.txt`
+//! puzzle input has a `NAME.answers` sidecar with one `key = value` per line: `part1` and
+//! `part2` are the expected answers, and any other key is passed to the solution as a
+//! parameter.  Lines starting with `#` are comments.
+//!
+//! A day's `build.rs` calls [`generate_tests`] and its tests call [`example_tests!`] to turn
+//! every fixture into a `NAME_partN` test case.
+
+use crate::{read_input, AocError, Params, Result, Solution};
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Clone, Debug)]
+pub struct Example {
+    pub input: String,
+    pub params: Params,
+    pub expected: [Option<String>; 2],
+}
+
+impl Example {
+    /// Loads the example input at `path` and its `.answers` sidecar.
+    pub fn load(path: &Path) -> Result<Self> {
+        let input = read_input(path)?;
+        let (params, expected) = parse_sidecar(&read_input(&sidecar_path(path))?)?;
+        Ok(Self {
+            input,
+            params,
+            expected,
+        })
+    }
+}
+
+pub fn sidecar_path(path: &Path) -> PathBuf {
+    path.with_extension("answers")
+}
+
+fn parse_sidecar(data: &str) -> Result<(Params, [Option<String>; 2])> {
+    let mut params = Params::parse(data)?;
+    let expected = [params.take("part1"), params.take("part2")];
+    Ok((params, expected))
+}
+
+/// Runs one part of the example at `path` and checks it against its sidecar.
+pub fn check<S>(solution: &S, path: &str, part: u8)
+where
+    S: Solution + ?Sized,
+{
+    let example = Example::load(Path::new(path)).unwrap_or_else(|err| panic!("{}", err));
+    let expected = example.expected[part as usize - 1]
+        .as_ref()
+        .unwrap_or_else(|| panic!("{} has no answer for part {}", path, part));
+    let actual = solution
+        .part(part, &example.input, &example.params)
+        .unwrap_or_else(|err| panic!("{} part {}: {}", path, part, err));
+    assert_eq!(&actual, expected, "{} part {}", path, part);
+}
+
+fn test_name(stem: &str) -> String {
+    let name = stem
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        "example_".to_owned() + &name
+    } else {
+        name
+    }
+}
+
+fn generate_tests_for_dir(dir: &Path) -> Result<String> {
+    let mut inputs = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+            .collect::<Vec<_>>(),
+        Err(_) => vec![],
+    };
+    inputs.sort();
+
+    let mut tests = String::new();
+    for input in inputs {
+        let sidecar = sidecar_path(&input);
+        println!("cargo:rerun-if-changed={}", input.display());
+        println!("cargo:rerun-if-changed={}", sidecar.display());
+        let (_, expected) = parse_sidecar(&read_input(&sidecar)?)?;
+        let name = test_name(&input.file_stem().unwrap_or_default().to_string_lossy());
+        let path = input.canonicalize().map_err(|source| AocError::Io {
+            path: input.clone(),
+            source,
+        })?;
+        for (i, _) in expected.iter().enumerate().filter(|(_, e)| e.is_some()) {
+            writeln!(
+                tests,
+                "example_test!({}_part{}, {:?}, {});",
+                name,
+                i + 1,
+                path.to_string_lossy(),
+                i + 1
+            )
+            .expect("write to string");
+        }
+    }
+    Ok(tests)
+}
+
+/// Called from a day's `build.rs` to generate a test case for every fixture in
+/// `examples/NN/`, where `NN` comes from the `aocNN` package name.
+pub fn generate_tests() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR"));
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR"));
+    let name = env::var("CARGO_PKG_NAME").expect("CARGO_PKG_NAME");
+    let day = name.trim_start_matches("aoc");
+    let dir = manifest_dir.join("../examples").join(day);
+
+    println!("cargo:rerun-if-changed={}", dir.display());
+    let tests = generate_tests_for_dir(&dir).unwrap_or_else(|err| panic!("{}", err));
+    fs::write(out_dir.join("example_tests.rs"), tests).expect("write example_tests.rs");
+}
+
+/// Expands to a test module with a test case for every example fixture of the day, run
+/// against `$solution`.
+#[macro_export]
+macro_rules! example_tests {
+    ($solution:expr) => {
+        #[cfg(test)]
+        mod example_tests {
+            #[allow(unused_imports)]
+            use super::*;
+
+            macro_rules! example_test {
+                ($name:ident, $path:expr, $part:expr) => {
+                    #[test]
+                    fn $name() {
+                        $crate::examples::check(&$solution, $path, $part);
+                    }
+                };
+            }
+
+            include!(concat!(env!("OUT_DIR"), "/example_tests.rs"));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sidecar() {
+        let (params, expected) = parse_sidecar("# room\nwidth = 11\n\npart1 = 12\n").unwrap();
+        assert_eq!(params, Params::new().with("width", "11"));
+        assert_eq!(expected, [Some("12".to_owned()), None]);
+        assert!(parse_sidecar("part1 12").is_err());
+    }
+
+    #[test]
+    fn test_test_name() {
+        assert_eq!(test_name("test"), "test");
+        assert_eq!(test_name("part2-small"), "part2_small");
+        assert_eq!(test_name("1"), "example_1");
+    }
+}
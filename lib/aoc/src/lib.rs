@@ -0,0 +1,240 @@
+pub mod examples;
+mod solution;
+pub mod testing;
+
+pub use solution::*;
+
+use std::{
+    fmt::{self, Display},
+    fs::read_to_string,
+    io,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    str::FromStr,
+};
+
+#[derive(Debug)]
+pub enum AocError {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Parse {
+        line: usize,
+        col: usize,
+        msg: String,
+    },
+    InvalidState(String),
+    NoSolution(String),
+}
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+impl AocError {
+    pub fn parse(line: usize, col: usize, msg: impl Into<String>) -> Self {
+        Self::Parse {
+            line,
+            col,
+            msg: msg.into(),
+        }
+    }
+
+    pub fn invalid_state(msg: impl Into<String>) -> Self {
+        Self::InvalidState(msg.into())
+    }
+
+    pub fn no_solution(msg: impl Into<String>) -> Self {
+        Self::NoSolution(msg.into())
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            Self::Parse { line, col, msg } => {
+                write!(f, "parse error at line {}, column {}: {}", line, col, msg)
+            }
+            Self::InvalidState(msg) => write!(f, "invalid puzzle state: {}", msg),
+            Self::NoSolution(msg) => write!(f, "no solution: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// A line of puzzle input with its 1-based line number, used to attach positions to parse
+/// errors.
+#[derive(Clone, Copy, Debug)]
+pub struct Line<'a> {
+    pub text: &'a str,
+    pub num: usize,
+}
+
+impl<'a> Line<'a> {
+    pub fn new(text: &'a str, num: usize) -> Self {
+        Self { text, num }
+    }
+
+    /// Returns the 1-based column of `part`, which must be a slice of this line's text.
+    pub fn col(&self, part: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let part_start = part.as_ptr() as usize;
+        if part_start >= start && part_start <= start + self.text.len() {
+            part_start - start + 1
+        } else {
+            1
+        }
+    }
+
+    pub fn error(&self, part: &str, msg: impl Into<String>) -> AocError {
+        AocError::parse(self.num, self.col(part), msg)
+    }
+
+    pub fn end(&self) -> &'a str {
+        &self.text[self.text.len()..]
+    }
+
+    pub fn parse<T>(&self, part: &str) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        part.trim()
+            .parse()
+            .map_err(|err| self.error(part, format!("invalid value {:?}: {}", part, err)))
+    }
+
+    pub fn split_once(&self, delimiter: &str) -> Result<(&'a str, &'a str)> {
+        self.text
+            .split_once(delimiter)
+            .ok_or_else(|| self.error(self.text, format!("expected {:?}", delimiter)))
+    }
+
+    pub fn strip_prefix(&self, prefix: &str) -> Result<&'a str> {
+        self.text
+            .strip_prefix(prefix)
+            .ok_or_else(|| self.error(self.text, format!("expected {:?}", prefix)))
+    }
+
+    /// Parses every `separator` delimited field of the line.
+    pub fn parse_list<T>(&self, separator: &str) -> Result<Vec<T>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.text
+            .split(separator)
+            .map(|part| self.parse(part))
+            .collect()
+    }
+
+    pub fn parse_whitespace_list<T>(&self) -> Result<Vec<T>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.text
+            .split_ascii_whitespace()
+            .map(|part| self.parse(part))
+            .collect()
+    }
+}
+
+pub fn lines(data: &str) -> impl Iterator<Item = Line<'_>> {
+    data.lines()
+        .enumerate()
+        .map(|(i, text)| Line::new(text, i + 1))
+}
+
+/// Converts a byte offset into `data` into a 1-based line and column.
+pub fn line_col(data: &str, offset: usize) -> (usize, usize) {
+    let before = &data[..offset];
+    let line = before.matches('\n').count() + 1;
+    let col = offset - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+    (line, col)
+}
+
+/// Returns the input file named on the command line, or the day's `src/main.txt` next to the
+/// executable's source directory.
+pub fn input_path() -> Result<PathBuf> {
+    let args = std::env::args().collect::<Vec<_>>();
+    if args.len() >= 2 {
+        return Ok(PathBuf::from(&args[1]));
+    }
+    let exe = std::env::current_exe().map_err(|source| AocError::Io {
+        path: PathBuf::from(args.first().map_or("", |s| s.as_str())),
+        source,
+    })?;
+    let (Some(dir), Some(name)) = (exe.parent(), exe.file_name()) else {
+        return Err(AocError::invalid_state(format!(
+            "can't find input next to {}",
+            exe.display()
+        )));
+    };
+    Ok(dir.join("../..").join(name).join("src/main.txt"))
+}
+
+pub fn read_input(path: &Path) -> Result<String> {
+    read_to_string(path).map_err(|source| AocError::Io {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+/// Reads the day's input and passes it to `solve`, printing a diagnostic and returning a failing
+/// exit code if anything goes wrong.
+pub fn run<F>(solve: F) -> ExitCode
+where
+    F: FnOnce(&str) -> Result<()>,
+{
+    let name = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.file_stem().map(|s| s.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "aoc".to_owned());
+    let result = input_path()
+        .and_then(|path| read_input(&path))
+        .and_then(|data| solve(&data));
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{}: {}", name, err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_col() {
+        let data = "abc\ndef\n";
+        assert_eq!(line_col(data, 0), (1, 1));
+        assert_eq!(line_col(data, 2), (1, 3));
+        assert_eq!(line_col(data, 4), (2, 1));
+        assert_eq!(line_col(data, 6), (2, 3));
+    }
+
+    #[test]
+    fn test_line_parse_error() {
+        let line = Line::new("12,x4", 3);
+        let err = line.parse_list::<u32>(",").unwrap_err();
+        match err {
+            AocError::Parse { line, col, .. } => assert_eq!((line, col), (3, 4)),
+            _ => panic!("unexpected error {}", err),
+        }
+        assert_eq!(
+            Line::new("1,2", 1).parse_list::<u32>(",").unwrap(),
+            vec![1, 2]
+        );
+    }
+}
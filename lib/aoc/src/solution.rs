@@ -0,0 +1,102 @@
+use crate::{lines, AocError, Result};
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+/// Named puzzle parameters that differ between the examples and the real input, for example the
+/// size of the room in day 14.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params {
+    values: BTreeMap<String, String>,
+}
+
+impl Params {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.values.insert(name.to_owned(), value.to_owned());
+    }
+
+    pub fn with(mut self, name: &str, value: &str) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    /// Parses `name = value` lines.  Blank lines and lines starting with `#` are ignored.
+    pub fn parse(data: &str) -> Result<Self> {
+        let mut params = Self::new();
+        for line in lines(data) {
+            let text = line.text.trim();
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+            let (name, value) = line.split_once("=")?;
+            if name.trim().is_empty() {
+                return Err(line.error(line.text, "expected a name"));
+            }
+            params.set(name.trim(), value.trim());
+        }
+        Ok(params)
+    }
+
+    /// Removes the parameter `name`, returning its value if it was set.
+    pub fn take(&mut self, name: &str) -> Option<String> {
+        self.values.remove(name)
+    }
+
+    /// Returns the parameter `name` parsed as `T`, or `default` if it was not set.
+    pub fn get<T>(&self, name: &str, default: T) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        match self.values.get(name) {
+            None => Ok(default),
+            Some(value) => value.parse().map_err(|err| {
+                AocError::invalid_state(format!(
+                    "invalid value {:?} for parameter {}: {}",
+                    value, name, err
+                ))
+            }),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+/// A day's puzzle, answering both parts from the raw puzzle input.
+pub trait Solution {
+    fn part1(&self, input: &str, params: &Params) -> Result<String>;
+
+    fn part2(&self, input: &str, params: &Params) -> Result<String>;
+
+    fn part(&self, part: u8, input: &str, params: &Params) -> Result<String> {
+        match part {
+            1 => self.part1(input, params),
+            2 => self.part2(input, params),
+            _ => Err(AocError::invalid_state(format!("no part {}", part))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_params() {
+        let params = Params::new().with("size", "7");
+        assert_eq!(params.get("size", 71).unwrap(), 7);
+        assert_eq!(params.get("bytes", 1024).unwrap(), 1024);
+        assert!(Params::new().with("size", "x").get("size", 0).is_err());
+
+        let mut params = Params::parse("# example\nsize = 7\n\nbytes=12\n").unwrap();
+        assert_eq!(params, Params::new().with("size", "7").with("bytes", "12"));
+        assert_eq!(params.take("size"), Some("7".to_owned()));
+        assert_eq!(params.take("size"), None);
+        assert!(Params::parse("size 7").is_err());
+        assert!(Params::parse(" = 7").is_err());
+    }
+}
@@ -0,0 +1,374 @@
+//! Random inputs and differential testing.
+//!
+//! [`Rng`] is a small deterministic xorshift generator so failures can be replayed from the
+//! printed seed (set `AOC_SEED` to override the default).  The generators produce puzzle inputs
+//! in the formats used across the days, and [`differential`] runs a reference and an optimized
+//! implementation on the same generated inputs, shrinking any disagreement to a small case.
+
+use std::{env, fmt::Debug};
+
+const DEFAULT_SEED: u64 = 0x5eed_2024;
+const MAX_SHRINK_STEPS: usize = 1000;
+
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // xorshift gets stuck at zero, so mix the seed into a non-zero state.
+        Self {
+            state: (seed ^ 0x9e37_79b9_7f4a_7c15) | 1,
+        }
+    }
+
+    /// Uses the seed in `AOC_SEED` if it is set.
+    pub fn from_env() -> Self {
+        Self::new(seed_from_env())
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        // xorshift64*
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Returns a number in `0..n`.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "empty range");
+        self.next_u64() % n
+    }
+
+    /// Returns a number in `lo..=hi`.
+    pub fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        assert!(lo <= hi, "empty range {}..={}", lo, hi);
+        let span = hi.abs_diff(lo);
+        match span.checked_add(1) {
+            Some(n) => lo.wrapping_add(self.below(n) as i64),
+            None => self.next_u64() as i64,
+        }
+    }
+
+    pub fn usize(&mut self, lo: usize, hi: usize) -> usize {
+        self.range(lo as i64, hi as i64) as usize
+    }
+
+    pub fn bool(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+fn seed_from_env() -> u64 {
+    env::var("AOC_SEED")
+        .ok()
+        .and_then(|seed| seed.trim().parse().ok())
+        .unwrap_or(DEFAULT_SEED)
+}
+
+/// A `width` by `height` grid of characters from `alphabet`, one row per line.
+pub fn grid(rng: &mut Rng, width: usize, height: usize, alphabet: &[u8]) -> String {
+    let mut s = String::with_capacity((width + 1) * height);
+    for _ in 0..height {
+        s.extend((0..width).map(|_| *rng.choose(alphabet) as char));
+        s.push('\n');
+    }
+    s
+}
+
+pub fn number_list(rng: &mut Rng, len: usize, lo: i64, hi: i64) -> Vec<i64> {
+    (0..len).map(|_| rng.range(lo, hi)).collect()
+}
+
+/// `lines` lines of `per_line` whitespace separated numbers in `lo..=hi`.
+pub fn number_lines(rng: &mut Rng, lines: usize, per_line: usize, lo: i64, hi: i64) -> String {
+    (0..lines)
+        .map(|_| {
+            let nums = number_list(rng, per_line, lo, hi);
+            nums.iter()
+                .map(|n| n.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+                + "\n"
+        })
+        .collect()
+}
+
+/// A day 24 style netlist: random `xNN` and `yNN` inputs of `bits` bits, then `gates` randomly
+/// wired AND, OR and XOR gates in shuffled order, the last `bits + 1` of which drive the `zNN`
+/// outputs.  The netlist is always acyclic.
+pub fn netlist(rng: &mut Rng, bits: usize, gates: usize) -> String {
+    assert!(gates > bits, "need a gate for every output");
+    let mut s = String::new();
+    let mut wires = vec![];
+    for prefix in ["x", "y"] {
+        for i in 0..bits {
+            let name = format!("{}{:02}", prefix, i);
+            s += &format!("{}: {}\n", name, rng.below(2));
+            wires.push(name);
+        }
+    }
+    s.push('\n');
+
+    let mut lines = vec![];
+    for i in 0..gates {
+        let in1 = rng.choose(&wires).clone();
+        let in2 = rng.choose(&wires).clone();
+        let op = rng.choose(&["AND", "OR", "XOR"]);
+        let first_output = gates - bits - 1;
+        let out = if i >= first_output {
+            format!("z{:02}", i - first_output)
+        } else {
+            format!("g{:03}", i)
+        };
+        lines.push(format!("{} {} {} -> {}\n", in1, op, in2, out));
+        wires.push(out);
+    }
+    rng.shuffle(&mut lines);
+    s + &lines.concat()
+}
+
+/// A day 17 style program of `len` instructions.  Combo operands never use the reserved value
+/// 7, but the program is not guaranteed to halt.
+pub fn opcode_program(rng: &mut Rng, len: usize) -> Vec<u8> {
+    const COMBO_OPCODES: [u8; 5] = [0, 2, 5, 6, 7];
+    let mut program = Vec::with_capacity(len * 2);
+    for _ in 0..len {
+        let opcode = rng.below(8) as u8;
+        let operand = if COMBO_OPCODES.contains(&opcode) {
+            rng.below(7)
+        } else {
+            rng.below(8)
+        };
+        program.extend([opcode, operand as u8]);
+    }
+    program
+}
+
+/// Produces simpler versions of a failing input.  Candidates are tried in order, so the most
+/// aggressive simplifications come first.
+pub trait Shrink: Sized {
+    fn shrink(&self) -> Vec<Self>;
+}
+
+macro_rules! shrink_unsigned {
+    ($($t:ty),*) => {
+        $(impl Shrink for $t {
+            fn shrink(&self) -> Vec<Self> {
+                let mut candidates = vec![0, self / 2, self.saturating_sub(1)];
+                candidates.dedup();
+                candidates.retain(|c| c < self);
+                candidates
+            }
+        })*
+    };
+}
+
+shrink_unsigned!(u8, u16, u32, u64, usize);
+
+impl Shrink for i64 {
+    fn shrink(&self) -> Vec<Self> {
+        // Prefer smaller magnitudes, then positive numbers.
+        let mut candidates = vec![0];
+        if *self < 0 {
+            candidates.push(self.saturating_neg());
+        }
+        candidates.extend([self / 2, self - self.signum()]);
+        candidates.dedup();
+        candidates.retain(|&c| {
+            c.unsigned_abs() < self.unsigned_abs()
+                || (c.unsigned_abs() == self.unsigned_abs() && c > *self)
+        });
+        candidates
+    }
+}
+
+impl Shrink for bool {
+    fn shrink(&self) -> Vec<Self> {
+        if *self {
+            vec![false]
+        } else {
+            vec![]
+        }
+    }
+}
+
+impl<T: Shrink + Clone> Shrink for Vec<T> {
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = vec![];
+        if self.len() > 1 {
+            candidates.push(self[..self.len() / 2].to_vec());
+            candidates.push(self[self.len() / 2..].to_vec());
+        }
+        for i in 0..self.len() {
+            let mut v = self.clone();
+            v.remove(i);
+            candidates.push(v);
+        }
+        for (i, item) in self.iter().enumerate() {
+            for smaller in item.shrink() {
+                let mut v = self.clone();
+                v[i] = smaller;
+                candidates.push(v);
+            }
+        }
+        candidates
+    }
+}
+
+impl<A: Shrink + Clone, B: Shrink + Clone> Shrink for (A, B) {
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = self
+            .0
+            .shrink()
+            .into_iter()
+            .map(|a| (a, self.1.clone()))
+            .collect::<Vec<_>>();
+        candidates.extend(self.1.shrink().into_iter().map(|b| (self.0.clone(), b)));
+        candidates
+    }
+}
+
+/// Greedily shrinks `input` while `fails` still holds.
+pub fn shrink<I, F>(mut input: I, fails: F) -> I
+where
+    I: Shrink,
+    F: Fn(&I) -> bool,
+{
+    let mut steps = 0;
+    'outer: while steps < MAX_SHRINK_STEPS {
+        for candidate in input.shrink() {
+            steps += 1;
+            if fails(&candidate) {
+                input = candidate;
+                continue 'outer;
+            }
+        }
+        break;
+    }
+    input
+}
+
+/// Runs `reference` and `optimized` on `cases` inputs from `generate` and panics with the
+/// smallest disagreeing input it can find.
+pub fn differential<I, O, G, R, F>(
+    name: &str,
+    cases: usize,
+    mut generate: G,
+    reference: R,
+    optimized: F,
+) where
+    I: Shrink + Debug,
+    O: PartialEq + Debug,
+    G: FnMut(&mut Rng) -> I,
+    R: Fn(&I) -> O,
+    F: Fn(&I) -> O,
+{
+    let seed = seed_from_env();
+    let mut rng = Rng::new(seed);
+    let fails = |input: &I| reference(input) != optimized(input);
+    for case in 0..cases {
+        let input = generate(&mut rng);
+        if fails(&input) {
+            let input = shrink(input, fails);
+            panic!(
+                "{}: implementations disagree (AOC_SEED={}, case {})\n input: {:?}\n reference: {:?}\n optimized: {:?}",
+                name,
+                seed,
+                case,
+                input,
+                reference(&input),
+                optimized(&input)
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng() {
+        let mut rng = Rng::new(1);
+        let mut other = Rng::new(1);
+        assert_eq!(rng.next_u64(), other.next_u64());
+        for _ in 0..1000 {
+            let n = rng.range(-3, 3);
+            assert!((-3..=3).contains(&n));
+        }
+        assert_eq!(rng.range(5, 5), 5);
+        let mut items = (0..10).collect::<Vec<_>>();
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_generators() {
+        let mut rng = Rng::new(2);
+        let grid = grid(&mut rng, 3, 2, b".#");
+        assert_eq!(grid.lines().count(), 2);
+        assert!(grid.lines().all(|line| line.len() == 3));
+
+        let lines = number_lines(&mut rng, 4, 2, 1, 9);
+        assert!(lines.lines().all(|line| line.split(' ').count() == 2));
+
+        let netlist = netlist(&mut rng, 2, 6);
+        let (inputs, gates) = netlist.split_once("\n\n").unwrap();
+        assert_eq!(inputs.lines().count(), 4);
+        assert_eq!(gates.lines().count(), 6);
+        for z in ["z00", "z01", "z02"] {
+            assert!(gates.contains(&format!("-> {}", z)));
+        }
+
+        let program = opcode_program(&mut rng, 5);
+        assert_eq!(program.len(), 10);
+        assert!(program.iter().all(|&v| v < 8));
+    }
+
+    #[test]
+    fn test_shrink() {
+        assert_eq!(shrink(100u64, |&n| n >= 17), 17);
+        assert_eq!(shrink(-100i64, |&n| n <= -17), -17);
+        assert_eq!(
+            shrink(vec![5u64, 8, 1, 9], |v| v.iter().any(|&n| n >= 7)),
+            vec![7]
+        );
+    }
+
+    #[test]
+    fn test_differential() {
+        differential(
+            "sum",
+            50,
+            |rng| number_list(rng, 5, -9, 9),
+            |v| v.iter().sum::<i64>(),
+            |v| v.iter().rev().sum::<i64>(),
+        );
+
+        let result = std::panic::catch_unwind(|| {
+            differential(
+                "max",
+                50,
+                |rng| number_list(rng, 5, 0, 9),
+                |v| v.iter().max().copied(),
+                |v| v.first().copied(),
+            )
+        });
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,123 @@
+//! Writing graphs out for Graphviz and other graph tools.
+
+use crate::{Graph, NodeId};
+use std::fmt::Write;
+
+impl Graph {
+    // Every edge once, lower id first.
+    fn edges(&self) -> impl Iterator<Item = (NodeId, NodeId)> + '_ {
+        self.nodes().flat_map(move |a| {
+            self.neighbors(a)
+                .iter()
+                .filter(move |&&b| a < b)
+                .map(move |&b| (a, b))
+        })
+    }
+
+    /// The graph in Graphviz DOT.  The `highlight` nodes and the edges among them are drawn
+    /// in red.
+    pub fn to_dot(&self, name: &str, highlight: &[NodeId]) -> String {
+        let quote = |text: &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+        let lit = |id: &NodeId| highlight.contains(id);
+        let mut dot = format!("graph {} {{\n", quote(name));
+        for v in self.nodes() {
+            let style = if lit(&v) {
+                " [color=red, style=filled, fillcolor=mistyrose]"
+            } else {
+                ""
+            };
+            writeln!(dot, "  {}{};", quote(self.name(v)), style).unwrap();
+        }
+        for (a, b) in self.edges() {
+            let style = if lit(&a) && lit(&b) {
+                " [color=red, penwidth=2]"
+            } else {
+                ""
+            };
+            writeln!(
+                dot,
+                "  {} -- {}{};",
+                quote(self.name(a)),
+                quote(self.name(b)),
+                style
+            )
+            .unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// The graph in GraphML, with a `highlight` flag on nodes and edges.
+    pub fn to_graphml(&self, highlight: &[NodeId]) -> String {
+        let escape = |text: &str| {
+            text.replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+                .replace('"', "&quot;")
+        };
+        let lit = |id: &NodeId| highlight.contains(id);
+        let mut xml = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"highlight\" for=\"all\" attr.name=\"highlight\" attr.type=\"boolean\">\n",
+            "    <default>false</default>\n",
+            "  </key>\n",
+            "  <graph edgedefault=\"undirected\">\n",
+        ));
+        let data = |lit: bool| {
+            if lit {
+                "<data key=\"highlight\">true</data>"
+            } else {
+                ""
+            }
+        };
+        for v in self.nodes() {
+            writeln!(
+                xml,
+                "    <node id=\"{}\">{}</node>",
+                escape(self.name(v)),
+                data(lit(&v))
+            )
+            .unwrap();
+        }
+        for (a, b) in self.edges() {
+            writeln!(
+                xml,
+                "    <edge source=\"{}\" target=\"{}\">{}</edge>",
+                escape(self.name(a)),
+                escape(self.name(b)),
+                data(lit(&a) && lit(&b))
+            )
+            .unwrap();
+        }
+        xml.push_str("  </graph>\n</graphml>\n");
+        xml
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Graph;
+
+    #[test]
+    fn test_export() {
+        let graph = Graph::from_edges([("a", "b"), ("b", "c\"&")]).unwrap();
+        assert_eq!(
+            graph.to_dot("lan", &[0, 1]),
+            concat!(
+                "graph \"lan\" {\n",
+                "  \"a\" [color=red, style=filled, fillcolor=mistyrose];\n",
+                "  \"b\" [color=red, style=filled, fillcolor=mistyrose];\n",
+                "  \"c\\\"&\";\n",
+                "  \"a\" -- \"b\" [color=red, penwidth=2];\n",
+                "  \"b\" -- \"c\\\"&\";\n",
+                "}\n"
+            )
+        );
+        let xml = graph.to_graphml(&[1]);
+        assert!(xml.contains("<node id=\"b\"><data key=\"highlight\">true</data></node>"));
+        assert!(xml.contains("<node id=\"c&quot;&amp;\"></node>"));
+        assert!(xml.contains("<edge source=\"a\" target=\"b\"></edge>"));
+        assert!(xml.ends_with("</graphml>\n"));
+    }
+}
@@ -0,0 +1,483 @@
+//! Undirected graphs of named nodes, for network puzzles.
+//!
+//! Names are interned into [`NodeId`]s in the order they are first seen, and every node keeps
+//! both a neighbor list and a [`BitSet`] of its neighbors, so walks are cheap and so are the
+//! set operations of the clique search.
+
+mod bitset;
+mod export;
+
+pub use bitset::BitSet;
+
+use aoc::AocError;
+use std::collections::{HashMap, VecDeque};
+
+pub type NodeId = u16;
+
+#[derive(Clone, Debug, Default)]
+pub struct Graph {
+    names: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    neighbors: Vec<Vec<NodeId>>,
+    adjacency: Vec<BitSet>,
+    edges: usize,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_edges<'a>(
+        edges: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> aoc::Result<Self> {
+        let mut graph = Self::new();
+        for (a, b) in edges {
+            graph.add_edge(a, b)?;
+        }
+        Ok(graph)
+    }
+
+    /// The id of `name`, adding it if it is new.
+    pub fn add_node(&mut self, name: &str) -> aoc::Result<NodeId> {
+        if let Some(&id) = self.ids.get(name) {
+            return Ok(id);
+        }
+        let Ok(id) = NodeId::try_from(self.names.len()) else {
+            return Err(AocError::invalid_state(format!(
+                "too many nodes to add {:?}",
+                name
+            )));
+        };
+        self.names.push(name.to_owned());
+        self.ids.insert(name.to_owned(), id);
+        self.neighbors.push(vec![]);
+        self.adjacency.push(BitSet::new());
+        Ok(id)
+    }
+
+    /// Connects two nodes, adding them if they are new.  Returns whether the edge is new.
+    pub fn add_edge(&mut self, a: &str, b: &str) -> aoc::Result<bool> {
+        if a == b {
+            return Err(AocError::invalid_state(format!(
+                "{:?} can't be connected to itself",
+                a
+            )));
+        }
+        let (a, b) = (self.add_node(a)?, self.add_node(b)?);
+        if !self.adjacency[a as usize].insert(b as usize) {
+            return Ok(false);
+        }
+        self.adjacency[b as usize].insert(a as usize);
+        self.neighbors[a as usize].push(b);
+        self.neighbors[b as usize].push(a);
+        self.edges += 1;
+        Ok(true)
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        // Every id fits in a NodeId, but with all of them taken the length does not.
+        (0..self.names.len()).map(|id| id as NodeId)
+    }
+
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id as usize]
+    }
+
+    /// The names of `ids`, sorted.
+    pub fn sorted_names(&self, ids: &[NodeId]) -> Vec<&str> {
+        let mut names = ids.iter().map(|&id| self.name(id)).collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    pub fn neighbors(&self, id: NodeId) -> &[NodeId] {
+        &self.neighbors[id as usize]
+    }
+
+    pub fn adjacency(&self, id: NodeId) -> &BitSet {
+        &self.adjacency[id as usize]
+    }
+
+    pub fn degree(&self, id: NodeId) -> usize {
+        self.neighbors[id as usize].len()
+    }
+
+    pub fn has_edge(&self, a: NodeId, b: NodeId) -> bool {
+        self.adjacency[a as usize].contains(b as usize)
+    }
+
+    /// The order in which repeatedly removing a node of the lowest remaining degree removes
+    /// them, and the core number of each node, which is the largest such degree seen by the
+    /// time the node goes.  This is the bucket algorithm of Batagelj and Zaversnik.
+    fn peel(&self) -> (Vec<NodeId>, Vec<usize>) {
+        let n = self.len();
+        let mut degree = (0..n).map(|v| self.neighbors[v].len()).collect::<Vec<_>>();
+        let max = degree.iter().copied().max().unwrap_or(0);
+
+        // Nodes sorted by degree, with where each degree starts.
+        let mut start = vec![0; max + 2];
+        for &d in &degree {
+            start[d + 1] += 1;
+        }
+        for d in 1..start.len() {
+            start[d] += start[d - 1];
+        }
+        let mut order = vec![0; n];
+        let mut pos = vec![0; n];
+        let mut next = start.clone();
+        for v in 0..n {
+            pos[v] = next[degree[v]];
+            order[pos[v]] = v as NodeId;
+            next[degree[v]] += 1;
+        }
+
+        for i in 0..n {
+            let v = order[i] as usize;
+            for &u in &self.neighbors[v] {
+                let u = u as usize;
+                if degree[u] > degree[v] {
+                    // Swap u to the front of its bucket and move the bucket start past it.
+                    let du = degree[u];
+                    let (pu, pw) = (pos[u], start[du]);
+                    let w = order[pw] as usize;
+                    order.swap(pu, pw);
+                    (pos[u], pos[w]) = (pw, pu);
+                    start[du] += 1;
+                    degree[u] -= 1;
+                }
+            }
+        }
+        (order, degree)
+    }
+
+    /// Nodes in an order where each has at most the degeneracy many neighbors after it, and
+    /// the degeneracy.
+    pub fn degeneracy_order(&self) -> (Vec<NodeId>, usize) {
+        let (order, cores) = self.peel();
+        (order, cores.into_iter().max().unwrap_or(0))
+    }
+
+    /// The largest k for each node such that it is in a subgraph where every node has degree at
+    /// least k.
+    pub fn core_numbers(&self) -> Vec<usize> {
+        self.peel().1
+    }
+
+    /// Calls `f` with every maximal clique, found by Bron-Kerbosch with Tomita's pivot, starting
+    /// from each node in degeneracy order.
+    pub fn for_each_maximal_clique(&self, mut f: impl FnMut(&[NodeId])) {
+        let mut later = BitSet::full(self.len());
+        let mut clique = vec![];
+        for v in self.degeneracy_order().0 {
+            later.remove(v as usize);
+            let adjacent = self.adjacency(v);
+            clique.push(v);
+            self.expand(
+                &mut clique,
+                adjacent.intersection(&later),
+                adjacent.difference(&later),
+                &mut f,
+            );
+            clique.pop();
+        }
+    }
+
+    // Extends `clique` by the candidates in `p`, with `x` holding nodes already covered.
+    fn expand(
+        &self,
+        clique: &mut Vec<NodeId>,
+        mut p: BitSet,
+        mut x: BitSet,
+        f: &mut impl FnMut(&[NodeId]),
+    ) {
+        if p.is_empty() {
+            if x.is_empty() {
+                f(clique);
+            }
+            return;
+        }
+        // Any maximal clique holds the pivot or one of its non-neighbors.
+        let pivot = p
+            .iter()
+            .chain(x.iter())
+            .max_by_key(|&u| p.intersection_len(&self.adjacency[u]))
+            .unwrap();
+        for v in p.difference(&self.adjacency[pivot]).iter() {
+            let adjacent = &self.adjacency[v];
+            clique.push(v as NodeId);
+            self.expand(
+                clique,
+                p.intersection(adjacent),
+                x.intersection(adjacent),
+                f,
+            );
+            clique.pop();
+            p.remove(v);
+            x.insert(v);
+        }
+    }
+
+    /// Every maximal clique, each sorted by id.
+    pub fn maximal_cliques(&self) -> Vec<Vec<NodeId>> {
+        let mut cliques = vec![];
+        self.for_each_maximal_clique(|clique| {
+            let mut clique = clique.to_vec();
+            clique.sort();
+            cliques.push(clique);
+        });
+        cliques
+    }
+
+    /// A largest clique, sorted by id.  The search skips branches that can't beat the best
+    /// clique found so far.
+    pub fn maximum_clique(&self) -> Vec<NodeId> {
+        fn search(graph: &Graph, clique: &mut Vec<NodeId>, p: BitSet, best: &mut Vec<NodeId>) {
+            if p.is_empty() {
+                if clique.len() > best.len() {
+                    best.clone_from(clique);
+                }
+                return;
+            }
+            let mut p = p;
+            while !p.is_empty() && clique.len() + p.len() > best.len() {
+                let v = p.iter().next().unwrap();
+                p.remove(v);
+                clique.push(v as NodeId);
+                search(graph, clique, p.intersection(&graph.adjacency[v]), best);
+                clique.pop();
+            }
+        }
+
+        let mut best = vec![];
+        let mut later = BitSet::full(self.len());
+        for v in self.degeneracy_order().0 {
+            later.remove(v as usize);
+            let p = self.adjacency(v).intersection(&later);
+            if p.len() < best.len() {
+                continue;
+            }
+            search(self, &mut vec![v], p, &mut best);
+        }
+        best.sort();
+        best
+    }
+
+    /// Calls `f` with every triangle once.  Edges point from the lower to the higher of
+    /// (degree, id), so every node has at most about sqrt(2m) out-neighbors and the search takes
+    /// O(m sqrt m) time.
+    pub fn for_each_triangle(&self, mut f: impl FnMut([NodeId; 3])) {
+        let rank = |v: NodeId| (self.degree(v), v);
+        let out = self
+            .nodes()
+            .map(|v| {
+                self.neighbors(v)
+                    .iter()
+                    .copied()
+                    .filter(|&u| rank(u) > rank(v))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        for u in self.nodes() {
+            for &v in &out[u as usize] {
+                for &w in &out[v as usize] {
+                    if self.has_edge(u, w) {
+                        f([u, v, w]);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn triangles(&self) -> Vec<[NodeId; 3]> {
+        let mut triangles = vec![];
+        self.for_each_triangle(|triangle| triangles.push(triangle));
+        triangles
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        let mut count = 0;
+        self.for_each_triangle(|_| count += 1);
+        count
+    }
+
+    /// The connected components, each in the order a breadth-first search reaches it.
+    pub fn components(&self) -> Vec<Vec<NodeId>> {
+        let mut seen = BitSet::new();
+        let mut components = vec![];
+        for start in self.nodes() {
+            if !seen.insert(start as usize) {
+                continue;
+            }
+            let mut component = vec![];
+            let mut queue = VecDeque::from([start]);
+            while let Some(v) = queue.pop_front() {
+                component.push(v);
+                for &u in self.neighbors(v) {
+                    if seen.insert(u as usize) {
+                        queue.push_back(u);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::testing::Rng;
+
+    fn random_graph(rng: &mut Rng, nodes: usize, percent: usize) -> Graph {
+        let mut graph = Graph::new();
+        for a in 0..nodes {
+            graph.add_node(&a.to_string()).unwrap();
+            for b in 0..a {
+                if rng.usize(1, 100) <= percent {
+                    graph.add_edge(&a.to_string(), &b.to_string()).unwrap();
+                }
+            }
+        }
+        graph
+    }
+
+    // Whether `nodes` are all connected to each other.
+    fn is_clique(graph: &Graph, nodes: &[NodeId]) -> bool {
+        nodes
+            .iter()
+            .enumerate()
+            .all(|(i, &a)| nodes[..i].iter().all(|&b| graph.has_edge(a, b)))
+    }
+
+    #[test]
+    fn test_bitset() {
+        let mut set = BitSet::new();
+        assert!(set.insert(3));
+        assert!(!set.insert(3));
+        assert!(set.insert(130));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [3, 130]);
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(130) && !set.contains(4) && !set.contains(1000));
+        let other = [3, 4, 64].into_iter().collect::<BitSet>();
+        assert_eq!(set.intersection(&other).iter().collect::<Vec<_>>(), [3]);
+        assert_eq!(set.intersection_len(&other), 1);
+        assert_eq!(set.difference(&other).iter().collect::<Vec<_>>(), [130]);
+        assert_eq!(other.difference(&set).iter().collect::<Vec<_>>(), [4, 64]);
+        assert!(set.remove(3) && !set.remove(3) && !set.remove(1000));
+        assert_eq!(BitSet::full(65).len(), 65);
+        assert!(BitSet::full(0).is_empty());
+    }
+
+    #[test]
+    fn test_graph() {
+        let mut graph =
+            Graph::from_edges([("a", "b"), ("b", "c"), ("c", "a"), ("d", "e")]).unwrap();
+        assert!(!graph.add_edge("b", "a").unwrap());
+        assert!(graph.add_edge("a", "a").is_err());
+        graph.add_node("f").unwrap();
+        assert_eq!((graph.len(), graph.edge_count()), (6, 4));
+        assert_eq!(graph.id("c"), Some(2));
+        assert_eq!(graph.name(3), "d");
+        assert_eq!(graph.sorted_names(&[2, 0]), ["a", "c"]);
+        assert_eq!(graph.triangles(), [[0, 1, 2]]);
+        assert_eq!(graph.components(), [vec![0, 1, 2], vec![3, 4], vec![5]]);
+        assert_eq!(graph.degeneracy_order().1, 2);
+        assert_eq!(graph.maximum_clique(), [0, 1, 2]);
+        let mut cliques = graph.maximal_cliques();
+        cliques.sort();
+        assert_eq!(cliques, [vec![0, 1, 2], vec![3, 4], vec![5]]);
+
+        let mut graph = Graph::new();
+        for i in 0..=NodeId::MAX {
+            graph.add_node(&i.to_string()).unwrap();
+        }
+        assert_eq!(graph.nodes().count(), 1 << 16);
+        assert_eq!(graph.nodes().last(), Some(NodeId::MAX));
+        assert!(graph.add_node("one more").is_err());
+        assert_eq!(graph.add_node("0").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_random_graphs() {
+        let mut rng = Rng::from_env();
+        for _ in 0..30 {
+            let nodes = rng.usize(1, 14);
+            let percent = rng.usize(10, 90);
+            let graph = random_graph(&mut rng, nodes, percent);
+
+            // Every subset of nodes, as bits.
+            let subsets = (0u32..1 << nodes)
+                .map(|bits| {
+                    (0..nodes as NodeId)
+                        .filter(|&v| bits >> v & 1 == 1)
+                        .collect::<Vec<_>>()
+                })
+                .filter(|nodes| is_clique(&graph, nodes))
+                .collect::<Vec<_>>();
+            let maximal = subsets
+                .iter()
+                .filter(|clique| {
+                    graph.nodes().all(|v| {
+                        clique.contains(&v) || !clique.iter().all(|&u| graph.has_edge(u, v))
+                    })
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            let mut cliques = graph.maximal_cliques();
+            cliques.sort();
+            let mut expected = maximal.clone();
+            expected.sort();
+            assert_eq!(cliques, expected);
+            let largest = subsets.iter().map(|clique| clique.len()).max().unwrap();
+            let best = graph.maximum_clique();
+            assert!(is_clique(&graph, &best));
+            assert_eq!(best.len(), largest);
+            assert_eq!(
+                graph.triangle_count(),
+                subsets.iter().filter(|clique| clique.len() == 3).count()
+            );
+
+            // Each node has at most the degeneracy many neighbors after it.
+            let (order, degeneracy) = graph.degeneracy_order();
+            for (i, &v) in order.iter().enumerate() {
+                let after = order[i + 1..]
+                    .iter()
+                    .filter(|&&u| graph.has_edge(u, v))
+                    .count();
+                assert!(after <= degeneracy);
+            }
+            // The k-core left after dropping lower cores has minimum degree k.
+            let cores = graph.core_numbers();
+            assert_eq!(cores.iter().max().copied().unwrap_or(0), degeneracy);
+            for v in graph.nodes() {
+                let k = cores[v as usize];
+                let inside = graph
+                    .neighbors(v)
+                    .iter()
+                    .filter(|&&u| cores[u as usize] >= k);
+                assert!(inside.count() >= k);
+            }
+
+            let components = graph.components();
+            assert_eq!(components.iter().map(Vec::len).sum::<usize>(), nodes);
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use aoc::AocError;
 use std::ops::*;
 
 #[derive(Clone, Debug)]
@@ -46,6 +47,44 @@ impl<T> Grid<T> {
         }
     }
 
+    /// Parses a rectangular grid of bytes, mapping each byte to a cell with `f` and reporting
+    /// the line and column of ragged rows or unexpected bytes.
+    pub fn parse<F>(data: &str, f: F) -> aoc::Result<Self>
+    where
+        F: Fn(u8) -> Option<T>,
+    {
+        let lines = data.lines().collect::<Vec<_>>();
+        let Some(first) = lines.first() else {
+            return Err(AocError::parse(1, 1, "empty grid"));
+        };
+        let x_size = first.len();
+        let mut grid = Vec::with_capacity(x_size * lines.len());
+        for (y, line) in lines.iter().enumerate() {
+            if line.len() != x_size {
+                return Err(AocError::parse(
+                    y + 1,
+                    std::cmp::min(line.len(), x_size) + 1,
+                    format!("expected row of length {}, found {}", x_size, line.len()),
+                ));
+            }
+            for (x, b) in line.bytes().enumerate() {
+                let cell = f(b).ok_or_else(|| {
+                    AocError::parse(
+                        y + 1,
+                        x + 1,
+                        format!("unexpected character {:?}", b as char),
+                    )
+                })?;
+                grid.push(cell);
+            }
+        }
+        Ok(Self {
+            x_size,
+            y_size: lines.len(),
+            grid,
+        })
+    }
+
     pub fn from_iter<I>(x_size: usize, y_size: usize, iter: I) -> Self
     where
         I: Iterator<Item = T>,
@@ -309,13 +348,13 @@ impl Direction {
         }
     }
 
-    pub fn from(c: u8) -> Self {
+    pub fn from(c: u8) -> Option<Self> {
         match c {
-            b'^' => UP,
-            b'>' => RIGHT,
-            b'v' => DOWN,
-            b'<' => LEFT,
-            _ => panic!(),
+            b'^' => Some(UP),
+            b'>' => Some(RIGHT),
+            b'v' => Some(DOWN),
+            b'<' => Some(LEFT),
+            _ => None,
         }
     }
 
@@ -0,0 +1,138 @@
+//! JavaScript bindings for the solutions, built with `wasm-pack`.
+//!
+//! Everything takes the puzzle input as a string.  Parameters use the same `name = value` lines
+//! as the example sidecars, for example `"width = 11\nheight = 7"` for the day 14 example.
+
+use aoc::{AocError, Params, Solution};
+use wasm_bindgen::prelude::*;
+
+pub fn solution(day: u8) -> Option<&'static dyn Solution> {
+    let solution: &'static dyn Solution = match day {
+        1 => &aoc01::Day01,
+        2 => &aoc02::Day02,
+        3 => &aoc03::Day03,
+        4 => &aoc04::Day04,
+        5 => &aoc05::Day05,
+        6 => &aoc06::Day06,
+        7 => &aoc07::Day07,
+        8 => &aoc08::Day08,
+        9 => &aoc09::Day09,
+        10 => &aoc10::Day10,
+        11 => &aoc11::Day11,
+        12 => &aoc12::Day12,
+        13 => &aoc13::Day13,
+        14 => &aoc14::Day14,
+        15 => &aoc15::Day15,
+        16 => &aoc16::Day16,
+        17 => &aoc17::Day17,
+        18 => &aoc18::Day18,
+        19 => &aoc19::Day19,
+        20 => &aoc20::Day20,
+        21 => &aoc21::Day21,
+        22 => &aoc22::Day22,
+        23 => &aoc23::Day23,
+        24 => &aoc24::Day24,
+        25 => &aoc25::Day25,
+        _ => return None,
+    };
+    Some(solution)
+}
+
+pub fn solve_part(day: u8, part: u8, input: &str, params: &str) -> aoc::Result<String> {
+    let solution =
+        solution(day).ok_or_else(|| AocError::invalid_state(format!("no day {}", day)))?;
+    solution.part(part, input, &Params::parse(params)?)
+}
+
+/// The robots of day 14 after `seconds` seconds, one row of the room per line.
+pub fn robots_frame(input: &str, params: &str, seconds: i32) -> aoc::Result<String> {
+    let size = aoc14::room_size(&Params::parse(params)?)?;
+    let robots = aoc14::parse_input(input, &size)?;
+    let robots = aoc14::traverse_robots(&robots, seconds, &size).collect::<Vec<_>>();
+    Ok(aoc14::pretty_print_robots(&robots, &size))
+}
+
+/// The day 15 warehouse after the robot's first `moves` moves, in the doubled warehouse of part
+/// 2 if `wide` is set.
+pub fn warehouse_frame(input: &str, wide: bool, moves: usize) -> aoc::Result<String> {
+    let (mut state, movements) = aoc15::parse_input(input)?;
+    if wide {
+        state = aoc15::State::double_from(&state);
+    }
+    state.move_robot_multiple(&movements[..moves.min(movements.len())]);
+    Ok(state.pretty_print_with_robot())
+}
+
+/// The number of robot moves in a day 15 input, for stepping through its frames.
+pub fn warehouse_moves(input: &str) -> aoc::Result<usize> {
+    let (_, movements) = aoc15::parse_input(input)?;
+    Ok(movements.len())
+}
+
+fn js_error(err: AocError) -> JsError {
+    JsError::new(&err.to_string())
+}
+
+/// Returns the answer to `part` of `day` for `input`.
+#[wasm_bindgen]
+pub fn solve(day: u8, part: u8, input: &str, params: &str) -> Result<String, JsError> {
+    solve_part(day, part, input, params).map_err(js_error)
+}
+
+#[wasm_bindgen(js_name = robotsFrame)]
+pub fn robots_frame_js(input: &str, params: &str, seconds: i32) -> Result<String, JsError> {
+    robots_frame(input, params, seconds).map_err(js_error)
+}
+
+#[wasm_bindgen(js_name = warehouseFrame)]
+pub fn warehouse_frame_js(input: &str, wide: bool, moves: usize) -> Result<String, JsError> {
+    warehouse_frame(input, wide, moves).map_err(js_error)
+}
+
+#[wasm_bindgen(js_name = warehouseMoves)]
+pub fn warehouse_moves_js(input: &str) -> Result<usize, JsError> {
+    warehouse_moves(input).map_err(js_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROBOTS: &str = include_str!("../../examples/14/test.txt");
+    const WAREHOUSE: &str = include_str!("../../examples/15/small.txt");
+
+    #[test]
+    fn test_solve_part() {
+        let input = include_str!("../../examples/01/test.txt");
+        assert_eq!(solve_part(1, 1, input, "").unwrap(), "11");
+        assert_eq!(solve_part(1, 2, input, "").unwrap(), "31");
+        assert_eq!(
+            solve_part(14, 1, ROBOTS, "width = 11\nheight = 7").unwrap(),
+            "12"
+        );
+        assert!(solve_part(26, 1, input, "").is_err());
+        assert!(solve_part(1, 3, input, "").is_err());
+        assert!(solve_part(1, 1, "1 x", "").is_err());
+    }
+
+    #[test]
+    fn test_robots_frame() {
+        let frame = robots_frame(ROBOTS, "width = 11\nheight = 7", 100).unwrap();
+        assert_eq!(frame.lines().count(), 7);
+        assert!(frame.lines().all(|line| line.len() == 11));
+        assert!(robots_frame(ROBOTS, "width = 3\nheight = 3", 0).is_err());
+    }
+
+    #[test]
+    fn test_warehouse_frame() {
+        assert_eq!(warehouse_moves(WAREHOUSE).unwrap(), 15);
+        assert_eq!(
+            warehouse_frame(WAREHOUSE, false, 0).unwrap(),
+            WAREHOUSE.split("\n\n").next().unwrap()
+        );
+        let last = warehouse_frame(WAREHOUSE, false, usize::MAX).unwrap();
+        assert!(last.starts_with("########\n#....OO#\n##.....#"));
+        let wide = warehouse_frame(WAREHOUSE, true, 0).unwrap();
+        assert!(wide.lines().all(|line| line.len() == 16));
+    }
+}
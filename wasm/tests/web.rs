@@ -0,0 +1,25 @@
+//! Run with `wasm-pack test --node wasm`.
+#![cfg(target_arch = "wasm32")]
+
+use aoc_wasm::{robots_frame_js, solve, warehouse_frame_js, warehouse_moves_js};
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn solve_example() {
+    let input = include_str!("../../examples/01/test.txt");
+    assert_eq!(solve(1, 1, input, "").unwrap(), "11");
+    assert_eq!(solve(1, 2, input, "").unwrap(), "31");
+    assert!(solve(26, 1, input, "").is_err());
+}
+
+#[wasm_bindgen_test]
+fn render_frames() {
+    let robots = include_str!("../../examples/14/test.txt");
+    let frame = robots_frame_js(robots, "width = 11\nheight = 7", 100).unwrap();
+    assert_eq!(frame.lines().count(), 7);
+
+    let warehouse = include_str!("../../examples/15/small.txt");
+    let moves = warehouse_moves_js(warehouse).unwrap();
+    let frame = warehouse_frame_js(warehouse, false, moves).unwrap();
+    assert!(frame.starts_with("########\n#....OO#"));
+}